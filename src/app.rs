@@ -1,3 +1,7 @@
+use crate::bazaar::auction_client::{AuctionHouseClient, SharedAuctionClient};
+use crate::bazaar::auction_sources::AuctionSource;
+use crate::bazaar::bazaar_client::{BazaarClient, SharedBazaarClient};
+use crate::bazaar::price_sources::PriceSource;
 use crate::catacombs::catacombs_page::CatacombsLootPage;
 use crate::slayer::slayer_page::SlayerLootPage;
 use crate::shards::shards_page::ShardsPage;
@@ -7,6 +11,7 @@ use egui_extras::image::load_image_bytes;
 use include_dir::{include_dir, Dir};
 use std::collections::HashMap;
 use std::rc::Rc;
+use web_time::{Duration, Instant};
 use eframe::epaint::text::{TextFormat, TextWrapMode};
 use egui::text::LayoutJob;
 use num_format::Locale::is;
@@ -51,6 +56,8 @@ pub struct CalculatorApp {
     shards_page: ShardsPage,
 
     images: Rc<HashMap<String, TextureHandle>>,
+    bazaar: SharedBazaarClient,
+    auctions: SharedAuctionClient,
 }
 
 impl eframe::App for CalculatorApp {
@@ -70,6 +77,14 @@ impl eframe::App for CalculatorApp {
 
         ctx.set_theme(ThemePreference::Dark);
 
+        let system_time = Instant::now();
+        if self.bazaar.borrow_mut().poll(system_time) | self.auctions.borrow_mut().poll(system_time) {
+            ctx.request_repaint();
+        } else if self.bazaar.borrow().is_fetching() || self.auctions.borrow().is_fetching() {
+            // nothing else wakes the ui up when the response arrives
+            ctx.request_repaint_after(Duration::from_millis(250));
+        }
+
         egui::TopBottomPanel::top("top_panel")
             .frame(egui::Frame::new().inner_margin(4))
             .show(ctx, |ui| {
@@ -124,21 +139,39 @@ impl eframe::App for CalculatorApp {
     }
 
     /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.catacombs_page.save(storage);
+        self.shards_page.save(storage);
+        self.slayer_page.save(storage);
+    }
 }
 
 impl CalculatorApp {
-    pub fn new(context: &Context) -> Self {
+    pub fn new(context: &Context, storage: Option<&dyn eframe::Storage>) -> Self {
         let images = Rc::new(load_images(context));
+        let bazaar = BazaarClient::new_shared();
+        let auctions = AuctionHouseClient::new_shared();
         Self {
             selected_page: Page::Shards,
-            catacombs_page: CatacombsLootPage::new(Rc::clone(&images)),
-            slayer_page: SlayerLootPage::new(Rc::clone(&images)),
-            shards_page: ShardsPage::new(Rc::clone(&images)),
+            catacombs_page: CatacombsLootPage::new(Rc::clone(&images), Rc::clone(&bazaar), Rc::clone(&auctions), storage),
+            slayer_page: SlayerLootPage::new(Rc::clone(&images), Rc::clone(&bazaar), Rc::clone(&auctions), storage),
+            shards_page: ShardsPage::new(Rc::clone(&images), Rc::clone(&bazaar), storage),
             images,
+            bazaar,
+            auctions,
         }
     }
 
+    /// Loads prices from somewhere other than the live bazaar, like a local mock server or a saved response.
+    pub fn set_price_source(&mut self, price_source: Box<dyn PriceSource>) {
+        self.bazaar.borrow_mut().set_source(price_source);
+    }
+
+    /// Same as `set_price_source`, for auction house prices.
+    pub fn set_auction_source(&mut self, auction_source: Box<dyn AuctionSource>) {
+        self.auctions.borrow_mut().set_source(auction_source);
+    }
+
     fn show_selected_page(&mut self, ctx: &Context, frame: &mut eframe::Frame) {
         let selected_page = self.selected_page;
         for (_name, page, app) in self.apps_iter_mut() {
@@ -0,0 +1,7 @@
+pub mod auction_client;
+pub mod auction_data;
+pub mod auction_sources;
+pub mod bazaar_client;
+pub mod bazaar_data;
+pub mod fetch_client;
+pub mod price_sources;
@@ -0,0 +1,102 @@
+use crate::bazaar::auction_data::{AuctionData, AuctionPrice};
+use crate::bazaar::auction_sources::{AuctionSource, HypixelAuctionSource};
+use crate::bazaar::bazaar_client::{self, BazaarClient, SharedBazaarClient};
+use crate::bazaar::bazaar_data::ProfitType;
+use crate::bazaar::fetch_client::{self, DataSource, FetchClient, FetchFuture, SharedFetchClient};
+use egui::{Color32, Context, Response, RichText, Ui};
+use num_format::Locale::en;
+use num_format::ToFormattedString;
+use web_time::{Duration, Instant};
+
+/// Every refresh downloads every page of the auction house, so it's spaced out a lot more than bazaar refreshes.
+pub const AUCTION_REFRESH_COOLDOWN: Duration = Duration::from_secs(300);
+
+pub type AuctionHouseClient = FetchClient<dyn AuctionSource>;
+/// Shared by every page that prices auction house items, like the bazaar client.
+pub type SharedAuctionClient = SharedFetchClient<dyn AuctionSource>;
+
+impl DataSource for dyn AuctionSource {
+    type Data = AuctionData;
+
+    fn get_name(&self) -> String {
+        AuctionSource::get_name(self)
+    }
+
+    fn load(&self) -> FetchFuture<AuctionData> {
+        self.load_auctions()
+    }
+}
+
+impl Default for AuctionHouseClient {
+    fn default() -> Self {
+        Self::new(Box::new(HypixelAuctionSource), AUCTION_REFRESH_COOLDOWN)
+    }
+}
+
+impl AuctionHouseClient {
+    pub fn get_auction_price(&self, item_id: &str) -> Option<&AuctionPrice> {
+        self.get_data().and_then(|data| data.get(item_id))
+    }
+}
+
+/// What an item is worth: its insta-sell price if it's on the bazaar, otherwise what it goes for on the auction house.
+pub fn get_item_value(bazaar: &BazaarClient, auctions: &AuctionHouseClient, bazaar_id: Option<&str>, auction_id: Option<&str>) -> Option<f64> {
+    bazaar_id
+        .and_then(|bazaar_id| bazaar.get_sell_price(bazaar_id, ProfitType::InstaSell))
+        .or_else(|| auction_id.and_then(|auction_id| auctions.get_auction_price(auction_id)?.get_price()))
+}
+
+/// Auction prices are only fetched when asked for, since it downloads every page of the auction house.
+pub fn add_auction_refresh_button(auctions: &SharedAuctionClient, ctx: &Context, ui: &mut Ui, system_time: Instant) {
+    fetch_client::add_refresh_button(auctions, "auctions", ctx, ui, system_time, |ui, auctions| {
+        let text = if auctions.has_data() { "Refresh Auctions" } else { "Load Auction Prices" };
+        ui.button(text)
+            .on_hover_text(format!("Downloads every page of the auction house from {}", auctions.get_source_name()))
+    });
+}
+
+/// Shows bazaar prices for items sold there, and auction house prices for everything else.
+pub fn add_item_price_hover(
+    response: Response,
+    bazaar: &SharedBazaarClient,
+    auctions: &SharedAuctionClient,
+    bazaar_id: Option<&str>,
+    auction_id: Option<&str>,
+) -> Response {
+    let on_bazaar = bazaar_id.is_some_and(|bazaar_id| bazaar.borrow().get_product(bazaar_id).is_some());
+    if on_bazaar || auction_id.is_none() {
+        return bazaar_client::add_price_hover(response, bazaar, bazaar_id);
+    }
+
+    response.on_hover_ui(|ui| {
+        let auctions = auctions.borrow();
+        match auction_id.and_then(|auction_id| auctions.get_auction_price(auction_id)) {
+            Some(price) => {
+                let prices = [
+                    ("Lowest BIN", price.lowest_bin, format!("{} listed", price.bin_count)),
+                    ("Median Sold", price.median_sold, format!("{} recent sales", price.sold_count)),
+                ];
+                for (label, price, count) in prices {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{label}:"));
+                        match price {
+                            Some(price) => {
+                                ui.label(RichText::new((price as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                                ui.label(RichText::new(format!("({count})")).color(Color32::GRAY));
+                            }
+                            None => {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            }
+                        }
+                    });
+                }
+            }
+            None if !auctions.has_data() => {
+                ui.label(RichText::new("Loading auction prices...").color(Color32::GRAY));
+            }
+            None => {
+                ui.label(RichText::new("Not on the auction house").color(Color32::GRAY));
+            }
+        }
+    })
+}
@@ -0,0 +1,284 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+
+// key: item id (see get_pet_auction_id for pets)
+pub type AuctionData = HashMap<String, AuctionPrice>;
+
+/// One page of the active auctions API (`/skyblock/auctions?page=N`).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuctionsPage {
+    pub total_pages: u32,
+    pub auctions: Vec<Auction>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Auction {
+    pub starting_bid: u64,
+    #[serde(default)]
+    pub bin: bool,
+    pub item_bytes: String,
+}
+
+/// Auctions that ended in the last minute (`/skyblock/auctions_ended`).
+#[derive(Debug, Deserialize)]
+pub struct EndedAuctionsResponse {
+    pub auctions: Vec<EndedAuction>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EndedAuction {
+    pub price: u64,
+    pub item_bytes: String,
+}
+
+/// Any response a saved auction dump can contain, told apart by their fields.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum AuctionResponse {
+    Page(AuctionsPage),
+    Ended(EndedAuctionsResponse),
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AuctionPrice {
+    /// The cheapest Buy It Now auction per item, or None if there aren't any right now.
+    pub lowest_bin: Option<f64>,
+    /// The median price per item of the auctions that recently sold, or None if none did.
+    pub median_sold: Option<f64>,
+    pub bin_count: u32,
+    pub sold_count: u32,
+}
+
+impl AuctionPrice {
+    /// Lowest BIN is what it can actually be bought for now, so it wins when both are known.
+    pub fn get_price(&self) -> Option<f64> {
+        self.lowest_bin.or(self.median_sold)
+    }
+}
+
+/// The auction house has no item ids, so pets are keyed by type and rarity, like `PET_SPIRIT_LEGENDARY`.
+pub fn get_pet_auction_id(pet: &str, tier: &str) -> String {
+    format!("PET_{}_{}", pet.to_uppercase(), tier.to_uppercase())
+}
+
+/// Groups the auctions by item id. Auctions whose item couldn't be read are skipped.
+pub fn calculate_auction_prices(pages: &[AuctionsPage], ended: &[EndedAuctionsResponse]) -> AuctionData {
+    let mut data = AuctionData::new();
+
+    for auction in pages.iter().flat_map(|p| p.auctions.iter()).filter(|a| a.bin) {
+        if let Some((item_id, count)) = read_item_bytes(&auction.item_bytes) {
+            let price = data.entry(item_id).or_default();
+            let unit_price = auction.starting_bid as f64 / count as f64;
+            price.lowest_bin = Some(price.lowest_bin.map_or(unit_price, |lowest| lowest.min(unit_price)));
+            price.bin_count += 1;
+        }
+    }
+
+    let mut sold_prices: HashMap<String, Vec<f64>> = HashMap::new();
+    for auction in ended.iter().flat_map(|e| e.auctions.iter()) {
+        if let Some((item_id, count)) = read_item_bytes(&auction.item_bytes) {
+            sold_prices.entry(item_id).or_default().push(auction.price as f64 / count as f64);
+        }
+    }
+    for (item_id, mut prices) in sold_prices {
+        prices.sort_by(|a, b| a.total_cmp(b));
+        let middle = prices.len() / 2;
+        let median = if prices.len() % 2 == 0 {
+            (prices[middle - 1] + prices[middle]) / 2.0
+        } else {
+            prices[middle]
+        };
+
+        let price = data.entry(item_id).or_default();
+        price.median_sold = Some(median);
+        price.sold_count = prices.len() as u32;
+    }
+
+    data
+}
+
+/// Reads the item id and stack size out of an auction's `item_bytes`, which is base64 encoded, gzipped NBT.
+pub fn read_item_bytes(item_bytes: &str) -> Option<(String, u32)> {
+    read_inventory_bytes(item_bytes)?.into_iter().next()
+}
+
+/// Reads every item out of base64 encoded, gzipped NBT, like auctions and the inventories of a SkyBlock profile. Empty
+/// slots and items without a SkyBlock id are skipped.
+pub fn read_inventory_bytes(inventory_bytes: &str) -> Option<Vec<(String, u32)>> {
+    let compressed = BASE64_STANDARD.decode(inventory_bytes.trim()).ok()?;
+    let mut bytes = Vec::new();
+    GzDecoder::new(compressed.as_slice()).read_to_end(&mut bytes).ok()?;
+
+    let root = NbtReader { bytes: &bytes, position: 0, depth: 0 }.read_root()?;
+    Some(root.get("i")?.as_list()?.iter().filter_map(read_item).collect())
+}
+
+fn read_item(item: &Nbt) -> Option<(String, u32)> {
+    let count = item.get("Count").and_then(|c| c.as_number()).unwrap_or(1).max(1) as u32;
+    let extra_attributes = item.get("tag")?.get("ExtraAttributes")?;
+    let id = extra_attributes.get("id")?.as_string()?;
+
+    if id == "PET" {
+        let pet_info = extra_attributes.get("petInfo")?.as_string()?;
+        let pet_info: PetInfo = serde_json::from_str(pet_info).ok()?;
+        Some((get_pet_auction_id(&pet_info.pet_type, &pet_info.tier), count))
+    } else {
+        Some((id.to_string(), count))
+    }
+}
+
+#[derive(Deserialize)]
+struct PetInfo {
+    #[serde(rename = "type")]
+    pet_type: String,
+    tier: String,
+}
+
+/// Only what's needed to find the item id; every other tag is read past and kept as `Other`.
+enum Nbt {
+    Number(i64),
+    String(String),
+    List(Vec<Nbt>),
+    Compound(Vec<(String, Nbt)>),
+    Other,
+}
+
+impl Nbt {
+    fn get(&self, name: &str) -> Option<&Nbt> {
+        match self {
+            Nbt::Compound(tags) => tags.iter().find(|(n, _)| n == name).map(|(_, tag)| tag),
+            _ => None,
+        }
+    }
+
+    fn as_list(&self) -> Option<&Vec<Nbt>> {
+        match self {
+            Nbt::List(tags) => Some(tags),
+            _ => None,
+        }
+    }
+
+    fn as_string(&self) -> Option<&str> {
+        match self {
+            Nbt::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    fn as_number(&self) -> Option<i64> {
+        match self {
+            Nbt::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+}
+
+/// How deep lists and compounds can be nested before the NBT is treated as broken, the same limit as the game's. Keeps
+/// crafted item bytes from overflowing the stack.
+const MAX_NBT_DEPTH: usize = 512;
+
+struct NbtReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+    /// How many lists and compounds the tag being read is inside of.
+    depth: usize,
+}
+
+impl NbtReader<'_> {
+    fn read_root(&mut self) -> Option<Nbt> {
+        let tag_type = self.read_bytes(1)?[0];
+        self.read_string()?;
+        self.read_tag(tag_type)
+    }
+
+    fn read_bytes(&mut self, length: usize) -> Option<&[u8]> {
+        let end = self.position.checked_add(length)?;
+        let bytes = self.bytes.get(self.position..end)?;
+        self.position += length;
+        Some(bytes)
+    }
+
+    fn read_number(&mut self, length: usize) -> Option<i64> {
+        let bytes = self.read_bytes(length)?;
+        // big endian and signed, so the first byte keeps its sign
+        let mut number = bytes[0] as i8 as i64;
+        for byte in &bytes[1..] {
+            number = (number << 8) | *byte as i64;
+        }
+        Some(number)
+    }
+
+    fn read_length(&mut self) -> Option<usize> {
+        usize::try_from(self.read_number(4)?).ok()
+    }
+
+    fn read_string(&mut self) -> Option<String> {
+        let length = self.read_number(2)? as u16 as usize;
+        // java's modified utf-8 only differs for null and 4 byte characters, which item ids don't use
+        Some(String::from_utf8_lossy(self.read_bytes(length)?).into_owned())
+    }
+
+    fn read_tag(&mut self, tag_type: u8) -> Option<Nbt> {
+        Some(match tag_type {
+            1 => Nbt::Number(self.read_number(1)?),
+            2 => Nbt::Number(self.read_number(2)?),
+            3 => Nbt::Number(self.read_number(4)?),
+            4 => Nbt::Number(self.read_number(8)?),
+            5 => {
+                self.read_bytes(4)?;
+                Nbt::Other
+            }
+            6 => {
+                self.read_bytes(8)?;
+                Nbt::Other
+            }
+            7 => {
+                let length = self.read_length()?;
+                self.read_bytes(length)?;
+                Nbt::Other
+            }
+            8 => Nbt::String(self.read_string()?),
+            9 | 10 if self.depth >= MAX_NBT_DEPTH => return None,
+            9 => {
+                let element_type = self.read_bytes(1)?[0];
+                let length = self.read_length()?;
+                let mut tags = Vec::new();
+                self.depth += 1;
+                for _ in 0..length {
+                    tags.push(self.read_tag(element_type)?);
+                }
+                self.depth -= 1;
+                Nbt::List(tags)
+            }
+            10 => {
+                let mut tags = Vec::new();
+                self.depth += 1;
+                loop {
+                    let tag_type = self.read_bytes(1)?[0];
+                    if tag_type == 0 {
+                        break;
+                    }
+                    let name = self.read_string()?;
+                    tags.push((name, self.read_tag(tag_type)?));
+                }
+                self.depth -= 1;
+                Nbt::Compound(tags)
+            }
+            11 => {
+                let length = self.read_length()?;
+                self.read_bytes(length.checked_mul(4)?)?;
+                Nbt::Other
+            }
+            12 => {
+                let length = self.read_length()?;
+                self.read_bytes(length.checked_mul(8)?)?;
+                Nbt::Other
+            }
+            _ => return None,
+        })
+    }
+}
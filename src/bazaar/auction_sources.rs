@@ -0,0 +1,142 @@
+use crate::bazaar::auction_data::{calculate_auction_prices, AuctionData, AuctionResponse, AuctionsPage, EndedAuctionsResponse};
+use crate::bazaar::fetch_client::FetchFuture;
+use reqwest::Client;
+
+pub const AUCTIONS_URL: &str = "https://api.hypixel.net/skyblock/auctions";
+pub const AUCTIONS_ENDED_URL: &str = "https://api.hypixel.net/skyblock/auctions_ended";
+
+pub type AuctionFuture = FetchFuture<AuctionData>;
+
+/// Somewhere auction house listings can be loaded from.
+pub trait AuctionSource {
+    fn get_name(&self) -> String;
+
+    /// Resolves to the prices of every item listed, or None if loading failed.
+    fn load_auctions(&self) -> AuctionFuture;
+}
+
+/// The live Hypixel auctions API, every page of it.
+pub struct HypixelAuctionSource;
+
+impl AuctionSource for HypixelAuctionSource {
+    fn get_name(&self) -> String {
+        "Hypixel Auction House".to_string()
+    }
+
+    fn load_auctions(&self) -> AuctionFuture {
+        Box::pin(request_auctions(AUCTIONS_URL.to_string(), Some(AUCTIONS_ENDED_URL.to_string())))
+    }
+}
+
+/// Any server with the same endpoints as the auctions API, like a local mirror or mock server. Without an ended
+/// auctions URL, there won't be any median sold prices.
+pub struct UrlAuctionSource {
+    pub auctions_url: String,
+    pub ended_auctions_url: Option<String>,
+}
+
+impl AuctionSource for UrlAuctionSource {
+    fn get_name(&self) -> String {
+        self.auctions_url.clone()
+    }
+
+    fn load_auctions(&self) -> AuctionFuture {
+        Box::pin(request_auctions(self.auctions_url.clone(), self.ended_auctions_url.clone()))
+    }
+}
+
+/// Saved responses from the auctions and ended auctions APIs, in any mix and order.
+pub struct JsonAuctionSource {
+    name: String,
+    responses: Vec<String>,
+}
+
+impl JsonAuctionSource {
+    pub fn new(name: String, responses: Vec<String>) -> Self {
+        Self { name, responses }
+    }
+
+    /// Reads a single saved response, or every `.json` file in a folder of them.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let read = |path: &std::path::Path| std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {e}", path.display()));
+
+        let path_buf = std::path::PathBuf::from(path);
+        let responses = if path_buf.is_dir() {
+            let mut files = walkdir::WalkDir::new(&path_buf)
+                .into_iter()
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().extension().is_some_and(|e| e == "json"))
+                .map(|entry| entry.into_path())
+                .collect::<Vec<_>>();
+            files.sort();
+            files.iter().map(|file| read(file)).collect::<Result<Vec<String>, String>>()?
+        } else {
+            vec![read(&path_buf)?]
+        };
+
+        Ok(Self::new(path.to_string(), responses))
+    }
+}
+
+impl AuctionSource for JsonAuctionSource {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn load_auctions(&self) -> AuctionFuture {
+        let prices = parse_auction_responses(&self.responses).ok();
+        Box::pin(async move { prices })
+    }
+}
+
+/// Fails if any of the responses can't be read, so a broken dump isn't mistaken for a quiet auction house.
+pub fn parse_auction_responses(responses: &[String]) -> Result<AuctionData, String> {
+    let mut pages = Vec::new();
+    let mut ended = Vec::new();
+    for (index, json) in responses.iter().enumerate() {
+        match serde_json::from_str::<AuctionResponse>(json) {
+            Ok(AuctionResponse::Page(page)) => pages.push(page),
+            Ok(AuctionResponse::Ended(response)) => ended.push(response),
+            Err(e) => return Err(format!("Response {}: {e}", index + 1)),
+        }
+    }
+    Ok(calculate_auction_prices(&pages, &ended))
+}
+
+/// Blocks on its own runtime, for tests and the CLI.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_auctions_blocking(source: &dyn AuctionSource) -> Option<AuctionData> {
+    let auctions = source.load_auctions();
+    tokio::runtime::Runtime::new().unwrap().block_on(auctions)
+}
+
+/// Fetches the first page to find out how many there are, then the rest one by one. Pages after the first that fail
+/// are skipped, as auctions shift between pages while fetching anyway.
+async fn request_auctions(auctions_url: String, ended_auctions_url: Option<String>) -> Option<AuctionData> {
+    let client = Client::new();
+    let first_page = request_json::<AuctionsPage>(&client, format!("{auctions_url}?page=0")).await?;
+
+    let mut pages = Vec::with_capacity(first_page.total_pages as usize);
+    let total_pages = first_page.total_pages;
+    pages.push(first_page);
+    for page in 1..total_pages {
+        if let Some(page) = request_json::<AuctionsPage>(&client, format!("{auctions_url}?page={page}")).await {
+            pages.push(page);
+        }
+    }
+
+    let mut ended = Vec::new();
+    if let Some(ended_auctions_url) = ended_auctions_url {
+        if let Some(response) = request_json::<EndedAuctionsResponse>(&client, ended_auctions_url).await {
+            ended.push(response);
+        }
+    }
+
+    Some(calculate_auction_prices(&pages, &ended))
+}
+
+async fn request_json<T: serde::de::DeserializeOwned>(client: &Client, url: String) -> Option<T> {
+    let response = client.get(url).send().await.ok()?;
+    response.json::<T>().await.ok()
+}
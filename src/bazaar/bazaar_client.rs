@@ -0,0 +1,117 @@
+use crate::bazaar::bazaar_data::{BazaarData, BazaarProduct, BuyType, ProfitType, QuickStatus};
+use crate::bazaar::price_sources::{HypixelBazaarSource, PriceSource};
+use crate::bazaar::fetch_client::{self, DataSource, FetchClient, FetchFuture, SharedFetchClient};
+use egui::{Color32, Context, Response, RichText, Ui};
+use num_format::Locale::en;
+use num_format::ToFormattedString;
+use web_time::{Duration, Instant};
+
+pub const BAZAAR_URL: &str = "https://api.hypixel.net/skyblock/bazaar";
+/// The API only updates about once a minute, so fetching more often than this just wastes requests.
+pub const REFRESH_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Ultimate enchantments are listed on the bazaar with an extra `ULTIMATE_` prefix.
+const ULTIMATE_ENCHANTMENTS: [&str; 13] = [
+    "BANK",
+    "CHIMERA",
+    "COMBO",
+    "DUPLEX",
+    "FATAL_TEMPO",
+    "INFERNO",
+    "LAST_STAND",
+    "LEGION",
+    "NO_PAIN_NO_GAIN",
+    "ONE_FOR_ALL",
+    "SOUL_EATER",
+    "SWARM",
+    "WISDOM",
+];
+
+pub type BazaarClient = FetchClient<dyn PriceSource>;
+/// Shared by every page, so there's only ever one request in flight and one cooldown.
+pub type SharedBazaarClient = SharedFetchClient<dyn PriceSource>;
+
+impl DataSource for dyn PriceSource {
+    type Data = BazaarData;
+
+    fn get_name(&self) -> String {
+        PriceSource::get_name(self)
+    }
+
+    fn load(&self) -> FetchFuture<BazaarData> {
+        self.load_prices()
+    }
+}
+
+impl Default for BazaarClient {
+    fn default() -> Self {
+        Self::new(Box::new(HypixelBazaarSource), REFRESH_COOLDOWN)
+    }
+}
+
+impl BazaarClient {
+    pub fn get_product(&self, product_id: &str) -> Option<&BazaarProduct> {
+        self.get_data().and_then(|data| data.get(product_id))
+    }
+
+    pub fn get_quick_status(&self, product_id: &str) -> Option<&QuickStatus> {
+        self.get_product(product_id).map(|product| &product.quick_status)
+    }
+
+    pub fn get_buy_price(&self, product_id: &str, buy_type: BuyType) -> Option<f64> {
+        self.get_quick_status(product_id).map(|quick_status| quick_status.get_buy_price(buy_type))
+    }
+
+    pub fn get_sell_price(&self, product_id: &str, profit_type: ProfitType) -> Option<f64> {
+        self.get_quick_status(product_id).map(|quick_status| quick_status.get_sell_price(profit_type))
+    }
+}
+
+pub fn get_enchantment_id(enchantment: &str, level: u8) -> String {
+    let enchantment = enchantment.to_uppercase();
+    if ULTIMATE_ENCHANTMENTS.contains(&enchantment.as_str()) {
+        format!("ENCHANTMENT_ULTIMATE_{enchantment}_{level}")
+    } else {
+        format!("ENCHANTMENT_{enchantment}_{level}")
+    }
+}
+
+pub fn get_essence_id(essence_type: &str) -> String {
+    format!("ESSENCE_{}", essence_type.to_uppercase())
+}
+
+/// The refresh button every page shows next to its price settings, disabled while fetching or on cooldown.
+pub fn add_refresh_button(bazaar: &SharedBazaarClient, ctx: &Context, ui: &mut Ui, system_time: Instant) {
+    fetch_client::add_refresh_button(bazaar, "Bazaar data", ctx, ui, system_time, |ui, bazaar| {
+        ui.button("Refresh Bazaar").on_hover_text(format!("Prices from {}", bazaar.get_source_name()))
+    });
+}
+
+/// Shows the item's bazaar prices when hovering over it. Items without a product id (or that aren't listed) say so
+/// instead.
+pub fn add_price_hover(response: Response, bazaar: &SharedBazaarClient, product_id: Option<&str>) -> Response {
+    response.on_hover_ui(|ui| {
+        let bazaar = bazaar.borrow();
+        let prices = product_id.and_then(|product_id| {
+            let buy_price = bazaar.get_buy_price(product_id, BuyType::InstaBuy)?;
+            let sell_price = bazaar.get_sell_price(product_id, ProfitType::InstaSell)?;
+            Some([("Insta-Buy", buy_price), ("Insta-Sell", sell_price)])
+        });
+        match prices {
+            Some(prices) => {
+                for (label, price) in prices {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{label}:"));
+                        ui.label(RichText::new((price as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                    });
+                }
+            }
+            None if !bazaar.has_data() => {
+                ui.label(RichText::new("Loading bazaar prices...").color(Color32::GRAY));
+            }
+            None => {
+                ui.label(RichText::new("Not sold on the bazaar").color(Color32::GRAY));
+            }
+        }
+    })
+}
@@ -1,9 +1,21 @@
 use serde::Deserialize;
 use std::collections::HashMap;
-use crate::shards::shards_page::{BuyType, ProfitType};
 
+// key: product id
 pub type BazaarData = HashMap<String, BazaarProduct>;
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum BuyType {
+    BuyOrder,
+    InstaBuy,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ProfitType {
+    SellOffer,
+    InstaSell,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BazaarResponse {
@@ -22,13 +34,13 @@ pub struct BazaarProduct {
 
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-struct OrderSummary {
+pub struct OrderSummary {
     amount: f64,
     price_per_unit: f64,
     orders: u64,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct QuickStatus {
     pub product_id: String,
@@ -0,0 +1,197 @@
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use egui::{Button, Context, Response, Ui};
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use web_time::{Duration, Instant};
+
+/// Native fetches run on their own thread, so the future has to be sendable there. Web fetches stay on the main
+/// thread, where reqwest's futures aren't `Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type FetchFuture<T> = Pin<Box<dyn Future<Output = Option<T>> + Send>>;
+#[cfg(target_arch = "wasm32")]
+pub type FetchFuture<T> = Pin<Box<dyn Future<Output = Option<T>>>>;
+
+/// Somewhere a `FetchClient` can load its data from, implemented for the bazaar's and auction house's sources.
+pub trait DataSource {
+    #[cfg(not(target_arch = "wasm32"))]
+    type Data: Send + 'static;
+    #[cfg(target_arch = "wasm32")]
+    type Data: 'static;
+
+    /// Shown next to the refresh button, so it's clear when prices aren't live.
+    fn get_name(&self) -> String;
+
+    /// Resolves to the loaded data, or None if loading failed.
+    fn load(&self) -> FetchFuture<Self::Data>;
+}
+
+/// Shared by every page, so there's only ever one request in flight and one cooldown per kind of data.
+pub type SharedFetchClient<S> = Rc<RefCell<FetchClient<S>>>;
+
+/// A finished fetch, tagged with the source generation it was started under.
+type FetchResult<T> = (u64, Option<T>);
+
+/// Loads data from a swappable source in the background, without fetching more often than the cooldown allows.
+pub struct FetchClient<S: DataSource + ?Sized> {
+    sender: Sender<FetchResult<S::Data>>,
+    receiver: Receiver<FetchResult<S::Data>>,
+    source: Box<S>,
+    /// Goes up every time the source is swapped, so a fetch from the old source that finishes late is ignored.
+    source_generation: u64,
+    data: Option<S::Data>,
+    fetching: bool,
+    last_fetch_time: Option<Instant>,
+    refresh_cooldown: Duration,
+    /// Goes up every time new data arrives, so pages know when to recalculate anything based on it.
+    data_version: u64,
+}
+
+impl<S: DataSource + ?Sized> FetchClient<S> {
+    pub fn new(source: Box<S>, refresh_cooldown: Duration) -> Self {
+        let (sender, receiver) = unbounded();
+        Self {
+            sender,
+            receiver,
+            source,
+            source_generation: 0,
+            data: None,
+            fetching: false,
+            last_fetch_time: None,
+            refresh_cooldown,
+            data_version: 0,
+        }
+    }
+
+    pub fn new_shared() -> SharedFetchClient<S>
+    where
+        Self: Default,
+    {
+        Rc::new(RefCell::new(Self::default()))
+    }
+
+    /// Switches where the data comes from. Data from the old source is dropped and the cooldown is reset, so the next
+    /// frame fetches from the new one straight away.
+    pub fn set_source(&mut self, source: Box<S>) {
+        self.source = source;
+        self.source_generation += 1;
+        self.data = None;
+        self.fetching = false;
+        self.last_fetch_time = None;
+        self.data_version += 1;
+    }
+
+    pub fn get_source_name(&self) -> String {
+        self.source.get_name()
+    }
+
+    /// Picks up the result of a finished fetch. Returns true if new data arrived.
+    pub fn poll(&mut self, system_time: Instant) -> bool {
+        match self.receiver.try_recv() {
+            Ok((source_generation, _)) if source_generation != self.source_generation => false,
+            Ok((_, data)) => {
+                self.fetching = false;
+                self.last_fetch_time = Some(system_time);
+                // keep showing the old data if a refresh failed
+                if data.is_some() {
+                    self.data = data;
+                    self.data_version += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Starts the first fetch if nothing has been loaded yet. Failed fetches are retried after the cooldown.
+    pub fn fetch_if_missing(&mut self, system_time: Instant) {
+        if self.data.is_none() {
+            self.request_refresh(system_time);
+        }
+    }
+
+    /// Starts a fetch unless one is already running or the last one was too recent. Returns whether it started.
+    pub fn request_refresh(&mut self, system_time: Instant) -> bool {
+        if self.fetching || self.get_time_until_refresh(system_time).is_some() {
+            return false;
+        }
+
+        self.fetching = true;
+        fetch_in_background(self.source.load(), self.source_generation, self.sender.clone());
+        true
+    }
+
+    /// How long until another fetch is allowed, or None if it's allowed now.
+    pub fn get_time_until_refresh(&self, system_time: Instant) -> Option<Duration> {
+        self.last_fetch_time
+            .map(|last_fetch_time| system_time.duration_since(last_fetch_time))
+            .filter(|elapsed| *elapsed < self.refresh_cooldown)
+            .map(|elapsed| self.refresh_cooldown - elapsed)
+    }
+
+    pub fn is_fetching(&self) -> bool {
+        self.fetching
+    }
+
+    pub fn has_data(&self) -> bool {
+        self.data.is_some()
+    }
+
+    pub fn get_data_version(&self) -> u64 {
+        self.data_version
+    }
+
+    pub fn get_data(&self) -> Option<&S::Data> {
+        self.data.as_ref()
+    }
+}
+
+/// The refresh button for a client, disabled while fetching or on cooldown. `data_name` fills in the disabled states,
+/// `add_button` adds the button itself for when a refresh is allowed.
+pub fn add_refresh_button<S: DataSource + ?Sized>(
+    client: &SharedFetchClient<S>,
+    data_name: &str,
+    ctx: &Context,
+    ui: &mut Ui,
+    system_time: Instant,
+    add_button: impl FnOnce(&mut Ui, &FetchClient<S>) -> Response,
+) {
+    let mut client = client.borrow_mut();
+    if client.is_fetching() {
+        ui.add_enabled(false, Button::new(format!("Refreshing {data_name}...")));
+    } else if let Some(time_until_refresh) = client.get_time_until_refresh(system_time) {
+        let remaining_ms = time_until_refresh.as_millis();
+        let seconds_until_refresh = remaining_ms.div_ceil(1000);
+        ctx.request_repaint_after(Duration::from_millis((remaining_ms % 1000).max(1) as u64));
+        ui.add_enabled(false, Button::new(format!("Can refresh {data_name} in {seconds_until_refresh}s")));
+    } else if add_button(ui, &client).clicked() {
+        client.request_refresh(system_time);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn fetch_in_background<T: 'static>(
+    future: Pin<Box<dyn Future<Output = Option<T>>>>,
+    source_generation: u64,
+    sender: Sender<(u64, Option<T>)>,
+) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let _ = sender.send((source_generation, future.await));
+    });
+}
+
+/// Sources always resolve, with None if loading failed, so the client never gets stuck waiting.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn fetch_in_background<T: Send + 'static>(
+    future: Pin<Box<dyn Future<Output = Option<T>> + Send>>,
+    source_generation: u64,
+    sender: Sender<(u64, Option<T>)>,
+) {
+    std::thread::spawn(move || {
+        let rt = tokio::runtime::Runtime::new().unwrap();
+        let _ = sender.send((source_generation, rt.block_on(future)));
+    });
+}
@@ -0,0 +1,148 @@
+use crate::bazaar::bazaar_client::BAZAAR_URL;
+use crate::bazaar::bazaar_data::{BazaarData, BazaarProduct, BazaarResponse, QuickStatus};
+use crate::bazaar::fetch_client::FetchFuture;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+pub type PriceFuture = FetchFuture<BazaarData>;
+
+/// Somewhere bazaar prices can be loaded from.
+pub trait PriceSource {
+    /// Shown next to the refresh button, so it's clear when prices aren't live.
+    fn get_name(&self) -> String;
+
+    /// Resolves to the full product map, or None if loading failed.
+    fn load_prices(&self) -> PriceFuture;
+}
+
+/// The live Hypixel bazaar API.
+pub struct HypixelBazaarSource;
+
+impl PriceSource for HypixelBazaarSource {
+    fn get_name(&self) -> String {
+        "Hypixel Bazaar".to_string()
+    }
+
+    fn load_prices(&self) -> PriceFuture {
+        Box::pin(request_prices(BAZAAR_URL.to_string()))
+    }
+}
+
+/// Any URL that serves the same JSON as the bazaar API, like a local mirror or mock server.
+pub struct UrlPriceSource {
+    pub url: String,
+}
+
+impl PriceSource for UrlPriceSource {
+    fn get_name(&self) -> String {
+        self.url.clone()
+    }
+
+    fn load_prices(&self) -> PriceFuture {
+        Box::pin(request_prices(self.url.clone()))
+    }
+}
+
+/// Prices from a saved bazaar API response, which never change.
+pub struct JsonPriceSource {
+    name: String,
+    json: String,
+}
+
+impl JsonPriceSource {
+    pub fn new(name: String, json: String) -> Self {
+        Self { name, json }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_path(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+        Ok(Self::new(path.to_string(), json))
+    }
+}
+
+impl PriceSource for JsonPriceSource {
+    fn get_name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn load_prices(&self) -> PriceFuture {
+        let prices = parse_bazaar_json(&self.json).ok();
+        Box::pin(async move { prices })
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq)]
+pub struct PriceOverride {
+    /// What it costs to insta-buy (and what sell offers go for).
+    pub buy_price: f64,
+    /// What it sells for instantly (and what buy orders cost).
+    pub sell_price: f64,
+}
+
+/// Replaces the prices of some products from another source with the user's own. Overridden products that the other
+/// source doesn't have are added, so items that aren't on the bazaar can be given a price too.
+pub struct ManualPriceSource {
+    pub base: Box<dyn PriceSource>,
+    // key: product id
+    pub overrides: BTreeMap<String, PriceOverride>,
+}
+
+impl ManualPriceSource {
+    /// Reads overrides in the form `{"PRODUCT_ID": {"buy_price": 1.0, "sell_price": 1.0}}`.
+    pub fn parse_overrides(json: &str) -> Result<BTreeMap<String, PriceOverride>, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
+
+impl PriceSource for ManualPriceSource {
+    fn get_name(&self) -> String {
+        format!("{} ({} overridden)", self.base.get_name(), self.overrides.len())
+    }
+
+    fn load_prices(&self) -> PriceFuture {
+        let base_prices = self.base.load_prices();
+        let overrides = self.overrides.clone();
+        Box::pin(async move {
+            let mut prices = base_prices.await?;
+            apply_overrides(&mut prices, &overrides);
+            Some(prices)
+        })
+    }
+}
+
+pub fn parse_bazaar_json(json: &str) -> Result<BazaarData, String> {
+    serde_json::from_str::<BazaarResponse>(json)
+        .map(|response| response.products)
+        .map_err(|e| e.to_string())
+}
+
+/// Blocks on its own runtime, for tests and the CLI.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_prices_blocking(source: &dyn PriceSource) -> Option<BazaarData> {
+    let prices = source.load_prices();
+    tokio::runtime::Runtime::new().unwrap().block_on(prices)
+}
+
+fn apply_overrides(prices: &mut BazaarData, overrides: &BTreeMap<String, PriceOverride>) {
+    for (product_id, price_override) in overrides {
+        let product = prices.entry(product_id.clone()).or_insert_with(|| BazaarProduct {
+            product_id: product_id.clone(),
+            sell_summary: Vec::new(),
+            buy_summary: Vec::new(),
+            quick_status: QuickStatus {
+                product_id: product_id.clone(),
+                ..Default::default()
+            },
+        });
+        product.quick_status.buy_price = price_override.buy_price;
+        product.quick_status.sell_price = price_override.sell_price;
+    }
+}
+
+async fn request_prices(url: String) -> Option<BazaarData> {
+    let response = Client::new().get(url).send().await.ok()?;
+    let bazaar_response = response.json::<BazaarResponse>().await.ok()?;
+    Some(bazaar_response.products)
+}
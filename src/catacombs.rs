@@ -1,5 +1,16 @@
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch_opening;
+pub mod breakpoint_calculator;
 pub mod catacombs_loot;
 pub mod catacombs_loot_calculator;
 pub mod catacombs_page;
+pub mod dry_streak;
+pub mod essence_summary;
+pub mod loot_log;
+pub mod loot_validator;
 mod options;
+pub mod pinned_comparison;
+pub mod profile_import;
+pub mod quality_modifiers;
+pub mod sensitivity_calculator;
 mod unused_weight_math_calculator;
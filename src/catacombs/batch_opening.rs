@@ -0,0 +1,176 @@
+use crate::catacombs::catacombs_loot::LootChest;
+use crate::catacombs::catacombs_loot_calculator::{generate_random_table, get_rng_meter_xp_for_score, RngMeterData};
+use std::collections::BTreeMap;
+
+/// Opening is done on the UI thread, so this keeps a single batch from freezing the page for long.
+pub const MAX_BATCH_OPENING_CHESTS: u32 = 10_000;
+
+#[derive(Debug, Default)]
+pub struct BatchOpeningResult {
+    pub chests_opened: u32,
+    // key: entry identifier, value: times rolled (essence can roll more than once per chest)
+    pub drop_counts: BTreeMap<String, u32>,
+    pub target_entry: Option<String>,
+    /// The chests (counting from 0) the target entry dropped from.
+    pub target_drops: Vec<u32>,
+    /// How many of the target entry's drops were guaranteed by a full RNG meter.
+    pub meter_drops: u32,
+    pub final_meter_xp: i32,
+}
+
+/// A run of chests, up to and including the one the target entry dropped from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChestSequence {
+    pub first_chest: u32,
+    pub last_chest: u32,
+    /// False for the chests opened after the last drop.
+    pub ended_with_drop: bool,
+}
+
+impl ChestSequence {
+    pub fn get_length(&self) -> u32 {
+        self.last_chest - self.first_chest + 1
+    }
+}
+
+impl BatchOpeningResult {
+    pub fn get_count(&self, identifier: &str) -> u32 {
+        self.drop_counts.get(identifier).copied().unwrap_or(0)
+    }
+
+    /// Splits the chests into the runs it took to get each drop of the target entry, with the chests opened since
+    /// the last drop at the end.
+    pub fn get_sequences(&self) -> Vec<ChestSequence> {
+        let mut sequences = Vec::new();
+        let mut first_chest = 0;
+        for drop in self.target_drops.iter() {
+            sequences.push(ChestSequence { first_chest, last_chest: *drop, ended_with_drop: true });
+            first_chest = drop + 1;
+        }
+        if first_chest < self.chests_opened {
+            sequences.push(ChestSequence {
+                first_chest,
+                last_chest: self.chests_opened - 1,
+                ended_with_drop: false,
+            });
+        }
+        sequences
+    }
+
+    /// The fewest chests it took to get the target entry.
+    pub fn get_luckiest_sequence(&self) -> Option<ChestSequence> {
+        self.get_sequences()
+            .into_iter()
+            .filter(|sequence| sequence.ended_with_drop)
+            .min_by_key(|sequence| sequence.get_length())
+    }
+
+    /// The most chests opened in a row without the target entry, which can still be going.
+    pub fn get_unluckiest_sequence(&self) -> Option<ChestSequence> {
+        self.get_sequences()
+            .into_iter()
+            .max_by_key(|sequence| sequence.get_length() - sequence.ended_with_drop as u32)
+    }
+}
+
+/// Opens the chest again and again, filling the RNG meter between chests like a run with the given score would. The
+/// meter resets whenever the selected item drops, whether it was guaranteed or not.
+pub fn open_chests(
+    chest: &LootChest,
+    quality: i16,
+    rng_meter_data: &RngMeterData,
+    chests: u32,
+    score_per_run: i32,
+    target_entry: Option<String>,
+) -> BatchOpeningResult {
+    let mut result = BatchOpeningResult {
+        target_entry,
+        ..Default::default()
+    };
+    let mut rng_meter_data = rng_meter_data.clone();
+    let meter_xp_per_run = get_rng_meter_xp_for_score(score_per_run);
+
+    for chest_index in 0..chests {
+        let meter_full = rng_meter_data.selected_item.as_ref().is_some_and(|selected_item| {
+            rng_meter_data.selected_xp >= selected_item.required_xp && selected_item.lowest_tier_chest_type == chest.chest_type
+        });
+
+        let rolled_entries = generate_random_table(chest, quality, &rng_meter_data);
+        for rolled_entry in rolled_entries.iter() {
+            *result.drop_counts.entry(rolled_entry.entry.to_string()).or_insert(0) += 1;
+        }
+        let dropped = |identifier: &str| rolled_entries.iter().any(|e| e.entry.to_string() == identifier);
+
+        if let Some(target_entry) = &result.target_entry {
+            if dropped(target_entry) {
+                result.target_drops.push(chest_index);
+            }
+        }
+
+        if let Some(selected_item) = &rng_meter_data.selected_item {
+            if dropped(&selected_item.identifier) {
+                if meter_full {
+                    result.meter_drops += 1;
+                }
+                rng_meter_data.selected_xp = 0;
+            }
+            // xp is added after rolling, same as the deselection calculator
+            rng_meter_data.selected_xp += meter_xp_per_run;
+        }
+    }
+
+    result.chests_opened = chests;
+    result.final_meter_xp = rng_meter_data.selected_xp;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchOpeningResult, ChestSequence};
+    use crate::catacombs::catacombs_loot::{read_all_chests, ChestType, LootEntry};
+
+    #[test]
+    fn luckiest_and_unluckiest_sequences() {
+        let result = BatchOpeningResult {
+            chests_opened: 20,
+            target_drops: vec![4, 6, 15],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            result.get_luckiest_sequence(),
+            Some(ChestSequence { first_chest: 5, last_chest: 6, ended_with_drop: true })
+        );
+        assert_eq!(
+            result.get_unluckiest_sequence(),
+            Some(ChestSequence { first_chest: 7, last_chest: 15, ended_with_drop: true })
+        );
+        // the chests after the last drop are a dry streak that hasn't ended yet
+        assert_eq!(result.get_sequences().last().unwrap().get_length(), 4);
+    }
+
+    #[test]
+    fn no_drops_is_one_unlucky_sequence() {
+        let result = BatchOpeningResult { chests_opened: 10, ..Default::default() };
+        assert_eq!(result.get_luckiest_sequence(), None);
+        assert_eq!(result.get_unluckiest_sequence().unwrap().get_length(), 10);
+    }
+
+    #[test]
+    fn guaranteed_essence_drops_from_every_chest() {
+        let chest = read_all_chests(&crate::app::ASSETS_DIR)
+            .remove("f1")
+            .unwrap()
+            .into_iter()
+            .find(|chest| chest.chest_type == ChestType::Wood)
+            .unwrap();
+        let result = super::open_chests(&chest, chest.base_quality as i16, &Default::default(), 200, 300, None);
+
+        assert_eq!(result.chests_opened, 200);
+        for entry in chest.loot.iter() {
+            if let LootEntry::Essence { weight: 0, quality: 0, .. } = entry.as_ref() {
+                assert_eq!(result.get_count(&entry.to_string()), 200);
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use crate::catacombs::catacombs_loot::{LootChest, LootEntry};
+use crate::catacombs::quality_modifiers::{
+    QualityModifiers, BOSS_LUCK_TIERS, MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE,
+    TREASURE_ACCESSORY_TIERS,
+};
+use std::rc::Rc;
+
+pub const MAX_BREAKPOINT_ROLLS: usize = 3;
+
+pub struct RollBreakpoint {
+    /// None if the chest doesn't have enough entries to roll this many times before it.
+    pub required_quality: Option<i16>,
+    /// The modifiers with the fewest upgrade steps that reach the required quality. Each Box attribute level counts as
+    /// a step, so this isn't necessarily the cheapest in coins.
+    pub fewest_upgrade_modifiers: Option<QualityModifiers>,
+}
+
+pub struct EntryBreakpoints {
+    pub entry: Rc<LootEntry>,
+    /// Index 0 is the first roll, index 1 the second roll, etc.
+    pub roll_breakpoints: Vec<RollBreakpoint>,
+}
+
+/// Finds the starting quality each entry needs to be rollable as the first, second, third (etc.) roll of the chest.
+///
+/// An entry can only roll once the remaining quality is at least its own quality, so rolling it later on needs enough
+/// quality for the lowest possible rolls before it as well. Only the weighted essence entry can be used more than once
+/// for those earlier rolls.
+pub fn calculate_quality_breakpoints(chest: &LootChest) -> Vec<EntryBreakpoints> {
+    let modifier_combinations = generate_all_modifier_combinations(chest);
+
+    let weighted_essence_quality = chest
+        .loot
+        .iter()
+        .find(|e| e.is_essence_and_can_roll_multiple_times() && e.get_weight() > 0 && e.get_quality() > 0)
+        .map(|e| e.get_quality());
+
+    let non_essence_entries = chest
+        .loot
+        .iter()
+        .filter(|e| !e.is_essence_and_can_roll_multiple_times())
+        .collect::<Vec<&Rc<LootEntry>>>();
+
+    let mut breakpoints = non_essence_entries
+        .iter()
+        .map(|entry| {
+            let other_qualities = non_essence_entries
+                .iter()
+                .filter(|other| !Rc::ptr_eq(other, entry))
+                .map(|other| other.get_quality())
+                .collect::<Vec<i16>>();
+
+            let roll_breakpoints = (0..MAX_BREAKPOINT_ROLLS)
+                .map(|previous_rolls| {
+                    let mut possible_previous_qualities = other_qualities.clone();
+                    if let Some(essence_quality) = weighted_essence_quality {
+                        possible_previous_qualities.extend(std::iter::repeat(essence_quality).take(previous_rolls));
+                    }
+                    possible_previous_qualities.sort();
+
+                    let required_quality = if possible_previous_qualities.len() < previous_rolls {
+                        None
+                    } else {
+                        let lowest_previous_quality: i16 = possible_previous_qualities.iter().take(previous_rolls).sum();
+                        Some(entry.get_quality() + lowest_previous_quality)
+                    };
+
+                    RollBreakpoint {
+                        required_quality,
+                        fewest_upgrade_modifiers: required_quality.and_then(|quality| find_fewest_upgrade_modifiers(&modifier_combinations, quality)),
+                    }
+                })
+                .collect::<Vec<RollBreakpoint>>();
+
+            EntryBreakpoints {
+                entry: Rc::clone(entry),
+                roll_breakpoints,
+            }
+        })
+        .collect::<Vec<EntryBreakpoints>>();
+
+    breakpoints.sort_by(|a, b| {
+        b.entry.get_quality().cmp(&a.entry.get_quality())
+            .then(a.entry.to_string().cmp(&b.entry.to_string()))
+    });
+    breakpoints
+}
+
+/// Every combination of modifiers along with the quality it gives, sorted with the fewest upgrade steps first and the
+/// lowest quality first between combinations with as many steps.
+fn generate_all_modifier_combinations(chest: &LootChest) -> Vec<(QualityModifiers, i16)> {
+    let s_plus_options: &[bool] = if chest.require_s_plus() { &[true] } else { &[false, true] };
+
+    let mut combinations = Vec::new();
+    for &s_plus in s_plus_options {
+        for (accessory_step, (treasure_accessory_multiplier, _)) in TREASURE_ACCESSORY_TIERS.iter().enumerate() {
+            for (boss_luck_step, (boss_luck_increase, _)) in BOSS_LUCK_TIERS.iter().enumerate() {
+                for catacombs_box_attribute_increase in 0..=MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE {
+                    let modifiers = QualityModifiers {
+                        treasure_accessory_multiplier: *treasure_accessory_multiplier,
+                        boss_luck_increase: *boss_luck_increase,
+                        catacombs_box_attribute_increase,
+                        s_plus,
+                    };
+                    let steps = accessory_step
+                        + boss_luck_step
+                        + catacombs_box_attribute_increase as usize
+                        + (s_plus && !chest.require_s_plus()) as usize;
+                    combinations.push((modifiers, modifiers.calculate_quality(chest), steps));
+                }
+            }
+        }
+    }
+
+    combinations.sort_by(|(_, a_quality, a_steps), (_, b_quality, b_steps)| a_steps.cmp(b_steps).then(a_quality.cmp(b_quality)));
+    combinations
+        .into_iter()
+        .map(|(modifiers, quality, _)| (modifiers, quality))
+        .collect()
+}
+
+fn find_fewest_upgrade_modifiers(combinations: &[(QualityModifiers, i16)], required_quality: i16) -> Option<QualityModifiers> {
+    combinations
+        .iter()
+        .find(|(_, quality)| *quality >= required_quality)
+        .map(|(modifiers, _)| *modifiers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_quality_breakpoints, generate_all_modifier_combinations, MAX_BREAKPOINT_ROLLS};
+    use crate::catacombs::catacombs_loot::{read_all_chests, LootChest};
+
+    fn get_f7_chests() -> Vec<LootChest> {
+        read_all_chests(&crate::app::ASSETS_DIR).remove("f7").unwrap()
+    }
+
+    #[test]
+    fn later_rolls_need_more_quality() {
+        for chest in get_f7_chests() {
+            for entry_breakpoints in calculate_quality_breakpoints(&chest) {
+                assert_eq!(entry_breakpoints.roll_breakpoints.len(), MAX_BREAKPOINT_ROLLS);
+                let required_qualities = entry_breakpoints
+                    .roll_breakpoints
+                    .iter()
+                    .filter_map(|breakpoint| breakpoint.required_quality)
+                    .collect::<Vec<i16>>();
+                assert_eq!(required_qualities.first(), Some(&entry_breakpoints.entry.get_quality()));
+                assert!(required_qualities.windows(2).all(|pair| pair[0] <= pair[1]));
+            }
+        }
+    }
+
+    #[test]
+    fn modifiers_reach_the_required_quality_with_the_fewest_upgrades() {
+        for chest in get_f7_chests() {
+            let combinations = generate_all_modifier_combinations(&chest);
+            let best_quality = combinations.iter().map(|(_, quality)| *quality).max().unwrap();
+
+            for breakpoint in calculate_quality_breakpoints(&chest).iter().flat_map(|e| e.roll_breakpoints.iter()) {
+                let Some(required_quality) = breakpoint.required_quality else {
+                    assert!(breakpoint.fewest_upgrade_modifiers.is_none());
+                    continue;
+                };
+                match breakpoint.fewest_upgrade_modifiers {
+                    Some(modifiers) => {
+                        assert!(modifiers.calculate_quality(&chest) >= required_quality);
+                        // nothing earlier in the list, so with as few or fewer steps, reaches it
+                        let position = combinations.iter().position(|(m, _)| *m == modifiers).unwrap();
+                        assert!(combinations[..position].iter().all(|(_, quality)| *quality < required_quality));
+                    }
+                    None => assert!(best_quality < required_quality),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_modifiers_come_first() {
+        for chest in get_f7_chests() {
+            let (modifiers, quality) = generate_all_modifier_combinations(&chest)[0];
+            assert_eq!(modifiers.catacombs_box_attribute_increase, 0);
+            assert_eq!(modifiers.s_plus, chest.require_s_plus());
+            assert_eq!(quality, modifiers.calculate_quality(&chest));
+        }
+    }
+}
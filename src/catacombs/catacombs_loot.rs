@@ -1,9 +1,13 @@
+use crate::bazaar::auction_client::{get_item_value, AuctionHouseClient};
+use crate::bazaar::auction_data::get_pet_auction_id;
+use crate::bazaar::bazaar_client::{get_enchantment_id, get_essence_id, BazaarClient};
 use crate::catacombs::catacombs_loot_calculator::SelectedRngMeterItem;
 use convert_case::{Case, Casing};
 use include_dir::Dir;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt::Display;
+use std::path::Path;
 use std::process::id;
 use std::rc::Rc;
 
@@ -32,6 +36,12 @@ impl LootChest {
         self.loot_strings.contains(&entry.identifier)
     }
 
+    /// The entries the RNG meter can be set to when this is the highest tier chest of its floor.
+    pub fn get_rng_meter_entries(&self) -> impl Iterator<Item = &Rc<LootEntry>> {
+        // essence doesn't show in rng meter
+        self.loot.iter().filter(|e| !e.is_essence_and_can_roll_multiple_times())
+    }
+
     pub fn require_s_plus(&self) -> bool {
         self.chest_type == ChestType::Bedrock
             && (self.floor == 5 || self.floor == 6 || (self.floor == 7 && !self.master_mode))
@@ -155,6 +165,36 @@ impl LootEntry {
         matches!(self, LootEntry::Essence { .. })
     }
 
+    /// The bazaar product id, if the entry can be sold there. Pets and most dungeon items only go on the auction house,
+    /// so they simply won't be found.
+    pub fn get_bazaar_id(&self) -> Option<String> {
+        match self {
+            LootEntry::Item { item, .. } => Some(item.clone()),
+            LootEntry::Pet { .. } => None,
+            LootEntry::Enchantment { enchantment, enchantment_level, .. } => Some(get_enchantment_id(enchantment, *enchantment_level)),
+            LootEntry::Essence { essence_type, .. } => Some(get_essence_id(essence_type)),
+        }
+    }
+
+    /// The id the entry is listed under on the auction house. Books and essence are only sold on the bazaar.
+    pub fn get_auction_id(&self) -> Option<String> {
+        match self {
+            LootEntry::Item { item, .. } => Some(item.clone()),
+            LootEntry::Pet { pet, tier, .. } => Some(get_pet_auction_id(pet, tier)),
+            LootEntry::Enchantment { .. } | LootEntry::Essence { .. } => None,
+        }
+    }
+
+    /// What the entry sells for, counting every piece of essence. None while prices are loading, or if it isn't sold
+    /// anywhere.
+    pub fn get_value(&self, bazaar: &BazaarClient, auctions: &AuctionHouseClient) -> Option<f64> {
+        let value = get_item_value(bazaar, auctions, self.get_bazaar_id().as_deref(), self.get_auction_id().as_deref())?;
+        match self {
+            LootEntry::Essence { essence_amount, .. } => Some(value * *essence_amount as f64),
+            _ => Some(value),
+        }
+    }
+
     pub fn get_possible_file_names(&self) -> Vec<String> {
         match self {
             LootEntry::Item { item, .. } => {
@@ -213,15 +253,7 @@ pub fn read_all_chests(dir: &Dir) -> BTreeMap<String, Vec<LootChest>> {
         match serde_json::from_slice::<LootChest>(entry.as_file().unwrap().contents()) {
             Ok(mut chest) => {
                 println!("Parsing loot JSON file from path {:?}", entry);
-                let floor = path
-                    .to_str()
-                    .unwrap()
-                    .to_string()
-                    .replace("dungeon_loot/", "")
-                    .split("/")
-                    .next()
-                    .unwrap()
-                    .to_string();
+                let floor = get_floor_from_path(path);
 
                 for entry in chest.loot.iter() {
                     chest.loot_strings.push(entry.to_string());
@@ -238,3 +270,14 @@ pub fn read_all_chests(dir: &Dir) -> BTreeMap<String, Vec<LootChest>> {
 
     chests
 }
+
+pub fn get_floor_from_path(path: &Path) -> String {
+    path.to_str()
+        .unwrap()
+        .to_string()
+        .replace("dungeon_loot/", "")
+        .split("/")
+        .next()
+        .unwrap()
+        .to_string()
+}
@@ -54,7 +54,7 @@ impl LootChanceEntry {
     }
 }
 
-#[derive(Default, Hash)]
+#[derive(Default, Hash, Clone)]
 pub struct RngMeterData {
     pub selected_item: Option<SelectedRngMeterItem>,
     pub selected_xp: i32,
@@ -112,9 +112,57 @@ pub struct AveragesCalculationResult {
 
 pub fn calculate_average_chances(
     chest: &LootChest,
-    mut starting_quality: i16,
+    starting_quality: i16,
     rng_meter_data: &RngMeterData,
 ) -> AveragesCalculationResult {
+    let (entry_data, guaranteed_essence_entries, starting_quality) = create_entry_data(chest, starting_quality, rng_meter_data);
+    let entry_data = Rc::new(RefCell::new(entry_data));
+    let mut recursion_data: RecursiveData = Default::default();
+
+    process_random_entries(
+        Rc::clone(&entry_data),
+        &mut recursion_data,
+        &mut (),
+        1.0,
+        starting_quality,
+        0,
+    );
+
+    match Rc::try_unwrap(entry_data).map(|refcell| refcell.into_inner()) {
+        Ok(data) => {
+            let mut results = data.weighted_entries;
+            results.push(data.leftover_essence_entry);
+
+            for guaranteed_entry in guaranteed_essence_entries {
+                results.push(Rc::new(RefCell::new(guaranteed_entry)));
+            }
+
+            sort_entries(&mut results, rng_meter_data.selected_item.as_ref());
+
+            let total_weight = results.iter().map(|e| e.borrow().used_weight).sum();
+            AveragesCalculationResult {
+                entries: results,
+                total_weight,
+            }
+        }
+        Err(..) => {
+            // panic!("Something went wrong");
+            AveragesCalculationResult {
+                entries: Vec::new(),
+                total_weight: 0.0,
+            }
+        }
+    }
+}
+
+/// The entries of the chest the roll tree is walked over, with the selected RNG meter item's weight boosted. A full
+/// meter guarantees the item in the lowest tier chest, so it's disabled there and its quality is taken out of the
+/// starting quality that's returned.
+fn create_entry_data(
+    chest: &LootChest,
+    mut starting_quality: i16,
+    rng_meter_data: &RngMeterData,
+) -> (EntryData, Vec<LootChanceEntry>, i16) {
     let mut weighted_entries: Vec<Rc<RefCell<LootChanceEntry>>> = Vec::new();
 
     let mut weighted_essence_entry: Option<Rc<RefCell<LootChanceEntry>>> = None;
@@ -122,7 +170,6 @@ pub fn calculate_average_chances(
     let mut leftover_essence_entry: Option<Rc<RefCell<LootChanceEntry>>> = None;
     let mut guaranteed_essence_entries: Vec<LootChanceEntry> = Vec::new();
 
-    let mut recursion_data: RecursiveData = Default::default();
     let mut lowest_non_essence_quality: Option<i16> = None;
 
     for entry in &chest.loot {
@@ -182,48 +229,16 @@ pub fn calculate_average_chances(
         };
     }
 
-    let entry_data = Rc::new(RefCell::new(EntryData {
+    let entry_data = EntryData {
         weighted_entries,
         weighted_essence_entry: weighted_essence_entry.unwrap(),
         leftover_essence_entry: leftover_essence_entry.unwrap(),
         lowest_non_essence_quality: lowest_non_essence_quality.unwrap(),
-    }));
-
-    process_random_entries(
-        Rc::clone(&entry_data),
-        &mut recursion_data,
-        1.0,
-        starting_quality,
-        0,
-    );
-
-    match Rc::try_unwrap(entry_data).map(|refcell| refcell.into_inner()) {
-        Ok(data) => {
-            let mut results = data.weighted_entries;
-            results.push(data.leftover_essence_entry);
-
-            for guaranteed_entry in guaranteed_essence_entries {
-                results.push(Rc::new(RefCell::new(guaranteed_entry)));
-            }
-
-            sort_entries(&mut results, rng_meter_data.selected_item.as_ref());
-
-            let total_weight = results.iter().map(|e| e.borrow().used_weight).sum();
-            AveragesCalculationResult {
-                entries: results,
-                total_weight,
-            }
-        }
-        Err(..) => {
-            // panic!("Something went wrong");
-            AveragesCalculationResult {
-                entries: Vec::new(),
-                total_weight: 0.0,
-            }
-        }
-    }
+    };
+    (entry_data, guaranteed_essence_entries, starting_quality)
 }
 
+
 fn sort_entries(entries: &mut [Rc<RefCell<LootChanceEntry>>], rng_meter_item: Option<&SelectedRngMeterItem>) {
     let rng_meter_string = rng_meter_item.map_or(String::new(), |e| e.identifier.clone());
 
@@ -244,9 +259,23 @@ fn sort_entries(entries: &mut [Rc<RefCell<LootChanceEntry>>], rng_meter_item: Op
     });
 }
 
-fn process_random_entries(
+/// Gets told about the non-essence entries rolled on the way down the roll tree, and about every finished chest.
+trait RollTreeObserver {
+    fn push_roll(&mut self, _entry: &Rc<LootEntry>) {}
+
+    fn pop_roll(&mut self) {}
+
+    /// Called once per leaf of the roll tree, with the chance of the chest ending up with the rolls pushed so far.
+    fn finish_chest(&mut self, _chance: f64) {}
+}
+
+/// The averages only need each entry's chance, which the recursion keeps track of itself.
+impl RollTreeObserver for () {}
+
+fn process_random_entries<O: RollTreeObserver>(
     entry_data: Rc<RefCell<EntryData>>,
     recursion_data: &mut RecursiveData,
+    observer: &mut O,
     overall_chance: f64,
     remaining_quality: i16,
     depth: i32,
@@ -263,6 +292,11 @@ fn process_random_entries(
             total_weight += entry.used_weight;
         }
     }
+    if total_weight == 0.0 {
+        // nothing fits in the quality that's left
+        observer.finish_chest(overall_chance);
+        return;
+    }
 
     for entry in entry_data.borrow().weighted_entries.iter() {
         if entry.borrow().disabled || entry.borrow().entry.get_quality() > remaining_quality {
@@ -329,25 +363,146 @@ fn process_random_entries(
             let chance_increase = weight_roll_chance * overall_chance * quality_multiplier;
             entry_data.borrow().leftover_essence_entry.borrow_mut().increase_chance(chance_increase);
 
+            // the rest of the chest is filled with essence
+            if !is_essence_entry {
+                observer.push_roll(&entry.borrow().entry);
+            }
+            observer.finish_chest(weight_roll_chance * overall_chance);
+            if !is_essence_entry {
+                observer.pop_roll();
+            }
+
             continue;
         } else {
             // println!("Iteration B {}, new remaining quality {}", recursion_data.iterations, new_remaining_quality);
             let chance_increase = weight_roll_chance * overall_chance;
             entry.borrow_mut().increase_chance(chance_increase);
 
-            if new_remaining_quality == 0 {
-                continue;
-            }
-
             let roll_once = !entry.borrow().entry.is_essence_and_can_roll_multiple_times();
             if roll_once {
+                observer.push_roll(&entry.borrow().entry);
+            }
+
+            if new_remaining_quality == 0 {
+                observer.finish_chest(chance_increase);
+            } else if roll_once {
                 entry.borrow_mut().disabled = true;
-                process_random_entries(Rc::clone(&entry_data), recursion_data, chance_increase, new_remaining_quality, depth + 1);
+                process_random_entries(Rc::clone(&entry_data), recursion_data, observer, chance_increase, new_remaining_quality, depth + 1);
                 entry.borrow_mut().disabled = false;
             } else {
-                process_random_entries(Rc::clone(&entry_data), recursion_data, chance_increase, new_remaining_quality, depth + 1);
+                process_random_entries(Rc::clone(&entry_data), recursion_data, observer, chance_increase, new_remaining_quality, depth + 1);
+            }
+
+            if roll_once {
+                observer.pop_roll();
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RollDistributionResult {
+    /// Index is the amount of non-essence entries in the chest, value is the chance of the chest containing exactly that many.
+    pub item_count_chances: Vec<f64>,
+    /// Keyed by the two entry identifiers, sorted alphabetically.
+    pub pair_chances: HashMap<(String, String), f64>,
+    pub wanted_chance: f64,
+}
+
+impl RollDistributionResult {
+    pub fn get_pair_chance(&self, first: &str, second: &str) -> f64 {
+        let key = if first <= second {
+            (first.to_string(), second.to_string())
+        } else {
+            (second.to_string(), first.to_string())
+        };
+        self.pair_chances.get(&key).copied().unwrap_or(0.0)
+    }
+
+    pub fn get_average_item_count(&self) -> f64 {
+        self.item_count_chances
+            .iter()
+            .enumerate()
+            .map(|(count, chance)| count as f64 * chance)
+            .sum()
+    }
+}
+
+struct DistributionData<'a> {
+    /// Entries guaranteed by a full RNG meter, they're in every chest.
+    guaranteed_entries: Vec<String>,
+    wanted_entries: &'a [String],
+    rolled_entries: Vec<Rc<LootEntry>>,
+    result: RollDistributionResult,
+}
+
+impl RollTreeObserver for DistributionData<'_> {
+    fn push_roll(&mut self, entry: &Rc<LootEntry>) {
+        self.rolled_entries.push(Rc::clone(entry));
+    }
+
+    fn pop_roll(&mut self) {
+        self.rolled_entries.pop();
+    }
+
+    fn finish_chest(&mut self, chance: f64) {
+        let mut identifiers = self.guaranteed_entries.clone();
+        identifiers.extend(self.rolled_entries.iter().map(|entry| entry.to_string()));
+
+        let count = identifiers.len();
+        if self.result.item_count_chances.len() <= count {
+            self.result.item_count_chances.resize(count + 1, 0.0);
+        }
+        self.result.item_count_chances[count] += chance;
+
+        for (index, first) in identifiers.iter().enumerate() {
+            for second in identifiers.iter().skip(index + 1) {
+                let key = if first <= second {
+                    (first.clone(), second.clone())
+                } else {
+                    (second.clone(), first.clone())
+                };
+                *self.result.pair_chances.entry(key).or_insert(0.0) += chance;
             }
         }
+
+        if identifiers.iter().any(|i| self.wanted_entries.contains(i)) {
+            self.result.wanted_chance += chance;
+        }
+    }
+}
+
+/// Walks the same roll tree as [`calculate_average_chances`], but keeps track of every full chest instead of each entry's
+/// marginal chance, so counts, pairs and "any of these" chances can be read off the finished chests.
+pub fn calculate_roll_distribution(
+    chest: &LootChest,
+    starting_quality: i16,
+    rng_meter_data: &RngMeterData,
+    wanted_entries: &[String],
+) -> RollDistributionResult {
+    let (entry_data, _, starting_quality) = create_entry_data(chest, starting_quality, rng_meter_data);
+    let mut data = DistributionData {
+        guaranteed_entries: entry_data
+            .weighted_entries
+            .iter()
+            .filter(|entry| entry.borrow().disabled)
+            .map(|entry| entry.borrow().entry.to_string())
+            .collect(),
+        wanted_entries,
+        rolled_entries: Vec::new(),
+        result: Default::default(),
+    };
+
+    process_random_entries(Rc::new(RefCell::new(entry_data)), &mut Default::default(), &mut data, 1.0, starting_quality, 0);
+    data.result
+}
+
+/// The RNG meter XP a run with the score gives, only S runs (270+) fill the meter and only S+ runs (300+) fully.
+pub fn get_rng_meter_xp_for_score(score: i32) -> i32 {
+    match score {
+        s if s >= 300 => s,
+        s if s >= 270 => (s as f64 * 0.7) as i32,
+        _ => 0,
     }
 }
 
@@ -631,11 +786,7 @@ pub fn calculate_amount_of_times_rolled_for_entry(
     let meter_data = calc.rng_meter_data.selected_item.as_ref().unwrap();
     let use_kismets = calc.rng_meter_calculation_use_kismet_feathers;
 
-    let per_run_score_increase = match average_score {
-        s if s >= 300 => s,
-        s if s >= 270 => (s as f64 * 0.7) as i32,
-        _ => 0,
-    };
+    let per_run_score_increase = get_rng_meter_xp_for_score(average_score);
 
     for _ in 0..runs {
         let mut new_meter_xp = None;
@@ -816,3 +967,55 @@ fn generate_possible_rng_meter_scores(
     values.dedup(); // Remove duplicates in case starting_value is min or max
     values
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_average_chances, calculate_roll_distribution, RngMeterData};
+    use crate::catacombs::catacombs_loot::{read_all_chests, LootChest};
+    use crate::catacombs::options::create_selected_rng_meter_item;
+    use std::rc::Rc;
+
+    #[test]
+    fn roll_distribution_matches_the_average_chances() {
+        let chests = read_all_chests(&crate::app::ASSETS_DIR)
+            .remove("f7")
+            .unwrap()
+            .into_iter()
+            .map(Rc::new)
+            .collect::<Vec<Rc<LootChest>>>();
+        let chest = chests.last().unwrap();
+        let quality = chest.base_quality as i16;
+        // an item only in this chest, so a full meter guarantees it here
+        let meter_item = chest
+            .loot
+            .iter()
+            .filter(|entry| !entry.is_essence_and_can_roll_multiple_times())
+            .filter_map(|entry| create_selected_rng_meter_item(entry, &chests))
+            .find(|meter_item| meter_item.lowest_tier_chest_type == chest.chest_type);
+
+        // without the meter, halfway and with a full meter guaranteeing the item
+        for selected_xp in [None, Some(0.5), Some(1.0)] {
+            let rng_meter_data = RngMeterData {
+                selected_item: selected_xp.and(meter_item.clone()),
+                selected_xp: selected_xp.map_or(0, |fraction| (meter_item.as_ref().unwrap().required_xp as f64 * fraction) as i32),
+            };
+
+            let averages = calculate_average_chances(chest, quality, &rng_meter_data);
+            let expected_items: f64 = averages
+                .entries
+                .iter()
+                .filter(|entry| !entry.borrow().entry.is_essence_and_can_roll_multiple_times())
+                .map(|entry| entry.borrow().chance)
+                .sum();
+
+            let distribution = calculate_roll_distribution(chest, quality, &rng_meter_data, &[]);
+            let total_chance: f64 = distribution.item_count_chances.iter().sum();
+            assert!((total_chance - 1.0).abs() < 1e-6, "chests add up to {total_chance}");
+            assert!(
+                (distribution.get_average_item_count() - expected_items).abs() < 1e-6,
+                "{} items on average, but the average chances add up to {expected_items}",
+                distribution.get_average_item_count()
+            );
+        }
+    }
+}
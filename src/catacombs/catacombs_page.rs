@@ -1,10 +1,25 @@
-use crate::catacombs::catacombs_loot::LootChest;
-use crate::catacombs::catacombs_loot_calculator::{cache_chances_per_rng_meter_value, calculate_average_chances, calculate_quality, AveragesCalculationResult, ChanceAndWeight, RandomlySelectedLootEntry, RngMeterCalculation, RngMeterData};
-use crate::catacombs::catacombs_page::CalculatorType::{AveragesLootTable, SpecificEntryRollCombinations, RandomLootTable, RngMeterDeselection};
+use crate::bazaar::auction_client::{self, SharedAuctionClient};
+use crate::bazaar::bazaar_client::SharedBazaarClient;
+use crate::bazaar::fetch_client::fetch_in_background;
+use crate::catacombs::catacombs_loot::{ChestType, LootChest};
+use crate::catacombs::catacombs_loot_calculator::{cache_chances_per_rng_meter_value, calculate_average_chances, calculate_quality, calculate_roll_distribution, AveragesCalculationResult, ChanceAndWeight, RandomlySelectedLootEntry, RngMeterCalculation, RngMeterData, RollDistributionResult};
+use crate::catacombs::catacombs_page::CalculatorType::{AveragesLootTable, SpecificEntryRollCombinations, RandomLootTable, RngMeterDeselection, RollDistribution, ModifierSensitivity, QualityBreakpoints, PinnedComparison, EssenceSummary, DryStreak, LootLogger};
+use crate::catacombs::profile_import::{fetch_profiles, parse_dungeon_profile, DungeonProfile};
+use crate::catacombs::pinned_comparison::{collect_entries, export_comparison_csv, get_entry_chance, PinnedConfiguration};
+use crate::catacombs::dry_streak::{calculate_dry_streak, calculate_meter_run_chances, RunChances};
+use crate::catacombs::loot_log::{calculate_luck, LoggedChest, LootLog, LuckReport, LOOT_LOG_STORAGE_KEY};
+use crate::catacombs::essence_summary::{calculate_floor_essence, ChestEssenceSummary};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::catacombs::batch_opening::{open_chests, BatchOpeningResult};
+use crate::catacombs::breakpoint_calculator::{calculate_quality_breakpoints, EntryBreakpoints, MAX_BREAKPOINT_ROLLS};
+use crate::catacombs::quality_modifiers::QualityModifiers;
+use crate::catacombs::sensitivity_calculator::{calculate_modifier_sensitivity, ModifierStep};
 use crate::catacombs::{catacombs_loot, catacombs_loot_calculator, options};
 use crate::images;
 use eframe::epaint::{Color32, TextureHandle};
 use egui::{Context, Grid, Label, RichText, ScrollArea, SidePanel, TextStyle, TextWrapMode, Ui};
+use convert_case::{Case, Casing};
+use crossbeam_channel::{unbounded, Receiver};
 use egui_extras::{Column, TableBuilder};
 use egui_plot::LineStyle::Solid;
 use egui_plot::{Legend, Line, Plot, PlotPoints};
@@ -29,12 +44,63 @@ pub struct CatacombsLootPage {
     pub forced_s_plus_const: bool,
     pub rng_meter_data: RngMeterData,
 
+    pub profile_import_open: bool,
+    pub profile_import_text: String,
+    pub profile_import_path: String,
+    pub profile_uuid: String,
+    pub profile_api_key: String,
+    pub profile_import_message: Option<String>,
+    profile_fetch_receiver: Option<Receiver<(u64, Option<String>)>>,
+    // key: floor, the RNG meter XP of the last imported profile
+    profile_rng_meter_xp: BTreeMap<String, i32>,
+
     pub calculator_type: CalculatorType,
     hashed_chances: HashMap<u64, AveragesCalculationResult>,
     pub comparison_hash: Option<u64>,
 
+    hashed_roll_distributions: HashMap<u64, RollDistributionResult>,
+    pub wanted_entries: Vec<String>,
+    pub pair_first_entry: Option<String>,
+    pub pair_second_entry: Option<String>,
+
+    hashed_sensitivity_reports: HashMap<u64, Vec<ModifierStep>>,
+    pub sensitivity_target_entry: Option<String>,
+    sensitivity_upgrade_costs: HashMap<String, u64>,
+
+    hashed_breakpoints: HashMap<u64, Vec<EntryBreakpoints>>,
+
+    pub pinned_configurations: Vec<PinnedConfiguration>,
+    pub pinned_configuration_name: String,
+    pinned_comparison_export_message: Option<String>,
+
+    hashed_essence_summaries: HashMap<u64, Vec<ChestEssenceSummary>>,
+    pub essence_run_chests: Vec<ChestType>,
+
+    // value: None if the target entry isn't in the chest
+    hashed_dry_streak_chances: HashMap<u64, Option<RunChances>>,
+    pub dry_streak_target_entry: Option<String>,
+    pub dry_streak_runs: u32,
+    pub dry_streak_score: i32,
+
+    loot_log: LootLog,
+    pub loot_log_received: Vec<String>,
+    hashed_loot_log_chances: HashMap<u64, AveragesCalculationResult>,
+    loot_log_report: Option<LuckReport>,
+    loot_log_export_message: Option<String>,
+
     random_table: Option<Vec<RandomlySelectedLootEntry>>,
     random_table_source_options_hash: Option<u64>,
+    pub casino_mode: CasinoMode,
+    #[cfg(not(target_arch = "wasm32"))]
+    batch_opening: Option<BatchOpeningResult>,
+    #[cfg(not(target_arch = "wasm32"))]
+    batch_opening_source_options_hash: Option<u64>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub batch_opening_chests: u32,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub batch_opening_score: i32,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub batch_opening_target_entry: Option<String>,
 
     rng_meter_calculations: HashMap<u64, Vec<(f64, RngMeterCalculation)>>, // hash -> map of rng deactivate % -> calc
     rng_meter_calculation_cached_chances: HashMap<u64, Vec<(Rc<LootChest>, HashMap<i32, ChanceAndWeight>)>>,
@@ -45,6 +111,8 @@ pub struct CatacombsLootPage {
 
     pub loot: BTreeMap<String, Vec<Rc<LootChest>>>,
     pub images: Rc<HashMap<String, TextureHandle>>,
+    pub bazaar: SharedBazaarClient,
+    pub auctions: SharedAuctionClient,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -53,17 +121,30 @@ pub enum CalculatorType {
     SpecificEntryRollCombinations,
     RandomLootTable,
     RngMeterDeselection,
+    RollDistribution,
+    ModifierSensitivity,
+    QualityBreakpoints,
+    PinnedComparison,
+    EssenceSummary,
+    DryStreak,
+    LootLogger,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CasinoMode {
+    SingleChest,
+    Batch,
 }
 
 impl CalculatorType {
     #[cfg(not(target_arch = "wasm32"))]
     pub fn should_display_rng_meter_section(&self) -> bool {
-        self == &AveragesLootTable || self == &SpecificEntryRollCombinations || self == &RandomLootTable
+        self == &AveragesLootTable || self == &SpecificEntryRollCombinations || self == &RandomLootTable || self == &RollDistribution || self == &ModifierSensitivity || self == &PinnedComparison || self == &EssenceSummary || self == &DryStreak || self == &LootLogger
     }
 
     #[cfg(target_arch = "wasm32")]
     pub fn should_display_rng_meter_section(&self) -> bool {
-        self == &AveragesLootTable || self == &SpecificEntryRollCombinations
+        self == &AveragesLootTable || self == &SpecificEntryRollCombinations || self == &RollDistribution || self == &ModifierSensitivity || self == &PinnedComparison || self == &EssenceSummary || self == &DryStreak || self == &LootLogger
     }
 }
 
@@ -82,6 +163,8 @@ impl eframe::App for CatacombsLootPage {
                         .spacing([15.0, 4.0])
                         .striped(true)
                         .show(ui, |ui| {
+                            options::add_profile_import_options(self, ui);
+                            ui.end_row();
                             options::add_treasure_talisman_options(self, ui);
                             ui.end_row();
                             options::add_boss_luck_options(self, ui);
@@ -101,6 +184,42 @@ impl eframe::App for CatacombsLootPage {
                                 ui.end_row();
                             }
 
+                            #[cfg(not(target_arch = "wasm32"))]
+                            if self.calculator_type == RandomLootTable && self.casino_mode == CasinoMode::Batch {
+                                options::add_batch_opening_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == DryStreak {
+                                options::add_dry_streak_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == LootLogger {
+                                options::add_loot_log_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == RollDistribution {
+                                options::add_roll_distribution_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == ModifierSensitivity {
+                                options::add_modifier_sensitivity_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == PinnedComparison {
+                                options::add_pinned_comparison_options(self, ui);
+                                ui.end_row();
+                            }
+
+                            if self.calculator_type == AveragesLootTable || self.calculator_type == EssenceSummary {
+                                options::add_price_options(self, ui);
+                                ui.end_row();
+                            }
+
                             #[cfg(not(target_arch = "wasm32"))]
                             if self.calculator_type == AveragesLootTable && self.get_loot_table_chances().is_some() {
                                 options::add_comparison_options(self, ui);
@@ -117,11 +236,19 @@ impl eframe::App for CatacombsLootPage {
                     ui.selectable_value(&mut self.calculator_type, SpecificEntryRollCombinations, "Roll Combinations");
                     ui.selectable_value(&mut self.calculator_type, RandomLootTable, "Casino");
                     ui.selectable_value(&mut self.calculator_type, RngMeterDeselection, "RNG Meter Deselection Calculator");
+                    ui.selectable_value(&mut self.calculator_type, RollDistribution, "Roll Distribution");
+                    ui.selectable_value(&mut self.calculator_type, ModifierSensitivity, "Modifier Sensitivity");
+                    ui.selectable_value(&mut self.calculator_type, QualityBreakpoints, "Quality Breakpoints");
+                    ui.selectable_value(&mut self.calculator_type, PinnedComparison, "Pinned Comparison");
+                    ui.selectable_value(&mut self.calculator_type, EssenceSummary, "Essence");
+                    ui.selectable_value(&mut self.calculator_type, DryStreak, "Dry Streak");
+                    ui.selectable_value(&mut self.calculator_type, LootLogger, "Loot Log");
                 });
                 ui.separator();
             }
 
-            if self.floor.is_none() || self.chest.is_none() {
+            // the log can be looked at without a chest selected, only logging needs one
+            if (self.floor.is_none() || self.chest.is_none()) && self.calculator_type != LootLogger {
                 ui.label("Select a floor and chest to see its loot.");
                 return;
             }
@@ -175,8 +302,253 @@ impl eframe::App for CatacombsLootPage {
                         self.add_loot_combinations_section(ui);
                     });
                 }
+                RollDistribution => {
+                    let hash = self.generate_roll_distribution_hash();
+
+                    if !self.hashed_roll_distributions.contains_key(&hash) {
+                        let chest = self.chest.as_ref().unwrap();
+                        let starting_quality = calculate_quality(
+                            chest,
+                            self.treasure_accessory_multiplier,
+                            self.boss_luck_increase,
+                            self.catacombs_box_attribute_increase,
+                            self.s_plus || chest.require_s_plus(),
+                        );
+
+                        let distribution = calculate_roll_distribution(chest, starting_quality, &self.rng_meter_data, &self.wanted_entries);
+                        self.hashed_roll_distributions.insert(hash, distribution);
+                    }
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("roll_distribution").show(ui, |ui| {
+                        self.add_roll_distribution_section(ui);
+                    });
+                }
+                ModifierSensitivity => {
+                    if self.sensitivity_target_entry.is_none() {
+                        ui.label("Select an item to see how each modifier changes its chance.");
+                        return;
+                    }
+
+                    let hash = self.generate_sensitivity_report_hash();
+                    if !self.hashed_sensitivity_reports.contains_key(&hash) {
+                        let chest = self.chest.as_ref().unwrap();
+                        let report = calculate_modifier_sensitivity(
+                            chest,
+                            self.sensitivity_target_entry.as_ref().unwrap(),
+                            self.get_quality_modifiers(),
+                            &self.rng_meter_data,
+                        );
+                        self.hashed_sensitivity_reports.insert(hash, report);
+                    }
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("modifier_sensitivity").show(ui, |ui| {
+                        self.add_modifier_sensitivity_section(ui);
+                    });
+                }
+                QualityBreakpoints => {
+                    let hash = self.generate_breakpoints_hash();
+                    if let Entry::Vacant(e) = self.hashed_breakpoints.entry(hash) {
+                        e.insert(calculate_quality_breakpoints(self.chest.as_ref().unwrap()));
+                    }
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("quality_breakpoints").show(ui, |ui| {
+                        self.add_quality_breakpoints_section(ui);
+                    });
+                }
+                PinnedComparison => {
+                    if self.pinned_configurations.is_empty() {
+                        ui.label("Pin a configuration to start comparing. Pins are only kept until the app is closed.");
+                        return;
+                    }
+
+                    for configuration in self.pinned_configurations.iter() {
+                        if let Entry::Vacant(e) = self.hashed_chances.entry(configuration.hash) {
+                            e.insert(calculate_average_chances(&configuration.chest, configuration.calculate_quality(), &configuration.rng_meter_data));
+                        }
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Copy as CSV").clicked() {
+                            self.pinned_comparison_export_message = Some(
+                                match export_comparison_csv(&self.pinned_configurations, &self.hashed_chances) {
+                                    Ok(csv) => {
+                                        ui.ctx().copy_text(csv);
+                                        "Copied to clipboard!".to_string()
+                                    }
+                                    Err(message) => format!("Failed to export: {message}"),
+                                },
+                            );
+                        }
+                        if let Some(message) = &self.pinned_comparison_export_message {
+                            ui.label(message);
+                        }
+                    });
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("pinned_comparison").show(ui, |ui| {
+                        self.add_pinned_comparison_section(ui);
+                    });
+                }
+                EssenceSummary => {
+                    let hash = self.generate_essence_summary_hash();
+                    if !self.hashed_essence_summaries.contains_key(&hash) {
+                        let chests = self.loot.get(self.floor.as_ref().unwrap()).unwrap();
+                        let modifiers = QualityModifiers { s_plus: self.s_plus, ..self.get_quality_modifiers() };
+                        let summaries = calculate_floor_essence(chests, &modifiers, &self.rng_meter_data);
+                        self.hashed_essence_summaries.insert(hash, summaries);
+                    }
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("essence_summary").show(ui, |ui| {
+                        self.add_essence_summary_section(ui);
+                    });
+                }
+                DryStreak => {
+                    let hash = self.generate_dry_streak_hash();
+                    if let Entry::Vacant(e) = self.hashed_dry_streak_chances.entry(hash) {
+                        let chest = self.chest.as_ref().unwrap();
+                        let starting_quality = calculate_quality(
+                            chest,
+                            self.treasure_accessory_multiplier,
+                            self.boss_luck_increase,
+                            self.catacombs_box_attribute_increase,
+                            self.s_plus || chest.require_s_plus(),
+                        );
+
+                        let chances = match (&self.rng_meter_data.selected_item, &self.dry_streak_target_entry) {
+                            (Some(meter_item), _) => chest.has_matching_entry_identifier(&meter_item.identifier).then(|| {
+                                let meter_xp_per_run = catacombs_loot_calculator::get_rng_meter_xp_for_score(self.dry_streak_score);
+                                calculate_meter_run_chances(chest, starting_quality, meter_item, meter_xp_per_run)
+                            }),
+                            (None, Some(target_entry)) => calculate_average_chances(chest, starting_quality, &RngMeterData::default())
+                                .entries
+                                .iter()
+                                .find(|entry| entry.borrow().entry.to_string() == *target_entry)
+                                .map(|entry| RunChances::without_meter(entry.borrow().chance)),
+                            (None, None) => None,
+                        };
+                        e.insert(chances);
+                    }
+
+                    ScrollArea::vertical().id_salt("dry_streak").show(ui, |ui| {
+                        self.add_dry_streak_section(ui);
+                    });
+                }
+                LootLogger => {
+                    for logged_chest in self.loot_log.chests.iter() {
+                        if let Entry::Vacant(e) = self.hashed_loot_log_chances.entry(logged_chest.generate_chances_hash()) {
+                            if let Some(chances) = calculate_logged_chest_chances(logged_chest, &self.loot) {
+                                e.insert(chances);
+                            }
+                        }
+                    }
+
+                    if self.loot_log_report.is_none() {
+                        let logged_chests = self
+                            .loot_log
+                            .chests
+                            .iter()
+                            .filter_map(|chest| self.hashed_loot_log_chances.get(&chest.generate_chances_hash()).map(|chances| (chest, chances)))
+                            .collect::<Vec<(&LoggedChest, &AveragesCalculationResult)>>();
+                        self.loot_log_report = Some(calculate_luck(&logged_chests));
+                    }
+
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} chest(s) logged", self.loot_log.chests.len().to_formatted_string(&en)));
+                        if ui.button("Copy as CSV").clicked() {
+                            self.loot_log_export_message = Some(match self.loot_log.export_csv() {
+                                Ok(csv) => {
+                                    ui.ctx().copy_text(csv);
+                                    "Copied to clipboard!".to_string()
+                                }
+                                Err(message) => format!("Failed to export: {message}"),
+                            });
+                        }
+                        if !self.loot_log.chests.is_empty() && ui.button("Clear Log").clicked() {
+                            self.loot_log.chests.clear();
+                            self.loot_log_report = None;
+                        }
+                        if let Some(message) = &self.loot_log_export_message {
+                            ui.label(message);
+                        }
+                    });
+
+                    ScrollArea::vertical().id_salt("loot_log").show(ui, |ui| {
+                        self.add_loot_log_section(ui);
+                    });
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                RandomLootTable if self.casino_mode == CasinoMode::Batch => {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.casino_mode, CasinoMode::SingleChest, "Single Chest");
+                        ui.selectable_value(&mut self.casino_mode, CasinoMode::Batch, "Batch");
+                    });
+
+                    let hash = self.generate_batch_opening_hash();
+                    let current_hash = self.batch_opening_source_options_hash.unwrap_or(0);
+
+                    let mut button_clicked = false;
+                    ui.horizontal(|ui| {
+                        if ui.button(format!("Open {} chests!", self.batch_opening_chests.to_formatted_string(&en))).clicked() {
+                            button_clicked = true;
+                        }
+                        if self.batch_opening.is_some() && hash != current_hash {
+                            ui.add(Label::new("The settings used to open these chests don't match the current settings.").wrap_mode(TextWrapMode::Wrap));
+                        }
+                    });
+
+                    let chest = Rc::clone(self.chest.as_ref().unwrap());
+                    let starting_quality = calculate_quality(
+                        &chest,
+                        self.treasure_accessory_multiplier,
+                        self.boss_luck_increase,
+                        self.catacombs_box_attribute_increase,
+                        self.s_plus || chest.require_s_plus(),
+                    );
+
+                    // the theoretical chances to compare against
+                    if self.get_loot_table_chances().is_none() {
+                        let new_chances = calculate_average_chances(&chest, starting_quality, &self.rng_meter_data);
+                        self.hashed_chances.insert(self.generate_loot_table_hash(), new_chances);
+                    }
+
+                    if button_clicked {
+                        let target_entry = self
+                            .batch_opening_target_entry
+                            .clone()
+                            .or(self.rng_meter_data.selected_item.as_ref().map(|item| item.identifier.clone()));
+                        self.batch_opening = Some(open_chests(
+                            &chest,
+                            starting_quality,
+                            &self.rng_meter_data,
+                            self.batch_opening_chests,
+                            self.batch_opening_score,
+                            target_entry,
+                        ));
+                        self.batch_opening_source_options_hash = Some(hash);
+                    }
+
+                    // Horizontal scrolling is done here, vertical scrolling is done on the table scrolling end
+                    // (this took painfully long to figure out)
+                    ScrollArea::horizontal().id_salt("cata_batch_opening").show(ui, |ui| {
+                        self.add_batch_opening_section(ui);
+                    });
+                }
                 #[cfg(not(target_arch = "wasm32"))]
                 RandomLootTable => {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.casino_mode, CasinoMode::SingleChest, "Single Chest");
+                        ui.selectable_value(&mut self.casino_mode, CasinoMode::Batch, "Batch");
+                    });
+
                     let hash = self.generate_loot_table_hash();
                     let current_hash = self.random_table_source_options_hash.unwrap_or(0);
 
@@ -236,11 +608,7 @@ impl eframe::App for CatacombsLootPage {
                     let meter_xp = self.rng_meter_data.selected_xp;
                     let meter_data = self.rng_meter_data.selected_item.as_ref().unwrap();
 
-                    let per_run_score_increase = match 300 {
-                        s if s >= 300 => s,
-                        s if s >= 270 => (s as f64 * 0.7) as i32,
-                        _ => 0,
-                    };
+                    let per_run_score_increase = catacombs_loot_calculator::get_rng_meter_xp_for_score(300);
 
                     if button_clicked {
                         let chest_data_hash = self.generate_rng_meter_calculation_chests_and_item_hash();
@@ -382,11 +750,18 @@ impl eframe::App for CatacombsLootPage {
     }
 
     /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, LOOT_LOG_STORAGE_KEY, &self.loot_log);
+    }
 }
 
 impl CatacombsLootPage {
-    pub fn new(images: Rc<HashMap<String, TextureHandle>>) -> Self {
+    pub fn new(
+        images: Rc<HashMap<String, TextureHandle>>,
+        bazaar: SharedBazaarClient,
+        auctions: SharedAuctionClient,
+        storage: Option<&dyn eframe::Storage>,
+    ) -> Self {
         Self {
             floor: None,
             chest: None,
@@ -398,12 +773,63 @@ impl CatacombsLootPage {
             forced_s_plus_const: true,
             rng_meter_data: Default::default(),
 
+            profile_import_open: false,
+            profile_import_text: String::new(),
+            profile_import_path: String::new(),
+            profile_uuid: String::new(),
+            profile_api_key: String::new(),
+            profile_import_message: None,
+            profile_fetch_receiver: None,
+            profile_rng_meter_xp: BTreeMap::new(),
+
             hashed_chances: HashMap::new(),
             calculator_type: AveragesLootTable,
             random_table: None,
             random_table_source_options_hash: None,
+            casino_mode: CasinoMode::SingleChest,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_opening: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_opening_source_options_hash: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_opening_chests: 1000,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_opening_score: 300,
+            #[cfg(not(target_arch = "wasm32"))]
+            batch_opening_target_entry: None,
             comparison_hash: None,
 
+            hashed_roll_distributions: HashMap::new(),
+            wanted_entries: Vec::new(),
+            pair_first_entry: None,
+            pair_second_entry: None,
+
+            hashed_sensitivity_reports: HashMap::new(),
+            sensitivity_target_entry: None,
+            sensitivity_upgrade_costs: HashMap::new(),
+
+            hashed_breakpoints: HashMap::new(),
+
+            pinned_configurations: Vec::new(),
+            pinned_configuration_name: String::new(),
+            pinned_comparison_export_message: None,
+
+            hashed_essence_summaries: HashMap::new(),
+            essence_run_chests: Vec::new(),
+
+            hashed_dry_streak_chances: HashMap::new(),
+            dry_streak_target_entry: None,
+            dry_streak_runs: 100,
+            dry_streak_score: 300,
+
+            loot_log: storage
+                .and_then(|storage| eframe::get_value(storage, LOOT_LOG_STORAGE_KEY))
+                .unwrap_or_default(),
+            loot_log_received: Vec::new(),
+            hashed_loot_log_chances: HashMap::new(),
+            loot_log_report: None,
+            loot_log_export_message: None,
+
             rng_meter_calculations: HashMap::new(),
             rng_meter_calculation_cached_chances: HashMap::new(),
             rng_meter_calculation_hash: None,
@@ -416,10 +842,15 @@ impl CatacombsLootPage {
                 .map(|(k, v)| (k, v.into_iter().map(Rc::new).collect()))
                 .collect(),
             images,
+            bazaar,
+            auctions,
         }
     }
 
     fn add_loot_section(&mut self, ui: &mut Ui) {
+        let system_time = web_time::Instant::now();
+        self.bazaar.borrow_mut().fetch_if_missing(system_time);
+
         let chances = self.get_loot_table_chances();
         if chances.is_none() {
             return;
@@ -452,6 +883,7 @@ impl CatacombsLootPage {
             .column(Column::auto())
             .column(Column::auto())
             .column(Column::auto())
+            .column(Column::auto())
             .drag_to_scroll(true)
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height);
@@ -471,6 +903,9 @@ impl CatacombsLootPage {
             header.col(|ui| {
                 ui.strong("Coins Cost");
             });
+            header.col(|ui| {
+                ui.strong("Value");
+            });
             header.col(|ui| {
                 ui.strong(format!("Quality ({})", starting_quality));
             });
@@ -538,12 +973,29 @@ impl CatacombsLootPage {
 
                         let text = entry.to_string();
                         let page_url = entry.get_wiki_page_name();
-                        ui.hyperlink_to(text, page_url);
+                        let response = ui.hyperlink_to(text, page_url);
+                        auction_client::add_item_price_hover(
+                            response,
+                            &self.bazaar,
+                            &self.auctions,
+                            entry.get_bazaar_id().as_deref(),
+                            entry.get_auction_id().as_deref(),
+                        );
                     });
                     row.col(|ui| {
                         ui.label(RichText::new((chest.base_cost + entry.get_added_chest_price()).to_formatted_string(&en))
                             .color(Color32::from_rgb(255, 170, 0)));
                     });
+                    row.col(|ui| {
+                        match entry.get_value(&self.bazaar.borrow(), &self.auctions.borrow()) {
+                            Some(value) => {
+                                ui.label(RichText::new((value as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                            }
+                            None => {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            }
+                        }
+                    });
                     row.col(|ui| {
                         ui.label(
                             RichText::new(format!("{}", entry.get_quality()))
@@ -701,102 +1153,648 @@ impl CatacombsLootPage {
         });
     }
 
-    fn add_random_loot_section(&mut self, ui: &mut Ui) {
-        if self.random_table.is_none() {
+    fn add_roll_distribution_section(&mut self, ui: &mut Ui) {
+        let distribution = self.hashed_roll_distributions.get(&self.generate_roll_distribution_hash());
+        if distribution.is_none() {
             return;
         }
-        let loot = self.random_table.as_ref().unwrap();
+        let distribution = distribution.unwrap();
 
         let text_height = TextStyle::Body
             .resolve(ui.style())
             .size
             .max(ui.spacing().interact_size.y);
 
-        let chest = self.chest.as_ref().unwrap();
-        let starting_quality = calculate_quality(
-            chest,
-            self.treasure_accessory_multiplier,
-            self.boss_luck_increase,
-            self.catacombs_box_attribute_increase,
-            self.s_plus || chest.require_s_plus(),
-        );
+        Grid::new("roll_distribution_summary")
+            .num_columns(2)
+            .spacing([15.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Average Items Per Chest:");
+                ui.label(
+                    RichText::new(format!("{:.3}", distribution.get_average_item_count()).trim_end_matches('0').trim_end_matches('.'))
+                        .color(Color32::from_rgb(85, 255, 255)),
+                );
+                ui.end_row();
+
+                ui.label(format!("At Least One Wanted Item ({}):", self.wanted_entries.len()));
+                ui.horizontal(|ui| {
+                    fill_in_chance_column(ui, distribution.wanted_chance);
+                });
+                ui.end_row();
+
+                if let (Some(first), Some(second)) = (&self.pair_first_entry, &self.pair_second_entry) {
+                    ui.label(format!("{first} + {second}:"));
+                    ui.horizontal(|ui| {
+                        fill_in_chance_column(ui, distribution.get_pair_chance(first, second));
+                    });
+                    ui.end_row();
+                }
+            });
+        ui.separator();
 
         let available_height = ui.available_height();
-        let table = TableBuilder::new(ui)
+        TableBuilder::new(ui)
             .striped(true)
             .resizable(false)
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
             .column(Column::auto())
             .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto())
-            .column(Column::auto())
             .drag_to_scroll(true)
             .min_scrolled_height(0.0)
-            .max_scroll_height(available_height);
-
-        table.header(20.0, |mut header| {
-            header.col(|ui| {
-                ui.strong("Entry");
-            });
-            header.col(|ui| {
-                ui.strong("Added Cost");
-            });
-            header.col(|ui| {
-                ui.strong(format!("Quality ({})", starting_quality));
-            });
-            header.col(|ui| {
-                ui.strong("Weight (Total)");
-            });
-            header.col(|ui| {
-                ui.strong("Slot Roll Chance");
-            });
-            header.col(|ui| {
-                ui.strong("Combined Chances");
-            });
-        })
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Items (Excluding Essence)");
+                });
+                header.col(|ui| {
+                    ui.strong("Chance");
+                });
+            })
             .body(|mut body| {
-                for entry in loot.iter() {
-                    let weight = entry.used_weight;
-                    let total_weight = entry.total_weight;
-                    let roll_chance = entry.roll_chance;
-                    let overall_chance = entry.overall_chance;
-                    let before_quality = entry.before_quality;
-                    let after_quality = before_quality - entry.entry.get_quality();
-                    let entry = &entry.entry;
+                for (count, chance) in distribution.item_count_chances.iter().enumerate() {
+                    if *chance == 0.0 {
+                        continue;
+                    }
 
                     body.row(text_height, |mut row| {
                         row.col(|ui| {
-                            images::add_first_valid_image(
-                                &self.images,
-                                ui,
-                                entry.get_possible_file_names(),
-                            );
-
-                            let text = entry.to_string();
-                            let page_url = entry.get_wiki_page_name();
-                            ui.hyperlink_to(text, page_url);
+                            ui.label(RichText::new(count.to_string()).color(Color32::from_rgb(85, 255, 85)));
                         });
                         row.col(|ui| {
-                            ui.label(
-                                RichText::new(
-                                    entry.get_added_chest_price().to_formatted_string(&en),
-                                )
-                                    .color(Color32::from_rgb(255, 170, 0)),
-                            );
+                            fill_in_chance_column(ui, *chance);
                         });
-                        row.col(|ui| {
-                            ui.label(
-                                RichText::new(format!("{}", entry.get_quality()))
-                                    .color(Color32::from_rgb(85, 255, 255)),
-                            );
-                            ui.label(format!(
-                                "({} -> {})",
-                                before_quality,
-                                format!("{}", after_quality as f32)
-                                    .trim_end_matches('0')
-                                    .trim_end_matches('.')
+                    });
+                }
+            });
+    }
+
+    fn add_modifier_sensitivity_section(&mut self, ui: &mut Ui) {
+        let report = self.hashed_sensitivity_reports.get(&self.generate_sensitivity_report_hash());
+        if report.is_none() {
+            return;
+        }
+        let mut report = report.unwrap().clone();
+
+        let current_chance = report.iter().find(|s| s.is_current).map_or(0.0, |s| s.chance);
+        let get_chance_per_coin = |step: &ModifierStep, costs: &HashMap<String, u64>| -> Option<f64> {
+            let cost = *costs.get(&step.get_cost_key())?;
+            if step.is_upgrade && cost > 0 {
+                Some((step.chance - current_chance) / cost as f64)
+            } else {
+                None
+            }
+        };
+
+        // cheapest upgrades first when costs are given, everything else keeps the modifier order
+        report.sort_by(|a, b| {
+            let a_chance_per_coin = get_chance_per_coin(a, &self.sensitivity_upgrade_costs);
+            let b_chance_per_coin = get_chance_per_coin(b, &self.sensitivity_upgrade_costs);
+            match (a_chance_per_coin, b_chance_per_coin) {
+                (Some(a), Some(b)) => b.total_cmp(&a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        });
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 7)
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Modifier");
+                });
+                header.col(|ui| {
+                    ui.strong("Level");
+                });
+                header.col(|ui| {
+                    ui.strong("Quality");
+                });
+                header.col(|ui| {
+                    ui.strong("Average Chance");
+                });
+                header.col(|ui| {
+                    ui.strong("Difference");
+                });
+                header.col(|ui| {
+                    ui.strong("Upgrade Cost");
+                });
+                header.col(|ui| {
+                    ui.strong("Chance Per 1M Coins");
+                });
+            })
+            .body(|mut body| {
+                for step in report.iter() {
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            ui.label(step.modifier_type.get_label_name());
+                        });
+                        row.col(|ui| {
+                            if step.is_current {
+                                ui.label(RichText::new(format!("{} (current)", step.label)).color(Color32::from_rgb(85, 255, 85)));
+                            } else {
+                                ui.label(&step.label);
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(format!("{}", step.quality)).color(Color32::from_rgb(85, 255, 255)));
+                        });
+                        row.col(|ui| {
+                            fill_in_chance_column(ui, step.chance);
+                        });
+                        row.col(|ui| {
+                            if step.is_current {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            } else if current_chance == 0.0 || step.chance == 0.0 {
+                                ui.label(RichText::new(if step.chance > current_chance { "Now Rollable" } else if step.chance < current_chance { "No Longer Rollable" } else { "Identical" }).color(Color32::GRAY));
+                            } else {
+                                fill_in_chance_differences_column(ui, step.chance, current_chance);
+                            }
+                        });
+                        row.col(|ui| {
+                            if step.is_upgrade {
+                                let cost = self.sensitivity_upgrade_costs.entry(step.get_cost_key()).or_insert(0);
+                                ui.add(egui::DragValue::new(cost).speed(10_000.0).suffix(" coins"));
+                            }
+                        });
+                        row.col(|ui| {
+                            if let Some(chance_per_coin) = get_chance_per_coin(step, &self.sensitivity_upgrade_costs) {
+                                let text = format!("{:.6}", chance_per_coin * 1_000_000.0 * 100.0);
+                                ui.label(RichText::new(format!("{}%", text.trim_end_matches('0').trim_end_matches('.'))).color(Color32::from_rgb(255, 255, 85)));
+                            }
+                        });
+                    });
+                }
+            });
+    }
+
+    fn add_quality_breakpoints_section(&mut self, ui: &mut Ui) {
+        let breakpoints = self.hashed_breakpoints.get(&self.generate_breakpoints_hash());
+        if breakpoints.is_none() {
+            return;
+        }
+        let breakpoints = breakpoints.unwrap();
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let starting_quality = self.get_quality_modifiers().calculate_quality(self.chest.as_ref().unwrap());
+
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 2 + MAX_BREAKPOINT_ROLLS)
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Entry");
+                });
+                header.col(|ui| {
+                    ui.strong("Quality");
+                });
+                for roll in 1..=MAX_BREAKPOINT_ROLLS {
+                    header.col(|ui| {
+                        ui.strong(format!("Roll #{roll}"))
+                            .on_hover_text("Required quality, along with the modifiers with the fewest upgrades that reach it");
+                    });
+                }
+            })
+            .body(|mut body| {
+                for entry_breakpoints in breakpoints.iter() {
+                    let entry = &entry_breakpoints.entry;
+
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            images::add_first_valid_image(
+                                &self.images,
+                                ui,
+                                entry.get_possible_file_names(),
+                            );
+
+                            let text = entry.to_string();
+                            let page_url = entry.get_wiki_page_name();
+                            ui.hyperlink_to(text, page_url);
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(format!("{}", entry.get_quality())).color(Color32::from_rgb(85, 255, 255)));
+                        });
+
+                        for breakpoint in entry_breakpoints.roll_breakpoints.iter() {
+                            row.col(|ui| {
+                                let width = ui.fonts(|f| f.glyph_width(&TextStyle::Body.resolve(ui.style()), ' '));
+                                ui.spacing_mut().item_spacing.x = width;
+
+                                match (breakpoint.required_quality, breakpoint.fewest_upgrade_modifiers) {
+                                    (None, _) => {
+                                        ui.label(RichText::new("-").color(Color32::GRAY));
+                                    }
+                                    (Some(required_quality), modifiers) => {
+                                        let color = if starting_quality >= required_quality {
+                                            Color32::from_rgb(85, 255, 85)
+                                        } else {
+                                            Color32::from_rgb(255, 85, 85)
+                                        };
+                                        ui.label(RichText::new(format!("{required_quality}")).color(color));
+
+                                        let modifiers_text = modifiers.map_or("Unreachable".to_string(), |m| m.describe());
+                                        ui.label(RichText::new(format!("({modifiers_text})")).color(Color32::GRAY));
+                                    }
+                                }
+                            });
+                        }
+                    });
+                }
+            });
+    }
+
+    fn add_pinned_comparison_section(&mut self, ui: &mut Ui) {
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let pinned = &self.pinned_configurations;
+        let chances = pinned
+            .iter()
+            .map(|c| self.hashed_chances.get(&c.hash))
+            .collect::<Option<Vec<&AveragesCalculationResult>>>();
+        if chances.is_none() {
+            return;
+        }
+        let chances = chances.unwrap();
+
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 2 * pinned.len())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Entry");
+                });
+                for (index, configuration) in pinned.iter().enumerate() {
+                    header.col(|ui| {
+                        ui.strong(&configuration.name).on_hover_text(configuration.describe());
+                    });
+                    if index > 0 {
+                        header.col(|ui| {
+                            ui.strong(format!("vs {}", pinned[0].name));
+                        });
+                    }
+                }
+            })
+            .body(|mut body| {
+                body.row(text_height, |mut row| {
+                    row.col(|ui| {
+                        ui.label("Quality");
+                    });
+                    let base_quality = pinned[0].calculate_quality();
+                    for (index, configuration) in pinned.iter().enumerate() {
+                        let quality = configuration.calculate_quality();
+                        row.col(|ui| {
+                            ui.label(RichText::new(format!("{quality}")).color(Color32::from_rgb(85, 255, 255)));
+                        });
+                        if index > 0 {
+                            row.col(|ui| {
+                                let difference = quality - base_quality;
+                                if difference == 0 {
+                                    ui.label("Identical");
+                                } else if difference > 0 {
+                                    ui.label(RichText::new(format!("+{difference}")).color(Color32::from_rgb(85, 255, 85)));
+                                } else {
+                                    ui.label(RichText::new(format!("{difference}")).color(Color32::from_rgb(255, 85, 85)));
+                                }
+                            });
+                        }
+                    }
+                });
+
+                for entry in collect_entries(pinned) {
+                    let name = entry.to_string();
+                    let entry_chances = chances
+                        .iter()
+                        .map(|c| get_entry_chance(c, &name).filter(|chance| *chance > 0.0))
+                        .collect::<Vec<Option<f64>>>();
+
+                    if entry_chances.iter().all(|c| c.is_none()) {
+                        continue;
+                    }
+
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            images::add_first_valid_image(
+                                &self.images,
+                                ui,
+                                entry.get_possible_file_names(),
+                            );
+
+                            let page_url = entry.get_wiki_page_name();
+                            ui.hyperlink_to(name, page_url);
+                        });
+
+                        for (index, chance) in entry_chances.iter().enumerate() {
+                            row.col(|ui| match chance {
+                                Some(chance) => fill_in_chance_column(ui, *chance),
+                                None => {
+                                    ui.label(RichText::new("-").color(Color32::GRAY));
+                                }
+                            });
+                            if index > 0 {
+                                row.col(|ui| match (chance, entry_chances[0]) {
+                                    (Some(current), Some(base)) => fill_in_chance_differences_column(ui, *current, base),
+                                    _ => {
+                                        ui.label(RichText::new("-").color(Color32::GRAY));
+                                    }
+                                });
+                            }
+                        }
+                    });
+                }
+            });
+    }
+
+    fn add_essence_summary_section(&mut self, ui: &mut Ui) {
+        let system_time = web_time::Instant::now();
+        self.bazaar.borrow_mut().fetch_if_missing(system_time);
+
+        let summaries = self.hashed_essence_summaries.get(&self.generate_essence_summary_hash());
+        if summaries.is_none() {
+            return;
+        }
+        let summaries = summaries.unwrap();
+        let bazaar = self.bazaar.borrow();
+        let auctions = self.auctions.borrow();
+
+        let mut essence_types = summaries.iter().flat_map(|s| s.essence.keys().cloned()).collect::<Vec<String>>();
+        essence_types.sort();
+        essence_types.dedup();
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let add_amount = |ui: &mut Ui, amount: f64| {
+            ui.label(
+                RichText::new(format!("{amount:.2}").trim_end_matches('0').trim_end_matches('.'))
+                    .color(Color32::from_rgb(85, 255, 255)),
+            )
+        };
+        let add_coins = |ui: &mut Ui, coins: Option<f64>| match coins {
+            Some(coins) => {
+                ui.label(RichText::new((coins as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+            }
+            None => {
+                ui.label(RichText::new("-").color(Color32::GRAY));
+            }
+        };
+        let add_share = |ui: &mut Ui, essence_value: Option<f64>, chest_value: Option<f64>| match (essence_value, chest_value) {
+            (Some(essence_value), Some(chest_value)) if chest_value > 0.0 => {
+                ui.label(
+                    RichText::new(format!("{:.1}%", essence_value / chest_value * 100.0))
+                        .color(Color32::from_rgb(85, 255, 85)),
+                );
+            }
+            _ => {
+                ui.label(RichText::new("-").color(Color32::GRAY));
+            }
+        };
+
+        let mut run_essence = vec![0.0; essence_types.len()];
+        let mut run_essence_value: Option<f64> = None;
+        let mut run_chest_value: Option<f64> = None;
+        let mut run_chest_cost = 0;
+
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 6 + essence_types.len())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Opened");
+                });
+                header.col(|ui| {
+                    ui.strong("Chest");
+                });
+                header.col(|ui| {
+                    ui.strong("Coins Cost");
+                });
+                for essence_type in essence_types.iter() {
+                    header.col(|ui| {
+                        ui.strong(format!("{} Essence", essence_type.to_case(Case::Title)));
+                    });
+                }
+                header.col(|ui| {
+                    ui.strong("Essence Value");
+                });
+                header.col(|ui| {
+                    ui.strong("Chest Value");
+                });
+                header.col(|ui| {
+                    ui.strong("From Essence");
+                });
+            })
+            .body(|mut body| {
+                for summary in summaries.iter() {
+                    let chest_type = summary.get_chest_type();
+                    let essence_value = summary.get_essence_value(&bazaar);
+                    let chest_value = summary.get_chest_value(&bazaar, &auctions);
+
+                    let opened = self.essence_run_chests.contains(chest_type);
+                    if opened {
+                        for (index, essence_type) in essence_types.iter().enumerate() {
+                            run_essence[index] += summary.essence.get(essence_type).map_or(0.0, |e| e.get_total());
+                        }
+                        if let Some(essence_value) = essence_value {
+                            run_essence_value = Some(run_essence_value.unwrap_or(0.0) + essence_value);
+                        }
+                        if let Some(chest_value) = chest_value {
+                            run_chest_value = Some(run_chest_value.unwrap_or(0.0) + chest_value);
+                        }
+                        run_chest_cost += summary.chest.base_cost;
+                    }
+
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            let mut checked = opened;
+                            if ui.checkbox(&mut checked, "").changed() {
+                                if checked {
+                                    self.essence_run_chests.push(chest_type.clone());
+                                } else {
+                                    self.essence_run_chests.retain(|c| c != chest_type);
+                                }
+                            }
+                        });
+                        row.col(|ui| {
+                            let chest_name = format!("{chest_type:?}");
+                            images::add_image(&self.images, ui, &format!("{}_chest.png", chest_name.to_lowercase()));
+                            ui.label(chest_name);
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(summary.chest.base_cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                        });
+                        for essence_type in essence_types.iter() {
+                            row.col(|ui| match summary.essence.get(essence_type) {
+                                Some(essence) => {
+                                    add_amount(ui, essence.get_total()).on_hover_text(format!(
+                                        "Guaranteed: {}\nRolled: {:.3}",
+                                        essence.guaranteed, essence.rolled
+                                    ));
+                                }
+                                None => {
+                                    ui.label(RichText::new("-").color(Color32::GRAY));
+                                }
+                            });
+                        }
+                        row.col(|ui| add_coins(ui, essence_value));
+                        row.col(|ui| add_coins(ui, chest_value));
+                        row.col(|ui| add_share(ui, essence_value, chest_value));
+                    });
+                }
+
+                body.row(text_height, |mut row| {
+                    row.col(|_| {});
+                    row.col(|ui| {
+                        ui.strong("Per Run").on_hover_text("Every chest ticked as opened, added together.");
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(run_chest_cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                    });
+                    for amount in run_essence.iter() {
+                        row.col(|ui| {
+                            add_amount(ui, *amount);
+                        });
+                    }
+                    row.col(|ui| add_coins(ui, run_essence_value));
+                    row.col(|ui| add_coins(ui, run_chest_value));
+                    row.col(|ui| add_share(ui, run_essence_value, run_chest_value));
+                });
+            });
+    }
+
+    fn add_random_loot_section(&mut self, ui: &mut Ui) {
+        if self.random_table.is_none() {
+            return;
+        }
+        let loot = self.random_table.as_ref().unwrap();
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let chest = self.chest.as_ref().unwrap();
+        let starting_quality = calculate_quality(
+            chest,
+            self.treasure_accessory_multiplier,
+            self.boss_luck_increase,
+            self.catacombs_box_attribute_increase,
+            self.s_plus || chest.require_s_plus(),
+        );
+
+        let available_height = ui.available_height();
+        let table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height);
+
+        table.header(20.0, |mut header| {
+            header.col(|ui| {
+                ui.strong("Entry");
+            });
+            header.col(|ui| {
+                ui.strong("Added Cost");
+            });
+            header.col(|ui| {
+                ui.strong(format!("Quality ({})", starting_quality));
+            });
+            header.col(|ui| {
+                ui.strong("Weight (Total)");
+            });
+            header.col(|ui| {
+                ui.strong("Slot Roll Chance");
+            });
+            header.col(|ui| {
+                ui.strong("Combined Chances");
+            });
+        })
+            .body(|mut body| {
+                for entry in loot.iter() {
+                    let weight = entry.used_weight;
+                    let total_weight = entry.total_weight;
+                    let roll_chance = entry.roll_chance;
+                    let overall_chance = entry.overall_chance;
+                    let before_quality = entry.before_quality;
+                    let after_quality = before_quality - entry.entry.get_quality();
+                    let entry = &entry.entry;
+
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            images::add_first_valid_image(
+                                &self.images,
+                                ui,
+                                entry.get_possible_file_names(),
+                            );
+
+                            let text = entry.to_string();
+                            let page_url = entry.get_wiki_page_name();
+                            ui.hyperlink_to(text, page_url);
+                        });
+                        row.col(|ui| {
+                            ui.label(
+                                RichText::new(
+                                    entry.get_added_chest_price().to_formatted_string(&en),
+                                )
+                                    .color(Color32::from_rgb(255, 170, 0)),
+                            );
+                        });
+                        row.col(|ui| {
+                            ui.label(
+                                RichText::new(format!("{}", entry.get_quality()))
+                                    .color(Color32::from_rgb(85, 255, 255)),
+                            );
+                            ui.label(format!(
+                                "({} -> {})",
+                                before_quality,
+                                format!("{}", after_quality as f32)
+                                    .trim_end_matches('0')
+                                    .trim_end_matches('.')
                             ));
                         });
                         row.col(|ui| {
@@ -828,6 +1826,388 @@ impl CatacombsLootPage {
             });
     }
 
+    fn add_loot_log_section(&mut self, ui: &mut Ui) {
+        if self.loot_log.chests.is_empty() {
+            ui.label("Select the items a chest gave and log it, the log is kept between sessions.");
+            return;
+        }
+        let Some(report) = &self.loot_log_report else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            ui.label("Average Item Luck:")
+                .on_hover_text("The mean of the luck of every item that could have dropped, not how many players were less lucky");
+            match report.average_item_luck {
+                Some(luck) => ui.label(RichText::new(format!("{:.1}%", luck * 100.0)).color(Color32::from_rgb(85, 255, 85))),
+                None => ui.label("Nothing logged could have dropped"),
+            };
+        });
+        ui.add(
+            Label::new(
+                "An item's luck is the chance of having received fewer of it, counting half of the chance of having \
+                received as many. 50% is average.",
+            )
+            .wrap_mode(TextWrapMode::Wrap),
+        );
+        ui.separator();
+
+        Grid::new("loot_log_luck_grid")
+            .num_columns(4)
+            .spacing([15.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Item");
+                ui.strong("Received");
+                ui.strong("Expected");
+                ui.strong("Luck");
+                ui.end_row();
+
+                for item in report.items.iter() {
+                    ui.label(&item.identifier);
+                    ui.label(RichText::new(item.received.to_formatted_string(&en)).color(Color32::from_rgb(85, 255, 255)));
+                    ui.label(
+                        RichText::new(format!("{:.4}", item.expected).trim_end_matches('0').trim_end_matches('.'))
+                            .color(Color32::from_rgb(255, 255, 85)),
+                    );
+                    let color = if item.percentile < 0.5 { Color32::from_rgb(255, 85, 85) } else { Color32::from_rgb(85, 255, 85) };
+                    ui.label(RichText::new(format!("{:.1}%", item.percentile * 100.0)).color(color));
+                    ui.end_row();
+                }
+            });
+        ui.separator();
+
+        let mut removed_index = None;
+        Grid::new("loot_log_chests_grid")
+            .num_columns(4)
+            .spacing([15.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.strong("Floor");
+                ui.strong("Chest");
+                ui.strong("Items");
+                ui.label("");
+                ui.end_row();
+
+                for (index, chest) in self.loot_log.chests.iter().enumerate().rev() {
+                    let mut floor_label = ui.label(options::floor_to_text(chest.floor.clone()));
+                    if !self.hashed_loot_log_chances.contains_key(&chest.generate_chances_hash()) {
+                        floor_label = floor_label.on_hover_text("This chest isn't in the loot data anymore, so it's left out of the luck");
+                    }
+                    floor_label.on_hover_text(format!(
+                        "Treasure Accessory: {}x, Boss Luck: +{}, Catacombs Box: +{}, S+: {}",
+                        chest.treasure_accessory_multiplier,
+                        chest.boss_luck_increase,
+                        chest.catacombs_box_attribute_increase,
+                        chest.s_plus
+                    ));
+                    ui.label(format!("{:?}", chest.chest_type));
+                    if chest.items.is_empty() {
+                        ui.label("Nothing");
+                    } else {
+                        ui.add(Label::new(chest.items.join(", ")).wrap_mode(TextWrapMode::Wrap));
+                    }
+                    if ui.button("Remove").clicked() {
+                        removed_index = Some(index);
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(index) = removed_index {
+            self.loot_log.chests.remove(index);
+            self.loot_log_report = None;
+        }
+    }
+
+    fn add_dry_streak_section(&mut self, ui: &mut Ui) {
+        let target_entry = match (&self.rng_meter_data.selected_item, &self.dry_streak_target_entry) {
+            (Some(meter_item), _) => meter_item.identifier.clone(),
+            (None, Some(target_entry)) => target_entry.clone(),
+            (None, None) => {
+                ui.label("Select a target item, or an item on the RNG meter to take the meter into account.");
+                return;
+            }
+        };
+        let Some(Some(chances)) = self.hashed_dry_streak_chances.get(&self.generate_dry_streak_hash()) else {
+            ui.label(format!("{target_entry} doesn't drop from this chest."));
+            return;
+        };
+        let result = calculate_dry_streak(chances, self.dry_streak_runs);
+
+        Grid::new("dry_streak_grid")
+            .num_columns(2)
+            .spacing([15.0, 4.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Target Item:");
+                ui.label(&target_entry);
+                ui.end_row();
+
+                ui.label("Runs Without It:");
+                ui.label(RichText::new(result.runs.to_formatted_string(&en)).color(Color32::from_rgb(85, 255, 255)));
+                ui.end_row();
+
+                ui.label("Chance Of Being This Dry:");
+                if result.dry_chance == 0.0 {
+                    ui.label("None, the RNG meter would have guaranteed it by now");
+                } else {
+                    ui.label(
+                        RichText::new(format!(
+                            "{}% (1 in {} players)",
+                            format!("{:.4}", result.dry_chance * 100.0).trim_end_matches('0').trim_end_matches('.'),
+                            format!("{:.2}", 1.0 / result.dry_chance).trim_end_matches('0').trim_end_matches('.')
+                        ))
+                        .color(Color32::from_rgb(85, 255, 85)),
+                    );
+                }
+                ui.end_row();
+
+                ui.label("Unluckier Than:");
+                ui.label(
+                    RichText::new(format!(
+                        "{}% of players",
+                        format!("{:.4}", result.percentile * 100.0).trim_end_matches('0').trim_end_matches('.')
+                    ))
+                    .color(Color32::from_rgb(85, 255, 85)),
+                );
+                ui.end_row();
+
+                ui.label("Expected Runs Left:");
+                if result.expected_runs_remaining.is_finite() {
+                    let text = format!("{:.2}", result.expected_runs_remaining);
+                    ui.label(
+                        RichText::new(text.trim_end_matches('0').trim_end_matches('.'))
+                            .color(Color32::from_rgb(255, 255, 85)),
+                    );
+                } else {
+                    ui.label("Never, it can't drop from this chest with these settings");
+                }
+                ui.end_row();
+
+                if let Some(meter_item) = &self.rng_meter_data.selected_item {
+                    ui.label("Guaranteed By The Meter:");
+                    match result.guaranteed_in_runs {
+                        Some(runs) => ui.label(format!("Within {} more run(s)", runs.to_formatted_string(&en))),
+                        None if self.chest.as_ref().is_some_and(|chest| chest.chest_type != meter_item.lowest_tier_chest_type) => {
+                            ui.label(format!("Never, only the {:?} chest is guaranteed", meter_item.lowest_tier_chest_type))
+                        }
+                        None => ui.label("Never, only S runs (270+ score) fill the meter"),
+                    };
+                    ui.end_row();
+                }
+            });
+
+        if self.rng_meter_data.selected_item.is_some() {
+            ui.add(
+                Label::new(
+                    "The meter is assumed to have been on the item for the whole streak, starting empty after the last \
+                    drop.",
+                )
+                .wrap_mode(TextWrapMode::Wrap),
+            );
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn add_batch_opening_section(&mut self, ui: &mut Ui) {
+        let (Some(result), Some(chances)) = (self.batch_opening.as_ref(), self.get_loot_table_chances()) else {
+            return;
+        };
+
+        ui.label(format!("Opened {} chests.", result.chests_opened.to_formatted_string(&en)));
+        if let Some(target_entry) = &result.target_entry {
+            let mut text = format!("{target_entry} dropped {} time(s)", result.target_drops.len().to_formatted_string(&en));
+            if result.meter_drops > 0 {
+                text += &format!(", {} of them from a full RNG meter", result.meter_drops.to_formatted_string(&en));
+            }
+            ui.label(text + ".");
+
+            if let Some(sequence) = result.get_luckiest_sequence() {
+                ui.label(RichText::new(format!(
+                    "Luckiest: chests #{} to #{}, {} chest(s) for one drop",
+                    (sequence.first_chest + 1).to_formatted_string(&en),
+                    (sequence.last_chest + 1).to_formatted_string(&en),
+                    sequence.get_length().to_formatted_string(&en)
+                ))
+                .color(Color32::from_rgb(85, 255, 85)));
+            }
+            if let Some(sequence) = result.get_unluckiest_sequence() {
+                let dry_chests = sequence.get_length() - sequence.ended_with_drop as u32;
+                let ongoing = if sequence.ended_with_drop { "" } else { " (still going)" };
+                ui.label(RichText::new(format!(
+                    "Unluckiest: chests #{} to #{}, {} chest(s) without a drop{ongoing}",
+                    (sequence.first_chest + 1).to_formatted_string(&en),
+                    (sequence.last_chest + 1).to_formatted_string(&en),
+                    dry_chests.to_formatted_string(&en)
+                ))
+                .color(Color32::from_rgb(255, 85, 85)));
+            }
+        }
+        if self.rng_meter_data.selected_item.is_some() {
+            ui.label(format!(
+                "RNG meter XP after the last chest: {}. The expected chances are for the starting XP, so the selected \
+                item drops more often than expected as the meter fills.",
+                result.final_meter_xp.to_formatted_string(&en)
+            ));
+        }
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let available_height = ui.available_height();
+        let table = TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .column(Column::auto())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height);
+
+        table
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Entry");
+                });
+                header.col(|ui| {
+                    ui.strong("Times Rolled");
+                });
+                header.col(|ui| {
+                    ui.strong("Per Chest");
+                });
+                header.col(|ui| {
+                    ui.strong("Expected");
+                });
+                header.col(|ui| {
+                    ui.strong("Difference");
+                });
+            })
+            .body(|mut body| {
+                for entry in chances.entries.iter() {
+                    let entry = entry.borrow();
+                    let identifier = entry.entry.to_string();
+                    let count = result.get_count(&identifier);
+                    let expected_chance = entry.chance;
+                    if count == 0 && expected_chance == 0.0 {
+                        continue;
+                    }
+                    let chance = count as f64 / result.chests_opened as f64;
+
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            images::add_first_valid_image(&self.images, ui, entry.entry.get_possible_file_names());
+                            ui.hyperlink_to(&identifier, entry.entry.get_wiki_page_name());
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(count.to_formatted_string(&en)).color(Color32::from_rgb(85, 255, 255)));
+                        });
+                        row.col(|ui| {
+                            fill_in_chance_column(ui, chance);
+                        });
+                        row.col(|ui| {
+                            fill_in_chance_column(ui, expected_chance);
+                        });
+                        row.col(|ui| {
+                            if count == 0 || expected_chance == 0.0 {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            } else {
+                                fill_in_chance_differences_column(ui, chance, expected_chance);
+                            }
+                        });
+                    });
+                }
+            });
+    }
+
+    /// Imports the pasted profile, or the file at the import path if one was entered (native only).
+    pub fn import_profile(&mut self) {
+        let mut text = self.profile_import_text.clone();
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.profile_import_path.trim().is_empty() {
+            match std::fs::read_to_string(self.profile_import_path.trim()) {
+                Ok(contents) => text = contents,
+                Err(e) => {
+                    self.profile_import_message = Some(format!("Failed to read file: {e}"));
+                    return;
+                }
+            }
+        }
+
+        self.import_profile_json(&text);
+    }
+
+    fn import_profile_json(&mut self, json: &str) {
+        self.profile_import_message = Some(match parse_dungeon_profile(json, Some(&self.profile_uuid)) {
+            Ok(profile) => {
+                self.apply_profile(&profile);
+                format!("Imported {}", profile.describe())
+            }
+            Err(message) => message,
+        });
+    }
+
+    pub fn apply_profile(&mut self, profile: &DungeonProfile) {
+        if let Some(multiplier) = profile.treasure_accessory_multiplier {
+            self.treasure_accessory_multiplier = multiplier;
+        }
+        if let Some(increase) = profile.boss_luck_increase {
+            self.boss_luck_increase = increase;
+        }
+        if let Some(increase) = profile.catacombs_box_attribute_increase {
+            self.catacombs_box_attribute_increase = increase;
+        }
+        self.profile_rng_meter_xp = profile.rng_meter_xp.clone();
+        self.apply_profile_rng_meter_xp();
+    }
+
+    /// Sets the RNG meter XP to the imported XP of the selected floor, if there is any.
+    pub fn apply_profile_rng_meter_xp(&mut self) {
+        let xp = self.floor.as_ref().and_then(|floor| self.profile_rng_meter_xp.get(floor));
+        if let Some(xp) = xp {
+            let required_xp = self.rng_meter_data.selected_item.as_ref().map_or(i32::MAX, |item| item.required_xp);
+            self.rng_meter_data.selected_xp = (*xp).clamp(0, required_xp);
+        }
+    }
+
+    pub fn is_fetching_profile(&self) -> bool {
+        self.profile_fetch_receiver.is_some()
+    }
+
+    /// The fetched profile is imported as is, it isn't kept around since the response holds every member of every profile.
+    pub fn fetch_profile(&mut self) {
+        let (sender, receiver) = unbounded();
+        fetch_in_background(fetch_profiles(&self.profile_uuid, &self.profile_api_key), 0, sender);
+        self.profile_fetch_receiver = Some(receiver);
+        self.profile_import_message = Some("Fetching profile...".to_string());
+    }
+
+    /// Imports the fetched profile once it arrives. Returns true while still waiting.
+    pub fn poll_profile_fetch(&mut self) -> bool {
+        let Some(receiver) = &self.profile_fetch_receiver else {
+            return false;
+        };
+        match receiver.try_recv() {
+            Ok((_, Some(json))) => {
+                self.profile_fetch_receiver = None;
+                self.import_profile_json(&json);
+                false
+            }
+            Ok((_, None)) => {
+                self.profile_fetch_receiver = None;
+                self.profile_import_message = Some("Failed to reach the Hypixel API".to_string());
+                false
+            }
+            Err(_) => true,
+        }
+    }
+
     pub fn require_s_plus(&self) -> bool {
         if let Some(chest) = self.chest.as_ref() {
             chest.require_s_plus()
@@ -836,6 +2216,39 @@ impl CatacombsLootPage {
         }
     }
 
+    /// Logs the selected chest with the current options and the received entries, the entries the chest doesn't have
+    /// are left out.
+    pub fn log_chest(&mut self) {
+        let (Some(floor), Some(chest)) = (&self.floor, &self.chest) else {
+            return;
+        };
+        let modifiers = self.get_quality_modifiers();
+        let mut items = std::mem::take(&mut self.loot_log_received);
+        items.retain(|identifier| chest.has_matching_entry_identifier(identifier));
+
+        self.loot_log.chests.push(LoggedChest {
+            floor: floor.clone(),
+            chest_type: chest.chest_type.clone(),
+            treasure_accessory_multiplier: modifiers.treasure_accessory_multiplier,
+            boss_luck_increase: modifiers.boss_luck_increase,
+            catacombs_box_attribute_increase: modifiers.catacombs_box_attribute_increase,
+            s_plus: modifiers.s_plus,
+            rng_meter_item: self.rng_meter_data.selected_item.as_ref().map(|item| item.identifier.clone()),
+            rng_meter_xp: if self.rng_meter_data.selected_item.is_some() { self.rng_meter_data.selected_xp } else { 0 },
+            items,
+        });
+        self.loot_log_report = None;
+    }
+
+    pub fn get_quality_modifiers(&self) -> QualityModifiers {
+        QualityModifiers {
+            treasure_accessory_multiplier: self.treasure_accessory_multiplier,
+            boss_luck_increase: self.boss_luck_increase,
+            catacombs_box_attribute_increase: self.catacombs_box_attribute_increase,
+            s_plus: self.s_plus || self.require_s_plus(),
+        }
+    }
+
     pub fn generate_loot_table_hash(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         (self.s_plus || self.require_s_plus()).hash(&mut hasher);
@@ -851,6 +2264,69 @@ impl CatacombsLootPage {
         hasher.finish()
     }
 
+    /// The RNG meter XP doesn't matter, as the meter is assumed to start empty at the start of the streak.
+    fn generate_dry_streak_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.s_plus || self.require_s_plus()).hash(&mut hasher);
+        self.treasure_accessory_multiplier
+            .to_string()
+            .hash(&mut hasher);
+        self.boss_luck_increase.hash(&mut hasher);
+        self.catacombs_box_attribute_increase.hash(&mut hasher);
+        self.floor.hash(&mut hasher);
+        self.chest.hash(&mut hasher);
+        self.rng_meter_data.selected_item.hash(&mut hasher);
+        self.dry_streak_target_entry.hash(&mut hasher);
+        self.dry_streak_score.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn generate_batch_opening_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.generate_loot_table_hash().hash(&mut hasher);
+        self.batch_opening_chests.hash(&mut hasher);
+        self.batch_opening_score.hash(&mut hasher);
+        self.batch_opening_target_entry.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn generate_roll_distribution_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.generate_loot_table_hash().hash(&mut hasher);
+        self.wanted_entries.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn generate_sensitivity_report_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.generate_loot_table_hash().hash(&mut hasher);
+        self.sensitivity_target_entry.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn generate_breakpoints_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.floor.hash(&mut hasher);
+        self.chest.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Every chest of the floor is calculated, so unlike the loot table hash this doesn't depend on the selected chest.
+    fn generate_essence_summary_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.s_plus.hash(&mut hasher);
+        self.treasure_accessory_multiplier
+            .to_string()
+            .hash(&mut hasher);
+        self.boss_luck_increase.hash(&mut hasher);
+        self.catacombs_box_attribute_increase.hash(&mut hasher);
+        self.floor.hash(&mut hasher);
+        self.rng_meter_data.selected_xp.hash(&mut hasher);
+        self.rng_meter_data.selected_item.hash(&mut hasher);
+        hasher.finish()
+    }
+
     fn generate_rng_meter_calculation_chests_and_item_hash(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
         (self.s_plus || self.require_s_plus()).hash(&mut hasher);
@@ -889,6 +2365,23 @@ impl CatacombsLootPage {
     }
 }
 
+/// The chances of a logged chest, rebuilding the RNG meter from the item that was selected. None if the floor or chest
+/// isn't in the loot data anymore.
+fn calculate_logged_chest_chances(logged_chest: &LoggedChest, loot: &BTreeMap<String, Vec<Rc<LootChest>>>) -> Option<AveragesCalculationResult> {
+    let floor_chests = loot.get(&logged_chest.floor)?;
+    let chest = floor_chests.iter().find(|chest| chest.chest_type == logged_chest.chest_type)?;
+    let selected_item = logged_chest.rng_meter_item.as_ref().and_then(|identifier| {
+        let entry = floor_chests.last()?.loot.iter().find(|entry| entry.to_string() == *identifier)?;
+        options::create_selected_rng_meter_item(entry, floor_chests)
+    });
+    let rng_meter_data = RngMeterData {
+        selected_item,
+        selected_xp: logged_chest.rng_meter_xp,
+    };
+
+    Some(calculate_average_chances(chest, logged_chest.get_modifiers().calculate_quality(chest), &rng_meter_data))
+}
+
 fn find_chests_with_entry<'a>(
     selected_item: &'a String,
     floor_chests: &'a [Rc<LootChest>],
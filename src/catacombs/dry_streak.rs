@@ -0,0 +1,225 @@
+use crate::catacombs::catacombs_loot::LootChest;
+use crate::catacombs::catacombs_loot_calculator::{calculate_average_chances, RngMeterData, SelectedRngMeterItem};
+
+/// How many RNG meter XP values the chance is calculated at, the values in between are interpolated.
+const METER_CHANCE_SAMPLES: i32 = 10;
+
+/// The chance of an entry dropping in each run of a dry streak.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunChances {
+    // (meter xp, chance), sorted by the xp, a single point when the meter isn't used
+    chances: Vec<(i32, f64)>,
+    meter_xp_per_run: i32,
+    // None if a full meter doesn't guarantee the entry
+    required_xp: Option<i32>,
+}
+
+impl RunChances {
+    pub fn without_meter(chance: f64) -> Self {
+        RunChances {
+            chances: vec![(0, chance)],
+            meter_xp_per_run: 0,
+            required_xp: None,
+        }
+    }
+
+    /// The meter starts empty, since it's reset whenever the selected item drops. A full meter only guarantees the
+    /// entry in the lowest tier chest that has it, so `required_xp` should be None for any other chest.
+    pub fn with_meter(chances: Vec<(i32, f64)>, meter_xp_per_run: i32, required_xp: Option<i32>) -> Self {
+        RunChances {
+            chances,
+            meter_xp_per_run,
+            required_xp,
+        }
+    }
+
+    /// The run (counting from 0) the meter is full for, if it ever fills.
+    pub fn get_guaranteed_run(&self) -> Option<u32> {
+        let required_xp = self.required_xp?;
+        if self.meter_xp_per_run <= 0 {
+            return None;
+        }
+        Some((required_xp as f64 / self.meter_xp_per_run as f64).ceil() as u32)
+    }
+
+    /// The chance of the run after the given amount of dry runs.
+    pub fn get_chance(&self, run: u32) -> f64 {
+        if self.get_guaranteed_run().is_some_and(|guaranteed_run| run >= guaranteed_run) {
+            return 1.0;
+        }
+
+        // xp is added after rolling, so the first run has none
+        let xp = run as i64 * self.meter_xp_per_run as i64;
+        let next_index = self.chances.partition_point(|(point_xp, _)| (*point_xp as i64) <= xp);
+        match (self.chances.get(next_index.wrapping_sub(1)), self.chances.get(next_index)) {
+            (Some((previous_xp, previous_chance)), Some((next_xp, next_chance))) => {
+                let progress = (xp - *previous_xp as i64) as f64 / (next_xp - previous_xp) as f64;
+                previous_chance + (next_chance - previous_chance) * progress
+            }
+            (Some((_, chance)), None) | (None, Some((_, chance))) => *chance,
+            (None, None) => 0.0,
+        }
+    }
+
+    fn is_constant(&self) -> bool {
+        self.get_guaranteed_run().is_none() && self.chances.len() <= 1
+    }
+
+    /// Whether the chance stays the same from this run on, as the meter is past the last sampled XP.
+    fn is_settled(&self, run: u32) -> bool {
+        let last_xp = self.chances.last().map_or(0, |(xp, _)| *xp) as i64;
+        self.meter_xp_per_run <= 0 || run as i64 * self.meter_xp_per_run as i64 >= last_xp
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DryStreakResult {
+    pub runs: u32,
+    /// The chance of going this many runs without the entry.
+    pub dry_chance: f64,
+    /// How many players would have gotten the entry by now, so how much unluckier than the rest this streak is.
+    pub percentile: f64,
+    /// Including the run it drops in, infinite if it can't drop.
+    pub expected_runs_remaining: f64,
+    pub guaranteed_in_runs: Option<u32>,
+}
+
+/// The chance of the entry in the chest at a few RNG meter XP values, from an empty meter up to just before it's full,
+/// or up to full in chests the meter doesn't guarantee the entry in. The entry has to be the selected RNG meter item.
+pub fn calculate_meter_run_chances(
+    chest: &LootChest,
+    quality: i16,
+    meter_item: &SelectedRngMeterItem,
+    meter_xp_per_run: i32,
+) -> RunChances {
+    let guaranteed = meter_item.lowest_tier_chest_type == chest.chest_type;
+    // a full meter that isn't guaranteed keeps the boosted chance
+    let last_xp = if guaranteed { meter_item.required_xp - 1 } else { meter_item.required_xp };
+
+    let mut chances = Vec::new();
+    for sample in 0..=METER_CHANCE_SAMPLES {
+        let xp = (meter_item.required_xp as i64 * sample as i64 / METER_CHANCE_SAMPLES as i64) as i32;
+        let xp = xp.min(last_xp).max(0);
+        if chances.last().is_some_and(|(last_xp, _)| *last_xp == xp) {
+            continue;
+        }
+
+        let rng_meter_data = RngMeterData {
+            selected_item: Some(meter_item.clone()),
+            selected_xp: xp,
+        };
+        let result = calculate_average_chances(chest, quality, &rng_meter_data);
+        let chance = result
+            .entries
+            .iter()
+            .find(|e| e.borrow().entry.to_string() == meter_item.identifier)
+            .map_or(0.0, |e| e.borrow().chance);
+        chances.push((xp, chance));
+    }
+    RunChances::with_meter(chances, meter_xp_per_run, guaranteed.then_some(meter_item.required_xp))
+}
+
+pub fn calculate_dry_streak(chances: &RunChances, runs: u32) -> DryStreakResult {
+    let guaranteed_run = chances.get_guaranteed_run();
+    let dry_chance = if chances.is_constant() {
+        (1.0 - chances.get_chance(0)).powi(runs as i32)
+    } else {
+        (0..runs).map(|run| 1.0 - chances.get_chance(run)).product()
+    };
+
+    // the sum of the chances to still be dry after each further run, which only ends once the meter is full
+    let expected_runs_remaining = match guaranteed_run {
+        Some(guaranteed_run) if runs >= guaranteed_run => 1.0,
+        Some(guaranteed_run) => {
+            let mut still_dry_chance = 1.0;
+            let mut expected_runs = 0.0;
+            for run in runs..=guaranteed_run {
+                expected_runs += still_dry_chance;
+                still_dry_chance *= 1.0 - chances.get_chance(run);
+            }
+            expected_runs
+        }
+        None => {
+            // the chance grows with the meter until it's past the samples, then stays the same
+            let mut still_dry_chance = 1.0;
+            let mut expected_runs = 0.0;
+            let mut run = runs;
+            while !chances.is_settled(run) {
+                expected_runs += still_dry_chance;
+                still_dry_chance *= 1.0 - chances.get_chance(run);
+                run += 1;
+            }
+            expected_runs + still_dry_chance / chances.get_chance(run)
+        }
+    };
+
+    DryStreakResult {
+        runs,
+        dry_chance,
+        percentile: 1.0 - dry_chance,
+        expected_runs_remaining,
+        guaranteed_in_runs: guaranteed_run.map(|guaranteed_run| guaranteed_run.saturating_sub(runs) + 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_dry_streak, calculate_meter_run_chances, RunChances};
+    use crate::catacombs::catacombs_loot::{read_all_chests, LootChest};
+    use crate::catacombs::options::create_selected_rng_meter_item;
+    use std::rc::Rc;
+
+    #[test]
+    fn dry_streak_without_meter() {
+        let result = calculate_dry_streak(&RunChances::without_meter(0.01), 100);
+        assert!((result.dry_chance - 0.99f64.powi(100)).abs() < 1e-12);
+        assert!((result.percentile - 0.634).abs() < 0.001);
+        // without a meter, past runs don't change what's left
+        assert!((result.expected_runs_remaining - 100.0).abs() < 1e-9);
+        assert_eq!(result.guaranteed_in_runs, None);
+    }
+
+    #[test]
+    fn meter_guarantees_the_drop() {
+        // fills after 10 runs
+        let chances = RunChances::with_meter(vec![(0, 0.01), (900, 0.03)], 100, Some(1000));
+        assert_eq!(chances.get_guaranteed_run(), Some(10));
+        assert!((chances.get_chance(3) - 0.0166667).abs() < 1e-6);
+        assert_eq!(chances.get_chance(10), 1.0);
+
+        let result = calculate_dry_streak(&chances, 4);
+        assert_eq!(result.guaranteed_in_runs, Some(7));
+        assert!(result.expected_runs_remaining < 7.0);
+        assert_eq!(calculate_dry_streak(&chances, 11).dry_chance, 0.0);
+    }
+
+    #[test]
+    fn meter_only_guarantees_the_lowest_tier_chest() {
+        let chests = read_all_chests(&crate::app::ASSETS_DIR)
+            .remove("f7")
+            .unwrap()
+            .into_iter()
+            .map(Rc::new)
+            .collect::<Vec<Rc<LootChest>>>();
+        let highest_tier_chest = chests.last().unwrap();
+        // an item that's also in a lower tier chest, so the meter guarantees it there instead
+        let meter_item = highest_tier_chest
+            .loot
+            .iter()
+            .filter(|entry| !entry.is_essence_and_can_roll_multiple_times())
+            .filter_map(|entry| create_selected_rng_meter_item(entry, &chests))
+            .find(|meter_item| meter_item.lowest_tier_chest_type != highest_tier_chest.chest_type)
+            .unwrap();
+
+        let quality = highest_tier_chest.base_quality as i16;
+        let chances = calculate_meter_run_chances(highest_tier_chest, quality, &meter_item, 500);
+        assert_eq!(chances.get_guaranteed_run(), None);
+        let result = calculate_dry_streak(&chances, 10_000);
+        assert!(result.dry_chance > 0.0);
+        assert_eq!(result.guaranteed_in_runs, None);
+
+        let lowest_tier_chest = chests.iter().find(|chest| chest.chest_type == meter_item.lowest_tier_chest_type).unwrap();
+        let chances = calculate_meter_run_chances(lowest_tier_chest, lowest_tier_chest.base_quality as i16, &meter_item, 500);
+        assert_eq!(chances.get_guaranteed_run(), Some((meter_item.required_xp as f64 / 500.0).ceil() as u32));
+    }
+}
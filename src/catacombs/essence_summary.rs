@@ -0,0 +1,140 @@
+use crate::bazaar::auction_client::AuctionHouseClient;
+use crate::bazaar::bazaar_client::{get_essence_id, BazaarClient};
+use crate::bazaar::bazaar_data::ProfitType;
+use crate::catacombs::catacombs_loot::{ChestType, LootChest, LootEntry};
+use crate::catacombs::catacombs_loot_calculator::{calculate_average_chances, AveragesCalculationResult, RngMeterData};
+use crate::catacombs::quality_modifiers::QualityModifiers;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ExpectedEssence {
+    /// Given with every chest, whatever else rolls.
+    pub guaranteed: f64,
+    /// From the weighted essence entry (which can roll again and again) and the leftover essence for quality nothing
+    /// else could use.
+    pub rolled: f64,
+}
+
+impl ExpectedEssence {
+    pub fn get_total(&self) -> f64 {
+        self.guaranteed + self.rolled
+    }
+}
+
+pub struct ChestEssenceSummary {
+    pub chest: Rc<LootChest>,
+    pub chances: AveragesCalculationResult,
+    // key: essence type
+    pub essence: BTreeMap<String, ExpectedEssence>,
+}
+
+impl ChestEssenceSummary {
+    pub fn get_chest_type(&self) -> &ChestType {
+        &self.chest.chest_type
+    }
+
+    /// None until the bazaar has loaded, essence without a price is left out.
+    pub fn get_essence_value(&self, bazaar: &BazaarClient) -> Option<f64> {
+        let values = self
+            .essence
+            .iter()
+            .filter_map(|(essence_type, essence)| Some(get_essence_price(bazaar, essence_type)? * essence.get_total()))
+            .collect::<Vec<f64>>();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.into_iter().sum())
+        }
+    }
+
+    /// The average value of everything in the chest that has a price, essence included.
+    pub fn get_chest_value(&self, bazaar: &BazaarClient, auctions: &AuctionHouseClient) -> Option<f64> {
+        let values = self
+            .chances
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let entry = entry.borrow();
+                Some(entry.entry.get_value(bazaar, auctions)? * entry.chance)
+            })
+            .collect::<Vec<f64>>();
+        if values.is_empty() {
+            None
+        } else {
+            Some(values.into_iter().sum())
+        }
+    }
+}
+
+pub fn get_essence_price(bazaar: &BazaarClient, essence_type: &str) -> Option<f64> {
+    bazaar.get_sell_price(&get_essence_id(essence_type), ProfitType::InstaSell)
+}
+
+/// Adds up the essence entries of a chest by type. The chance of the weighted and leftover essence entries is already
+/// the average amount of times they roll, so it only has to be multiplied by the amount given per roll.
+pub fn calculate_expected_essence(chances: &AveragesCalculationResult) -> BTreeMap<String, ExpectedEssence> {
+    let mut essence: BTreeMap<String, ExpectedEssence> = BTreeMap::new();
+    for entry in chances.entries.iter() {
+        let entry = entry.borrow();
+        if let LootEntry::Essence { essence_type, essence_amount, weight, quality, .. } = entry.entry.as_ref() {
+            let expected = essence.entry(essence_type.clone()).or_default();
+            let amount = *essence_amount as f64 * entry.chance;
+            if *weight == 0 && *quality == 0 {
+                expected.guaranteed += amount;
+            } else {
+                expected.rolled += amount;
+            }
+        }
+    }
+    essence
+}
+
+/// Calculates the essence of every chest of a floor, in chest order.
+pub fn calculate_floor_essence(
+    chests: &[Rc<LootChest>],
+    modifiers: &QualityModifiers,
+    rng_meter_data: &RngMeterData,
+) -> Vec<ChestEssenceSummary> {
+    let mut summaries = chests
+        .iter()
+        .map(|chest| {
+            let chances = calculate_average_chances(chest, modifiers.calculate_quality(chest), rng_meter_data);
+            ChestEssenceSummary {
+                chest: Rc::clone(chest),
+                essence: calculate_expected_essence(&chances),
+                chances,
+            }
+        })
+        .collect::<Vec<ChestEssenceSummary>>();
+    summaries.sort_by_key(|summary| summary.get_chest_type().get_order());
+    summaries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::calculate_floor_essence;
+    use crate::app::ASSETS_DIR;
+    use crate::catacombs::catacombs_loot::{read_all_chests, ChestType};
+    use crate::catacombs::quality_modifiers::QualityModifiers;
+    use std::rc::Rc;
+
+    #[test]
+    fn floor_one_wood_chest_essence() {
+        let chests = read_all_chests(&ASSETS_DIR).remove("f1").unwrap().into_iter().map(Rc::new).collect::<Vec<_>>();
+        let modifiers = QualityModifiers {
+            treasure_accessory_multiplier: 1.0,
+            boss_luck_increase: 0,
+            catacombs_box_attribute_increase: 0,
+            s_plus: false,
+        };
+        let summaries = calculate_floor_essence(&chests, &modifiers, &Default::default());
+        assert_eq!(summaries.len(), chests.len());
+
+        let wood = summaries.iter().find(|s| s.chest.chest_type == ChestType::Wood).unwrap();
+        assert_eq!(wood.essence["WITHER"].guaranteed, 4.0);
+        assert_eq!(wood.essence["UNDEAD"].guaranteed, 3.0);
+        // leftover quality always goes somewhere, so there's always some rolled essence on top
+        assert!(wood.essence.values().map(|e| e.rolled).sum::<f64>() > 0.0);
+    }
+}
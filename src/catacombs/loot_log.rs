@@ -0,0 +1,219 @@
+use crate::catacombs::catacombs_loot::ChestType;
+use crate::catacombs::catacombs_loot_calculator::AveragesCalculationResult;
+use crate::catacombs::quality_modifiers::QualityModifiers;
+use csv::Writer;
+use serde::{Deserialize, Serialize};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+pub const LOOT_LOG_STORAGE_KEY: &str = "catacombs_loot_log";
+
+/// A chest opened in game, with the modifiers it was opened with.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct LoggedChest {
+    pub floor: String,
+    pub chest_type: ChestType,
+    pub treasure_accessory_multiplier: f64,
+    pub boss_luck_increase: u8,
+    pub catacombs_box_attribute_increase: u8,
+    pub s_plus: bool,
+    pub rng_meter_item: Option<String>,
+    pub rng_meter_xp: i32,
+    /// The identifiers of the entries received, essence isn't logged.
+    pub items: Vec<String>,
+}
+
+impl LoggedChest {
+    pub fn get_modifiers(&self) -> QualityModifiers {
+        QualityModifiers {
+            treasure_accessory_multiplier: self.treasure_accessory_multiplier,
+            boss_luck_increase: self.boss_luck_increase,
+            catacombs_box_attribute_increase: self.catacombs_box_attribute_increase,
+            s_plus: self.s_plus,
+        }
+    }
+
+    /// Chests with the same hash have the same chances, so they only have to be calculated once.
+    pub fn generate_chances_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.floor.hash(&mut hasher);
+        self.chest_type.hash(&mut hasher);
+        self.treasure_accessory_multiplier.to_string().hash(&mut hasher);
+        self.boss_luck_increase.hash(&mut hasher);
+        self.catacombs_box_attribute_increase.hash(&mut hasher);
+        self.s_plus.hash(&mut hasher);
+        self.rng_meter_item.hash(&mut hasher);
+        self.rng_meter_xp.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct LootLog {
+    pub chests: Vec<LoggedChest>,
+}
+
+impl LootLog {
+    /// One row per chest, with the received items separated by semicolons.
+    pub fn export_csv(&self) -> Result<String, String> {
+        let mut writer = Writer::from_writer(Vec::new());
+        writer
+            .write_record([
+                "Floor",
+                "Chest",
+                "Treasure Accessory Multiplier",
+                "Boss Luck Increase",
+                "Catacombs Box Attribute Increase",
+                "S+",
+                "RNG Meter Item",
+                "RNG Meter XP",
+                "Items",
+            ])
+            .map_err(|e| e.to_string())?;
+
+        for chest in self.chests.iter() {
+            writer
+                .write_record([
+                    chest.floor.clone(),
+                    format!("{:?}", chest.chest_type),
+                    chest.treasure_accessory_multiplier.to_string(),
+                    chest.boss_luck_increase.to_string(),
+                    chest.catacombs_box_attribute_increase.to_string(),
+                    chest.s_plus.to_string(),
+                    chest.rng_meter_item.clone().unwrap_or_default(),
+                    chest.rng_meter_xp.to_string(),
+                    chest.items.join("; "),
+                ])
+                .map_err(|e| e.to_string())?;
+        }
+
+        let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+        String::from_utf8(bytes).map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemLuck {
+    pub identifier: String,
+    pub received: u32,
+    pub expected: f64,
+    /// How likely it is to have received fewer, counting half of the chance to have received exactly as many. 0.5 is
+    /// average luck, higher is luckier.
+    pub percentile: f64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LuckReport {
+    /// From the rarest to the most common.
+    pub items: Vec<ItemLuck>,
+    /// The mean of the percentiles of every item that could have dropped. It isn't a percentile itself, so it doesn't
+    /// say how many players were less lucky.
+    pub average_item_luck: Option<f64>,
+}
+
+/// Compares what was received from each logged chest against its chances. Essence is left out, as it isn't logged.
+pub fn calculate_luck(logged_chests: &[(&LoggedChest, &AveragesCalculationResult)]) -> LuckReport {
+    let mut identifiers: Vec<String> = Vec::new();
+    for (logged_chest, chances) in logged_chests {
+        let possible_entries = chances
+            .entries
+            .iter()
+            .filter(|e| e.borrow().chance > 0.0 && !e.borrow().entry.is_essence_and_can_roll_multiple_times())
+            .map(|e| e.borrow().entry.to_string());
+        for identifier in possible_entries.chain(logged_chest.items.iter().cloned()) {
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+    }
+
+    let mut items = identifiers
+        .into_iter()
+        .map(|identifier| {
+            let chances = logged_chests
+                .iter()
+                .map(|(_, chances)| {
+                    chances
+                        .entries
+                        .iter()
+                        .find(|e| e.borrow().entry.to_string() == identifier)
+                        .map_or(0.0, |e| e.borrow().chance.min(1.0))
+                })
+                .collect::<Vec<f64>>();
+            let received = logged_chests.iter().filter(|(chest, _)| chest.items.contains(&identifier)).count() as u32;
+
+            ItemLuck {
+                expected: chances.iter().sum(),
+                percentile: calculate_percentile(&chances, received),
+                identifier,
+                received,
+            }
+        })
+        .collect::<Vec<ItemLuck>>();
+    items.sort_by(|a, b| a.expected.total_cmp(&b.expected));
+
+    let counted = items.iter().filter(|item| item.expected > 0.0).map(|item| item.percentile).collect::<Vec<f64>>();
+    let average_item_luck = if counted.is_empty() {
+        None
+    } else {
+        Some(counted.iter().sum::<f64>() / counted.len() as f64)
+    };
+
+    LuckReport { items, average_item_luck }
+}
+
+/// The mid-percentile of the received amount, for an item with a different chance in every chest. Only the chances of
+/// receiving up to the received amount are needed, so the rest of the distribution isn't tracked.
+pub fn calculate_percentile(chances: &[f64], received: u32) -> f64 {
+    let received = received as usize;
+    // index: amount received so far
+    let mut distribution = vec![0.0; received + 1];
+    distribution[0] = 1.0;
+    for chance in chances {
+        for amount in (0..=received).rev() {
+            let from_previous = if amount > 0 { distribution[amount - 1] * chance } else { 0.0 };
+            distribution[amount] = distribution[amount] * (1.0 - chance) + from_previous;
+        }
+    }
+
+    let fewer: f64 = distribution[..received].iter().sum();
+    fewer + distribution[received] / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_percentile, LoggedChest, LootLog};
+    use crate::catacombs::catacombs_loot::ChestType;
+
+    #[test]
+    fn percentile_of_received_amount() {
+        // a coin flip: receiving none is unlucky, receiving it is lucky
+        assert_eq!(calculate_percentile(&[0.5], 0), 0.25);
+        assert_eq!(calculate_percentile(&[0.5], 1), 0.75);
+        // two coin flips, one of them is the average
+        assert_eq!(calculate_percentile(&[0.5, 0.5], 1), 0.5);
+        // can't receive more than were possible, and that's as lucky as it gets
+        assert_eq!(calculate_percentile(&[0.0, 0.0], 1), 1.0);
+    }
+
+    #[test]
+    fn log_is_exported_one_chest_per_row() {
+        let log = LootLog {
+            chests: vec![LoggedChest {
+                floor: "f7".to_string(),
+                chest_type: ChestType::Bedrock,
+                treasure_accessory_multiplier: 1.03,
+                boss_luck_increase: 10,
+                catacombs_box_attribute_increase: 0,
+                s_plus: true,
+                rng_meter_item: None,
+                rng_meter_xp: 0,
+                items: vec!["Necron's Handle".to_string(), "Recombobulator 3000".to_string()],
+            }],
+        };
+
+        let csv = log.export_csv().unwrap();
+        let lines = csv.lines().collect::<Vec<&str>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1], "f7,Bedrock,1.03,10,0,true,,0,Necron's Handle; Recombobulator 3000");
+    }
+}
@@ -0,0 +1,278 @@
+use crate::app;
+use crate::catacombs::catacombs_loot::{get_floor_from_path, ChestType, LootChest, LootEntry};
+use include_dir::Dir;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::rc::Rc;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum IssueSeverity {
+    /// The data is skipped when loading or breaks the calculators.
+    Error,
+    /// Allowed by the data format and sometimes intentional (chest tiers often weigh the same item differently), but
+    /// worth double checking against the game.
+    Warning,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum LootValidationIssue {
+    ParseError {
+        path: String,
+        error: String,
+    },
+    DuplicateEntry {
+        path: String,
+        entry: String,
+    },
+    MissingImage {
+        path: String,
+        entry: String,
+        possible_file_names: Vec<String>,
+    },
+    InvalidEssenceEntry {
+        path: String,
+        entry: String,
+        weight: u16,
+        quality: i16,
+    },
+    MissingEssenceEntry {
+        path: String,
+        description: &'static str,
+    },
+    InconsistentWeights {
+        floor: String,
+        entry: String,
+        weights: Vec<(ChestType, u16)>,
+    },
+    InconsistentIdentifiers {
+        floor: String,
+        entry: String,
+        identifiers: Vec<(ChestType, String)>,
+    },
+    UnweightedInHighestTierChest {
+        floor: String,
+        entry: String,
+        highest_tier_chest_type: ChestType,
+    },
+}
+
+impl LootValidationIssue {
+    pub fn get_severity(&self) -> IssueSeverity {
+        match self {
+            LootValidationIssue::ParseError { .. }
+            | LootValidationIssue::DuplicateEntry { .. }
+            | LootValidationIssue::InvalidEssenceEntry { .. }
+            | LootValidationIssue::MissingEssenceEntry { .. }
+            | LootValidationIssue::UnweightedInHighestTierChest { .. } => IssueSeverity::Error,
+            LootValidationIssue::MissingImage { .. }
+            | LootValidationIssue::InconsistentWeights { .. }
+            | LootValidationIssue::InconsistentIdentifiers { .. } => IssueSeverity::Warning,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.get_severity() == IssueSeverity::Error
+    }
+}
+
+impl Display for LootValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LootValidationIssue::ParseError { path, error } => write!(f, "{path}: failed to parse: {error}"),
+            LootValidationIssue::DuplicateEntry { path, entry } => write!(f, "{path}: {entry} is listed more than once"),
+            LootValidationIssue::MissingImage { path, entry, possible_file_names } => write!(
+                f,
+                "{path}: no image for {entry} (looked for {})",
+                possible_file_names.join(", ")
+            ),
+            LootValidationIssue::InvalidEssenceEntry { path, entry, weight, quality } => write!(
+                f,
+                "{path}: {entry} has weight {weight} and quality {quality}, but essence must be weighted (weight > 0, \
+                quality > 0), leftover (weight 0, quality 1) or guaranteed (weight 0, quality 0)"
+            ),
+            LootValidationIssue::MissingEssenceEntry { path, description } => write!(f, "{path}: {description}"),
+            LootValidationIssue::InconsistentWeights { floor, entry, weights } => write!(
+                f,
+                "{floor}: {entry} has different weights across chests ({})",
+                weights.iter().map(|(chest, weight)| format!("{chest:?}: {weight}")).collect::<Vec<String>>().join(", ")
+            ),
+            LootValidationIssue::InconsistentIdentifiers { floor, entry, identifiers } => write!(
+                f,
+                "{floor}: {entry} has different identifiers across chests ({})",
+                identifiers.iter().map(|(chest, id)| format!("{chest:?}: {id}")).collect::<Vec<String>>().join(", ")
+            ),
+            LootValidationIssue::UnweightedInHighestTierChest { floor, entry, highest_tier_chest_type } => write!(
+                f,
+                "{floor}: {entry} has no weight in the {highest_tier_chest_type:?} chest, so its RNG meter XP can't be calculated"
+            ),
+        }
+    }
+}
+
+/// Checks the loot data bundled with the app.
+pub fn validate_bundled_loot_data() -> Vec<LootValidationIssue> {
+    validate_loot_data(&app::ASSETS_DIR)
+}
+
+/// Loads every `dungeon_loot/**/*.json` file from the given assets directory and reports anything that would either
+/// be skipped when loading or make the calculators give wrong results. Images are looked up the same way the app
+/// does, by file name anywhere in the directory.
+pub fn validate_loot_data(dir: &Dir<'_>) -> Vec<LootValidationIssue> {
+    let mut issues = Vec::new();
+
+    let image_file_names = dir
+        .find("**/*.png")
+        .unwrap()
+        .chain(dir.find("**/*.gif").unwrap())
+        .filter_map(|file| file.path().file_name().and_then(|n| n.to_str()).map(String::from))
+        .collect::<HashSet<String>>();
+
+    let mut floors: BTreeMap<String, Vec<LootChest>> = BTreeMap::new();
+    for file in dir.find("dungeon_loot/**/*.json").unwrap() {
+        let path = file.path().display().to_string();
+        let chest = match serde_json::from_slice::<LootChest>(file.as_file().unwrap().contents()) {
+            Ok(chest) => chest,
+            Err(e) => {
+                issues.push(LootValidationIssue::ParseError { path, error: e.to_string() });
+                continue;
+            }
+        };
+
+        validate_chest(&chest, &path, &image_file_names, &mut issues);
+        floors.entry(get_floor_from_path(file.path())).or_default().push(chest);
+    }
+
+    for (floor, chests) in floors.iter_mut() {
+        chests.sort_by(|a, b| a.chest_type.get_order().cmp(&b.chest_type.get_order()));
+        validate_floor(floor, chests, &mut issues);
+    }
+
+    issues
+}
+
+fn validate_chest(chest: &LootChest, path: &str, image_file_names: &HashSet<String>, issues: &mut Vec<LootValidationIssue>) {
+    let mut seen_entries = HashSet::new();
+    let mut weighted_essence_entries = 0;
+    let mut leftover_essence_entries = 0;
+
+    for entry in chest.loot.iter() {
+        let name = entry.to_string();
+        if !seen_entries.insert(name.clone()) {
+            issues.push(LootValidationIssue::DuplicateEntry { path: path.to_string(), entry: name.clone() });
+        }
+
+        let possible_file_names = entry.get_possible_file_names();
+        if !possible_file_names.iter().any(|file_name| image_file_names.contains(file_name)) {
+            issues.push(LootValidationIssue::MissingImage {
+                path: path.to_string(),
+                entry: name.clone(),
+                possible_file_names,
+            });
+        }
+
+        if let LootEntry::Essence { weight, quality, .. } = entry.as_ref() {
+            match (*weight, *quality) {
+                (w, q) if w > 0 && q > 0 => weighted_essence_entries += 1,
+                (0, 1) => leftover_essence_entries += 1,
+                (0, 0) => {}
+                (weight, quality) => issues.push(LootValidationIssue::InvalidEssenceEntry {
+                    path: path.to_string(),
+                    entry: name,
+                    weight,
+                    quality,
+                }),
+            }
+        }
+    }
+
+    // the averages calculator needs exactly one of each of these to work
+    if weighted_essence_entries != 1 {
+        issues.push(LootValidationIssue::MissingEssenceEntry {
+            path: path.to_string(),
+            description: "chest should have exactly one weighted essence entry",
+        });
+    }
+    if leftover_essence_entries != 1 {
+        issues.push(LootValidationIssue::MissingEssenceEntry {
+            path: path.to_string(),
+            description: "chest should have exactly one leftover essence entry",
+        });
+    }
+}
+
+/// Expects the chests to be sorted from the lowest to the highest tier.
+fn validate_floor(floor: &str, chests: &[LootChest], issues: &mut Vec<LootValidationIssue>) {
+    let highest_tier_chest = chests.last();
+    if highest_tier_chest.is_none() {
+        return;
+    }
+    let highest_tier_chest = highest_tier_chest.unwrap();
+
+    let mut entry_names: Vec<String> = Vec::new();
+    for chest in chests {
+        for entry in chest.loot.iter() {
+            if !entry.is_essence_and_can_roll_multiple_times() && !entry_names.contains(&entry.to_string()) {
+                entry_names.push(entry.to_string());
+            }
+        }
+    }
+
+    for name in entry_names {
+        let matching_entries = chests
+            .iter()
+            .filter_map(|chest| {
+                chest
+                    .loot
+                    .iter()
+                    .find(|e| e.to_string() == name)
+                    .map(|e| (chest.chest_type.clone(), e))
+            })
+            .collect::<Vec<(ChestType, &Rc<LootEntry>)>>();
+
+        let weights = matching_entries
+            .iter()
+            .map(|(chest_type, e)| (chest_type.clone(), e.get_weight()))
+            .collect::<Vec<(ChestType, u16)>>();
+        if weights.iter().any(|(_, weight)| *weight != weights[0].1) {
+            issues.push(LootValidationIssue::InconsistentWeights {
+                floor: floor.to_string(),
+                entry: name.clone(),
+                weights,
+            });
+        }
+
+        let identifiers = matching_entries
+            .iter()
+            .map(|(chest_type, e)| (chest_type.clone(), get_identifier(e)))
+            .collect::<Vec<(ChestType, String)>>();
+        if identifiers.iter().any(|(_, id)| *id != identifiers[0].1) {
+            issues.push(LootValidationIssue::InconsistentIdentifiers {
+                floor: floor.to_string(),
+                entry: name.clone(),
+                identifiers,
+            });
+        }
+    }
+
+    // only the entries offered by the RNG meter selector, the rest of the floor's loot can't be picked on the meter
+    for entry in highest_tier_chest.get_rng_meter_entries() {
+        if entry.get_weight() == 0 {
+            issues.push(LootValidationIssue::UnweightedInHighestTierChest {
+                floor: floor.to_string(),
+                entry: entry.to_string(),
+                highest_tier_chest_type: highest_tier_chest.chest_type.clone(),
+            });
+        }
+    }
+}
+
+/// The raw identifier behind an entry's display name, so that entries which display the same but point at different
+/// items are caught.
+fn get_identifier(entry: &LootEntry) -> String {
+    match entry {
+        LootEntry::Item { item, .. } => item.clone(),
+        LootEntry::Pet { pet, tier, .. } => format!("{pet} ({tier})"),
+        LootEntry::Enchantment { enchantment, enchantment_level, .. } => format!("{enchantment} {enchantment_level}"),
+        LootEntry::Essence { essence_type, essence_amount, .. } => format!("{essence_type} {essence_amount}"),
+    }
+}
@@ -1,30 +1,89 @@
+use crate::bazaar::{auction_client, bazaar_client};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::catacombs::batch_opening::MAX_BATCH_OPENING_CHESTS;
 use crate::catacombs::catacombs_loot::{LootChest, LootEntry};
 use crate::catacombs::catacombs_loot_calculator::SelectedRngMeterItem;
 use crate::catacombs::catacombs_page::CalculatorType::AveragesLootTable;
 use crate::catacombs::catacombs_page::{CalculatorType, CatacombsLootPage};
+use crate::catacombs::pinned_comparison::PinnedConfiguration;
+use crate::catacombs::quality_modifiers::{
+    format_boss_luck_tier, format_treasure_accessory_tier, BOSS_LUCK_TIERS,
+    MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE, TREASURE_ACCESSORY_TIERS,
+};
 use crate::images;
-use egui::{Checkbox, Label, RichText, Slider, TextWrapMode, Ui};
+use egui::{Checkbox, Color32, Label, RichText, Slider, TextEdit, TextWrapMode, Ui};
 use num_format::{Locale, ToFormattedString};
 use std::rc::Rc;
 
+pub fn add_profile_import_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.poll_profile_fetch() {
+        ui.ctx().request_repaint_after(web_time::Duration::from_millis(250));
+    }
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "oak_sign.png");
+        ui.label("Profile: ");
+    });
+    ui.toggle_value(&mut calc.profile_import_open, "Import From Profile")
+        .on_hover_text("Fills in the modifiers and RNG meter XP from a SkyBlock profile");
+
+    if !calc.profile_import_open {
+        return;
+    }
+    ui.end_row();
+
+    ui.label("Profile JSON:")
+        .on_hover_text("A response from the profiles API, a single profile, or just your member object from one");
+    ui.add(TextEdit::multiline(&mut calc.profile_import_text).desired_rows(3).hint_text("Paste profile JSON here"));
+    ui.end_row();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        ui.label("Profile File:");
+        ui.text_edit_singleline(&mut calc.profile_import_path);
+        ui.end_row();
+    }
+
+    ui.label("Player UUID:").on_hover_text("Only needed to fetch a profile, or for profiles with more than one member");
+    ui.text_edit_singleline(&mut calc.profile_uuid);
+    ui.end_row();
+
+    ui.label("API Key:").on_hover_text("Only needed to fetch a profile. It isn't saved anywhere.");
+    ui.add(TextEdit::singleline(&mut calc.profile_api_key).password(true));
+    ui.end_row();
+
+    ui.label("");
+    ui.horizontal(|ui| {
+        if ui.button("Import").clicked() {
+            calc.import_profile();
+        }
+
+        let can_fetch = !calc.is_fetching_profile() && !calc.profile_uuid.trim().is_empty() && !calc.profile_api_key.trim().is_empty();
+        if ui.add_enabled(can_fetch, egui::Button::new("Fetch & Import")).clicked() {
+            calc.fetch_profile();
+        }
+    });
+
+    if let Some(message) = &calc.profile_import_message {
+        ui.end_row();
+        ui.label("");
+        ui.add(Label::new(RichText::new(message).color(Color32::GRAY)).wrap_mode(TextWrapMode::Wrap));
+    }
+}
+
 pub fn add_treasure_talisman_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
     ui.horizontal(|ui| {
         images::add_image(&calc.images, ui, "treasure_talisman.png");
         ui.label("Treasure Accessory: ");
     });
     ui.horizontal(|ui| {
-        ui.selectable_value(&mut calc.treasure_accessory_multiplier, 1.0, "None");
-        ui.selectable_value(
-            &mut calc.treasure_accessory_multiplier,
-            1.01,
-            "Talisman (1%)",
-        );
-        ui.selectable_value(&mut calc.treasure_accessory_multiplier, 1.02, "Ring (2%)");
-        ui.selectable_value(
-            &mut calc.treasure_accessory_multiplier,
-            1.03,
-            "Artifact (3%)",
-        );
+        for (multiplier, name) in TREASURE_ACCESSORY_TIERS {
+            ui.selectable_value(
+                &mut calc.treasure_accessory_multiplier,
+                multiplier,
+                format_treasure_accessory_tier(multiplier, name),
+            );
+        }
     });
 }
 
@@ -34,11 +93,9 @@ pub fn add_boss_luck_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
         ui.label("Boss Luck: ");
     });
     ui.horizontal(|ui| {
-        ui.selectable_value(&mut calc.boss_luck_increase, 0, "None");
-        ui.selectable_value(&mut calc.boss_luck_increase, 1, "I (+1)");
-        ui.selectable_value(&mut calc.boss_luck_increase, 3, "II (+3)");
-        ui.selectable_value(&mut calc.boss_luck_increase, 5, "III (+5)");
-        ui.selectable_value(&mut calc.boss_luck_increase, 10, "IV (+10)");
+        for (increase, name) in BOSS_LUCK_TIERS {
+            ui.selectable_value(&mut calc.boss_luck_increase, increase, format_boss_luck_tier(increase, name));
+        }
     });
 }
 
@@ -48,7 +105,7 @@ pub fn add_catacombs_box_attribute_options(calc: &mut CatacombsLootPage, ui: &mu
         ui.label("Catacombs Box: ");
     });
     ui.horizontal(|ui| {
-        ui.add(Slider::new(&mut calc.catacombs_box_attribute_increase, 0..=13));
+        ui.add(Slider::new(&mut calc.catacombs_box_attribute_increase, 0..=MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE));
     });
 }
 
@@ -73,6 +130,7 @@ pub fn add_floor_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
         images::add_image(&calc.images, ui, "catacombs.png");
         ui.label("Floor: ");
     });
+    let previous_floor = calc.floor.clone();
     egui::ComboBox::from_id_salt("select_floor")
         .selected_text(floor_to_text(
             calc.floor.as_deref().unwrap_or("None").to_string(),
@@ -141,6 +199,10 @@ pub fn add_floor_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
                 }
             }
         });
+
+    if calc.floor != previous_floor {
+        calc.apply_profile_rng_meter_xp();
+    }
 }
 
 pub fn add_chest_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
@@ -176,6 +238,20 @@ pub fn add_chest_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
         });
 }
 
+pub fn add_price_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    let ctx = ui.ctx().clone();
+    let system_time = web_time::Instant::now();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "golden_horse_armor.png");
+        ui.label("Prices: ");
+    });
+    ui.horizontal(|ui| {
+        bazaar_client::add_refresh_button(&calc.bazaar, &ctx, ui, system_time);
+        auction_client::add_auction_refresh_button(&calc.auctions, &ctx, ui, system_time);
+    });
+}
+
 pub fn add_comparison_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
     let chances = calc.get_loot_table_chances();
     if chances.is_none() {
@@ -211,6 +287,72 @@ pub fn add_comparison_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
         });
     }
 }
+
+pub fn add_pinned_comparison_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    ui.heading("Pinned Configurations")
+        .on_hover_text("Pins are only kept until the app is closed");
+    ui.end_row();
+
+    ui.label("Name");
+    ui.horizontal(|ui| {
+        ui.text_edit_singleline(&mut calc.pinned_configuration_name);
+
+        let can_pin = calc.floor.is_some() && calc.chest.is_some();
+        if ui.add_enabled(can_pin, egui::Button::new("Pin Current")).clicked() {
+            let chest = calc.chest.as_ref().unwrap();
+            let name = if calc.pinned_configuration_name.trim().is_empty() {
+                format!("{} {:?}", calc.floor.as_ref().unwrap(), chest.chest_type)
+            } else {
+                calc.pinned_configuration_name.trim().to_string()
+            };
+
+            let configuration = PinnedConfiguration {
+                name,
+                hash: calc.generate_loot_table_hash(),
+                floor: calc.floor.clone().unwrap(),
+                chest: Rc::clone(chest),
+                modifiers: calc.get_quality_modifiers(),
+                rng_meter_data: calc.rng_meter_data.clone(),
+            };
+            calc.pinned_configurations.push(configuration);
+            calc.pinned_configuration_name.clear();
+        }
+    });
+    ui.end_row();
+
+    let mut removed_index = None;
+    let mut raised_index = None;
+    for (index, configuration) in calc.pinned_configurations.iter().enumerate() {
+        ui.label(&configuration.name).on_hover_text(configuration.describe());
+        ui.horizontal(|ui| {
+            if index == 0 {
+                ui.label("(Base)");
+            } else if ui.button("Make Base").clicked() {
+                raised_index = Some(index);
+            }
+            if ui.button("Remove").clicked() {
+                removed_index = Some(index);
+            }
+        });
+        ui.end_row();
+    }
+
+    if let Some(index) = raised_index {
+        let configuration = calc.pinned_configurations.remove(index);
+        calc.pinned_configurations.insert(0, configuration);
+    }
+    if let Some(index) = removed_index {
+        calc.pinned_configurations.remove(index);
+    }
+
+    if !calc.pinned_configurations.is_empty() {
+        ui.horizontal(|_| {});
+        if ui.button("Clear Pins").clicked() {
+            calc.pinned_configurations.clear();
+        }
+    }
+}
+
 pub fn add_rng_meter_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
     if calc.floor.is_none() {
         return;
@@ -259,8 +401,7 @@ pub fn add_rng_meter_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
             ui.selectable_value(&mut calc.rng_meter_data.selected_item, None, "None");
 
             let mut sorted_loot = highest_tier_chest
-                .loot
-                .iter()
+                .get_rng_meter_entries()
                 .map(|e| {
                     if let Some(chest) = &calc.chest {
                         (e, chest.has_matching_entry(e))
@@ -275,10 +416,6 @@ pub fn add_rng_meter_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
                 let in_loot = entry.1;
                 let entry = entry.0;
 
-                if entry.is_essence_and_can_roll_multiple_times() {
-                    // essence doesn't show in rng meter
-                    continue;
-                }
                 let item_weight = entry.get_weight();
                 let required_xp: i32 =
                     (300.0 * (total_weight as f32 / item_weight as f32)).round() as i32;
@@ -302,20 +439,7 @@ pub fn add_rng_meter_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
                 let label = egui::SelectableLabel::new(selected, text);
                 if ui.add(label).clicked() {
                     let rng_meter_data = &mut calc.rng_meter_data;
-
-                    let lowest_match =
-                        find_matching_item_from_lowest_chest(entry, calc.loot.get(floor).unwrap())
-                            .unwrap();
-
-                    let new_selected_item_data = SelectedRngMeterItem {
-                        identifier: entry.to_string(),
-                        highest_tier_chest_entry: Rc::clone(entry),
-                        highest_tier_chest_type: highest_tier_chest.chest_type.clone(),
-                        lowest_tier_chest_entry: Rc::clone(lowest_match.1),
-                        lowest_tier_chest_type: lowest_match.0.chest_type.clone(),
-                        required_xp,
-                    };
-                    rng_meter_data.selected_item = Some(new_selected_item_data);
+                    rng_meter_data.selected_item = create_selected_rng_meter_item(entry, calc.loot.get(floor).unwrap());
                     rng_meter_data.selected_xp = rng_meter_data.selected_xp.min(required_xp);
                 }
             }
@@ -494,6 +618,28 @@ fn match_chest_type_or_none(
     None
 }
 
+/// The RNG meter data for an entry of the floor's highest tier chest, the chests have to be sorted from the lowest to the
+/// highest tier.
+pub fn create_selected_rng_meter_item(entry: &Rc<LootEntry>, floor_chests: &[Rc<LootChest>]) -> Option<SelectedRngMeterItem> {
+    let highest_tier_chest = floor_chests.last()?;
+    let total_weight: i32 = highest_tier_chest
+        .loot
+        .iter()
+        .map(|e| e.get_weight() as i32)
+        .sum();
+    let required_xp: i32 = (300.0 * (total_weight as f32 / entry.get_weight() as f32)).round() as i32;
+    let lowest_match = find_matching_item_from_lowest_chest(entry, floor_chests)?;
+
+    Some(SelectedRngMeterItem {
+        identifier: entry.to_string(),
+        highest_tier_chest_entry: Rc::clone(entry),
+        highest_tier_chest_type: highest_tier_chest.chest_type.clone(),
+        lowest_tier_chest_entry: Rc::clone(lowest_match.1),
+        lowest_tier_chest_type: lowest_match.0.chest_type.clone(),
+        required_xp,
+    })
+}
+
 fn find_matching_item_from_lowest_chest<'a>(
     selected_item: &'a Rc<LootEntry>,
     floor_chests: &'a [Rc<LootChest>],
@@ -520,3 +666,197 @@ pub fn floor_to_text(floor: String) -> String {
         _ => floor.to_string(),
     }
 }
+
+pub fn add_loot_log_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.floor.is_none() || calc.chest.is_none() {
+        return;
+    }
+    let chest = Rc::clone(calc.chest.as_ref().unwrap());
+
+    ui.heading("Loot Log");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "chest.png");
+        ui.label("Received: ");
+    });
+    ui.vertical(|ui| {
+        for entry in chest.loot.iter() {
+            if entry.is_essence_and_can_roll_multiple_times() {
+                continue;
+            }
+
+            let identifier = entry.to_string();
+            let mut received = calc.loot_log_received.contains(&identifier);
+            ui.horizontal(|ui| {
+                images::add_first_valid_image(&calc.images, ui, entry.get_possible_file_names());
+                if ui.checkbox(&mut received, &identifier).changed() {
+                    if received {
+                        calc.loot_log_received.push(identifier.clone());
+                    } else {
+                        calc.loot_log_received.retain(|e| e != &identifier);
+                    }
+                }
+            });
+        }
+    });
+    ui.end_row();
+
+    ui.label("");
+    if ui.button("Log Chest").on_hover_text("Logs the chest with the current options").clicked() {
+        calc.log_chest();
+    }
+    ui.end_row();
+}
+
+pub fn add_dry_streak_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.chest.is_none() {
+        return;
+    }
+    let chest = Rc::clone(calc.chest.as_ref().unwrap());
+
+    ui.heading("Dry Streak");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "eye_of_ender.png");
+        ui.label("Target Item: ");
+    });
+    if calc.rng_meter_data.selected_item.is_some() {
+        ui.label("The RNG meter item");
+    } else {
+        add_entry_combo_box(ui, "dry_streak_target_entry", &mut calc.dry_streak_target_entry, &chest);
+    }
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "redstone_torch.png");
+        ui.label("Runs Without It: ");
+    });
+    ui.add(egui::DragValue::new(&mut calc.dry_streak_runs).range(0..=100_000));
+    ui.end_row();
+
+    if calc.rng_meter_data.selected_item.is_some() {
+        ui.horizontal(|ui| {
+            images::add_image(&calc.images, ui, "redstone_repeater.png");
+            ui.label("Score Per Run: ");
+        });
+        ui.add(egui::DragValue::new(&mut calc.dry_streak_score).range(0..=317));
+        ui.end_row();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn add_batch_opening_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.chest.is_none() {
+        return;
+    }
+    let chest = Rc::clone(calc.chest.as_ref().unwrap());
+
+    ui.heading("Batch Opening");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "redstone_torch.png");
+        ui.label("Chests: ");
+    });
+    ui.add(egui::DragValue::new(&mut calc.batch_opening_chests).range(1..=MAX_BATCH_OPENING_CHESTS));
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "redstone_repeater.png");
+        ui.label("Score Per Run: ");
+    });
+    ui.add(egui::DragValue::new(&mut calc.batch_opening_score).range(0..=317))
+        .on_hover_text("Fills the RNG meter between chests, only S runs (270+) give RNG meter XP");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "eye_of_ender.png");
+        ui.label("Target Item: ")
+            .on_hover_text("The item to track the luckiest and unluckiest chests for, the RNG meter item if none is selected");
+    });
+    add_entry_combo_box(ui, "batch_opening_target_entry", &mut calc.batch_opening_target_entry, &chest);
+    ui.end_row();
+}
+
+pub fn add_roll_distribution_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.chest.is_none() {
+        return;
+    }
+    let chest = Rc::clone(calc.chest.as_ref().unwrap());
+
+    ui.heading("Roll Distribution");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "eye_of_ender.png");
+        ui.label("Wanted Items: ");
+    });
+    ui.vertical(|ui| {
+        for entry in chest.loot.iter() {
+            if entry.is_essence_and_can_roll_multiple_times() {
+                continue;
+            }
+
+            let identifier = entry.to_string();
+            let mut wanted = calc.wanted_entries.contains(&identifier);
+            ui.horizontal(|ui| {
+                images::add_first_valid_image(&calc.images, ui, entry.get_possible_file_names());
+                if ui.checkbox(&mut wanted, &identifier).changed() {
+                    if wanted {
+                        calc.wanted_entries.push(identifier.clone());
+                    } else {
+                        calc.wanted_entries.retain(|e| e != &identifier);
+                    }
+                }
+            });
+        }
+    });
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "painting.png");
+        ui.label("Pair: ");
+    });
+    ui.horizontal(|ui| {
+        add_entry_combo_box(ui, "pair_first_entry", &mut calc.pair_first_entry, &chest);
+        ui.label("+");
+        add_entry_combo_box(ui, "pair_second_entry", &mut calc.pair_second_entry, &chest);
+    });
+    ui.end_row();
+}
+
+pub fn add_modifier_sensitivity_options(calc: &mut CatacombsLootPage, ui: &mut Ui) {
+    if calc.chest.is_none() {
+        return;
+    }
+    let chest = Rc::clone(calc.chest.as_ref().unwrap());
+
+    ui.heading("Sensitivity Report");
+    ui.end_row();
+
+    ui.horizontal(|ui| {
+        images::add_image(&calc.images, ui, "eye_of_ender.png");
+        ui.label("Target Item: ");
+    });
+    add_entry_combo_box(ui, "sensitivity_target_entry", &mut calc.sensitivity_target_entry, &chest);
+    ui.end_row();
+}
+
+fn add_entry_combo_box(ui: &mut Ui, id: &str, selected_entry: &mut Option<String>, chest: &LootChest) {
+    egui::ComboBox::from_id_salt(id)
+        .height(400.0)
+        .selected_text(selected_entry.clone().unwrap_or_else(|| "None".to_string()))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected_entry, None, "None");
+            for entry in chest.loot.iter() {
+                if entry.is_essence_and_can_roll_multiple_times() {
+                    continue;
+                }
+
+                let identifier = entry.to_string();
+                ui.selectable_value(selected_entry, Some(identifier.clone()), identifier);
+            }
+        });
+}
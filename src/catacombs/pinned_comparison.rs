@@ -0,0 +1,103 @@
+use crate::catacombs::catacombs_loot::{LootChest, LootEntry};
+use crate::catacombs::catacombs_loot_calculator::{AveragesCalculationResult, RngMeterData};
+use crate::catacombs::quality_modifiers::QualityModifiers;
+use csv::Writer;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Pins only last for the session, they aren't saved since the chest and RNG meter item can't be stored as is.
+pub struct PinnedConfiguration {
+    pub name: String,
+    /// The loot table hash of the configuration at the time it was pinned, used to look up its chances.
+    pub hash: u64,
+    pub floor: String,
+    pub chest: Rc<LootChest>,
+    pub modifiers: QualityModifiers,
+    pub rng_meter_data: RngMeterData,
+}
+
+impl PinnedConfiguration {
+    pub fn calculate_quality(&self) -> i16 {
+        self.modifiers.calculate_quality(&self.chest)
+    }
+
+    pub fn describe(&self) -> String {
+        let mut description = format!("{} {:?} Chest, {}", self.floor, self.chest.chest_type, self.modifiers.describe());
+        if let Some(item) = &self.rng_meter_data.selected_item {
+            description.push_str(&format!(", RNG Meter: {} ({} XP)", item.identifier, self.rng_meter_data.selected_xp));
+        }
+        description
+    }
+}
+
+/// Every entry that shows up in at least one of the pinned chests, in the order they first appear.
+pub fn collect_entries(pinned: &[PinnedConfiguration]) -> Vec<Rc<LootEntry>> {
+    let mut entries: Vec<Rc<LootEntry>> = Vec::new();
+    for configuration in pinned {
+        for entry in configuration.chest.loot.iter() {
+            if !entries.iter().any(|e| e.to_string() == entry.to_string()) {
+                entries.push(Rc::clone(entry));
+            }
+        }
+    }
+    entries
+}
+
+pub fn get_entry_chance(chances: &AveragesCalculationResult, name: &String) -> Option<f64> {
+    chances
+        .entries
+        .iter()
+        .find(|e| &e.borrow().entry.to_string() == name)
+        .map(|e| e.borrow().chance)
+}
+
+/// Writes the comparison as CSV, with a chance column for every configuration and a difference column (relative to
+/// the first configuration) for every configuration after it. Entries a chest can't drop are left empty.
+pub fn export_comparison_csv(
+    pinned: &[PinnedConfiguration],
+    hashed_chances: &HashMap<u64, AveragesCalculationResult>,
+) -> Result<String, String> {
+    let mut writer = Writer::from_writer(Vec::new());
+
+    let mut header = vec!["Entry".to_string()];
+    for (index, configuration) in pinned.iter().enumerate() {
+        header.push(format!("{} Chance", configuration.name));
+        if index > 0 {
+            header.push(format!("{} Difference", configuration.name));
+        }
+    }
+    writer.write_record(&header).map_err(|e| e.to_string())?;
+
+    let mut quality_row = vec!["Quality".to_string()];
+    for (index, configuration) in pinned.iter().enumerate() {
+        quality_row.push(configuration.calculate_quality().to_string());
+        if index > 0 {
+            quality_row.push((configuration.calculate_quality() - pinned[0].calculate_quality()).to_string());
+        }
+    }
+    writer.write_record(&quality_row).map_err(|e| e.to_string())?;
+
+    for entry in collect_entries(pinned) {
+        let name = entry.to_string();
+        let chances = pinned
+            .iter()
+            .map(|c| hashed_chances.get(&c.hash).and_then(|chances| get_entry_chance(chances, &name)))
+            .collect::<Vec<Option<f64>>>();
+
+        let mut row = vec![name];
+        for (index, chance) in chances.iter().enumerate() {
+            row.push(chance.map_or(String::new(), |c| c.to_string()));
+            if index > 0 {
+                let difference = match (chance, chances[0]) {
+                    (Some(current), Some(base)) => (current - base).to_string(),
+                    _ => String::new(),
+                };
+                row.push(difference);
+            }
+        }
+        writer.write_record(&row).map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
@@ -0,0 +1,164 @@
+use crate::bazaar::auction_data::read_inventory_bytes;
+use crate::catacombs::quality_modifiers::{BOSS_LUCK_TIERS, MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE, TREASURE_ACCESSORY_TIERS};
+use reqwest::Client;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+
+pub const PROFILES_URL: &str = "https://api.hypixel.net/v2/skyblock/profiles";
+
+/// Only the best treasure accessory counts, same as in game.
+const TREASURE_ACCESSORIES: [(&str, f64); 3] = [
+    ("TREASURE_ARTIFACT", TREASURE_ACCESSORY_TIERS[3].0),
+    ("TREASURE_RING", TREASURE_ACCESSORY_TIERS[2].0),
+    ("TREASURE_TALISMAN", TREASURE_ACCESSORY_TIERS[1].0),
+];
+
+/// The inventories a treasure accessory works from.
+const ACCESSORY_INVENTORIES: [&str; 2] = ["/inventory/bag_contents/talisman_bag/data", "/inventory/inv_contents/data"];
+
+const DUNGEON_TYPES: [(&str, char); 2] = [("catacombs", 'f'), ("master_catacombs", 'm')];
+
+#[cfg(not(target_arch = "wasm32"))]
+pub type ProfileFuture = Pin<Box<dyn Future<Output = Option<String>> + Send>>;
+#[cfg(target_arch = "wasm32")]
+pub type ProfileFuture = Pin<Box<dyn Future<Output = Option<String>>>>;
+
+/// The loot modifiers read from a profile. Anything the profile doesn't have is left as None, so it isn't changed when
+/// imported.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DungeonProfile {
+    pub treasure_accessory_multiplier: Option<f64>,
+    pub boss_luck_increase: Option<u8>,
+    pub catacombs_box_attribute_increase: Option<u8>,
+    // key: floor, like the loot folders ("f7", "m7")
+    pub rng_meter_xp: BTreeMap<String, i32>,
+}
+
+impl DungeonProfile {
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(multiplier) = self.treasure_accessory_multiplier {
+            let name = TREASURE_ACCESSORY_TIERS.iter().find(|(m, _)| *m == multiplier).map_or("None", |(_, name)| name);
+            parts.push(format!("Treasure Accessory: {name}"));
+        }
+        if let Some(increase) = self.boss_luck_increase {
+            parts.push(format!("Boss Luck: +{increase}"));
+        }
+        if let Some(increase) = self.catacombs_box_attribute_increase {
+            parts.push(format!("Catacombs Box: +{increase}"));
+        }
+        if !self.rng_meter_xp.is_empty() {
+            parts.push(format!("RNG Meter XP for {} floor(s)", self.rng_meter_xp.len()));
+        }
+
+        if parts.is_empty() {
+            "Nothing to import".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+/// Reads a profiles response (`/v2/skyblock/profiles`), a single profile (`/v2/skyblock/profile`) or just the member
+/// object of one. Profiles with more than one member need the uuid of the member to import.
+///
+/// The modifiers are read from:
+/// - the best of the treasure talisman, ring and artifact in the accessory bag or inventory
+/// - `player_data.perks.catacombs_boss_luck`, the boss luck essence perk level
+/// - `attributes.stacks.catacombs_box`, the Catacombs Box attribute level
+/// - `dungeons.dungeon_types.<catacombs|master_catacombs>.rng_meter.<floor>`, the RNG meter XP of each floor
+pub fn parse_dungeon_profile(json: &str, member_uuid: Option<&str>) -> Result<DungeonProfile, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| format!("Invalid profile JSON: {e}"))?;
+    if root.get("success") == Some(&Value::Bool(false)) {
+        let cause = root.get("cause").and_then(|c| c.as_str()).unwrap_or("unknown error");
+        return Err(format!("The API returned an error: {cause}"));
+    }
+
+    let member = find_member(&root, member_uuid)?;
+
+    let mut accessory_ids = Vec::new();
+    for inventory in ACCESSORY_INVENTORIES {
+        if let Some(items) = member.pointer(inventory).and_then(|d| d.as_str()).and_then(read_inventory_bytes) {
+            accessory_ids.extend(items.into_iter().map(|(id, _)| id));
+        }
+    }
+    let has_inventory = ACCESSORY_INVENTORIES.iter().any(|inventory| member.pointer(inventory).is_some());
+    let treasure_accessory_multiplier = TREASURE_ACCESSORIES
+        .iter()
+        .find(|(id, _)| accessory_ids.iter().any(|a| a == id))
+        .map(|(_, multiplier)| *multiplier)
+        // without inventory api access there's no telling if they have one
+        .or(has_inventory.then_some(TREASURE_ACCESSORY_TIERS[0].0));
+
+    let boss_luck_increase = member
+        .pointer("/player_data/perks")
+        .map(|perks| perks.get("catacombs_boss_luck").and_then(|l| l.as_u64()).unwrap_or(0))
+        .map(|level| BOSS_LUCK_TIERS[(level as usize).min(BOSS_LUCK_TIERS.len() - 1)].0);
+
+    let catacombs_box_attribute_increase = member
+        .pointer("/attributes/stacks")
+        .map(|stacks| stacks.get("catacombs_box").and_then(|l| l.as_u64()).unwrap_or(0))
+        .map(|level| level.min(MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE as u64) as u8);
+
+    let mut rng_meter_xp = BTreeMap::new();
+    for (dungeon_type, floor_prefix) in DUNGEON_TYPES {
+        let meters = member.pointer(&format!("/dungeons/dungeon_types/{dungeon_type}/rng_meter")).and_then(|m| m.as_object());
+        for (floor, xp) in meters.into_iter().flatten() {
+            if let Some(xp) = xp.as_f64() {
+                rng_meter_xp.insert(format!("{floor_prefix}{floor}"), xp as i32);
+            }
+        }
+    }
+
+    Ok(DungeonProfile {
+        treasure_accessory_multiplier,
+        boss_luck_increase,
+        catacombs_box_attribute_increase,
+        rng_meter_xp,
+    })
+}
+
+fn find_member<'a>(root: &'a Value, member_uuid: Option<&str>) -> Result<&'a Value, String> {
+    let profile = if let Some(profiles) = root.get("profiles") {
+        let profiles = profiles.as_array().ok_or("\"profiles\" isn't a list")?;
+        profiles
+            .iter()
+            .find(|p| p.get("selected") == Some(&Value::Bool(true)))
+            .or(profiles.first())
+            .ok_or("There are no profiles in the response")?
+    } else {
+        root.get("profile").unwrap_or(root)
+    };
+
+    let Some(members) = profile.get("members").and_then(|m| m.as_object()) else {
+        // already a member
+        return Ok(profile);
+    };
+
+    match member_uuid.map(normalize_uuid).filter(|uuid| !uuid.is_empty()) {
+        Some(uuid) => members
+            .iter()
+            .find(|(member_uuid, _)| normalize_uuid(member_uuid) == uuid)
+            .map(|(_, member)| member)
+            .ok_or_else(|| format!("There's no member with the uuid {uuid} in the profile")),
+        None if members.len() == 1 => Ok(members.values().next().unwrap()),
+        None => Err(format!("The profile has {} members, enter the uuid of the one to import", members.len())),
+    }
+}
+
+fn normalize_uuid(uuid: &str) -> String {
+    uuid.trim().replace('-', "").to_lowercase()
+}
+
+/// Resolves to the response body whether the request worked or not, since the API explains what went wrong in it.
+/// Only resolves to None if the API couldn't be reached.
+pub fn fetch_profiles(uuid: &str, api_key: &str) -> ProfileFuture {
+    let url = format!("{PROFILES_URL}?uuid={}", normalize_uuid(uuid));
+    let api_key = api_key.trim().to_string();
+    Box::pin(async move {
+        let response = Client::new().get(url).header("API-Key", api_key).send().await.ok()?;
+        response.text().await.ok()
+    })
+}
@@ -0,0 +1,77 @@
+use crate::catacombs::catacombs_loot::LootChest;
+use crate::catacombs::catacombs_loot_calculator::calculate_quality;
+
+pub const TREASURE_ACCESSORY_TIERS: [(f64, &str); 4] = [
+    (1.0, "None"),
+    (1.01, "Talisman"),
+    (1.02, "Ring"),
+    (1.03, "Artifact"),
+];
+
+pub const BOSS_LUCK_TIERS: [(u8, &str); 5] = [
+    (0, "None"),
+    (1, "I"),
+    (3, "II"),
+    (5, "III"),
+    (10, "IV"),
+];
+
+pub const MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE: u8 = 13;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct QualityModifiers {
+    pub treasure_accessory_multiplier: f64,
+    pub boss_luck_increase: u8,
+    pub catacombs_box_attribute_increase: u8,
+    pub s_plus: bool,
+}
+
+impl QualityModifiers {
+    pub fn calculate_quality(&self, chest: &LootChest) -> i16 {
+        calculate_quality(
+            chest,
+            self.treasure_accessory_multiplier,
+            self.boss_luck_increase,
+            self.catacombs_box_attribute_increase,
+            self.s_plus || chest.require_s_plus(),
+        )
+    }
+
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((_, name)) = TREASURE_ACCESSORY_TIERS.iter().find(|(m, _)| *m == self.treasure_accessory_multiplier && *m != 1.0) {
+            parts.push(name.to_string());
+        }
+        if let Some((_, name)) = BOSS_LUCK_TIERS.iter().find(|(i, _)| *i == self.boss_luck_increase && *i != 0) {
+            parts.push(format!("Boss Luck {name}"));
+        }
+        if self.catacombs_box_attribute_increase > 0 {
+            parts.push(format!("Box +{}", self.catacombs_box_attribute_increase));
+        }
+        if self.s_plus {
+            parts.push("S+".to_string());
+        }
+
+        if parts.is_empty() {
+            "No Modifiers".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+pub fn format_treasure_accessory_tier(multiplier: f64, name: &str) -> String {
+    if multiplier == 1.0 {
+        name.to_string()
+    } else {
+        format!("{name} ({}%)", ((multiplier - 1.0) * 100.0).round())
+    }
+}
+
+pub fn format_boss_luck_tier(increase: u8, name: &str) -> String {
+    if increase == 0 {
+        name.to_string()
+    } else {
+        format!("{name} (+{increase})")
+    }
+}
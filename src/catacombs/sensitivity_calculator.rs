@@ -0,0 +1,187 @@
+use crate::catacombs::catacombs_loot::LootChest;
+use crate::catacombs::catacombs_loot_calculator::{calculate_average_chances, RngMeterData};
+use crate::catacombs::quality_modifiers::{
+    format_boss_luck_tier, format_treasure_accessory_tier, QualityModifiers, BOSS_LUCK_TIERS,
+    MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE, TREASURE_ACCESSORY_TIERS,
+};
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ModifierType {
+    TreasureAccessory,
+    BossLuck,
+    CatacombsBox,
+    SPlus,
+}
+
+impl ModifierType {
+    pub fn get_label_name(&self) -> &str {
+        match self {
+            ModifierType::TreasureAccessory => "Treasure Accessory",
+            ModifierType::BossLuck => "Boss Luck",
+            ModifierType::CatacombsBox => "Catacombs Box",
+            ModifierType::SPlus => "S+",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ModifierStep {
+    pub modifier_type: ModifierType,
+    pub label: String,
+    pub quality: i16,
+    pub chance: f64,
+    pub is_current: bool,
+    pub is_upgrade: bool,
+}
+
+impl ModifierStep {
+    /// Used to remember per-upgrade costs between reports, since the same step exists for every chest and target.
+    pub fn get_cost_key(&self) -> String {
+        format!("{:?} {}", self.modifier_type, self.label)
+    }
+}
+
+/// Goes through every level of each quality modifier while keeping the others at their current values, and returns the
+/// target entry's average chance at each of those levels.
+pub fn calculate_modifier_sensitivity(
+    chest: &LootChest,
+    identifier: &String,
+    current: QualityModifiers,
+    rng_meter_data: &RngMeterData,
+) -> Vec<ModifierStep> {
+    // plenty of steps end up with the same quality (especially the box levels), so only calculate each quality once
+    let mut chances_per_quality: HashMap<i16, f64> = HashMap::new();
+    let mut steps = Vec::new();
+
+    let mut add_step = |modifier_type: ModifierType, label: String, modifiers: QualityModifiers, is_current: bool, is_upgrade: bool| {
+        let quality = modifiers.calculate_quality(chest);
+        let chance = *chances_per_quality.entry(quality).or_insert_with(|| {
+            calculate_average_chances(chest, quality, rng_meter_data)
+                .entries
+                .iter()
+                .find(|e| &e.borrow().entry.to_string() == identifier)
+                .map_or(0.0, |e| e.borrow().chance)
+        });
+
+        steps.push(ModifierStep {
+            modifier_type,
+            label,
+            quality,
+            chance,
+            is_current,
+            is_upgrade,
+        });
+    };
+
+    for (multiplier, name) in TREASURE_ACCESSORY_TIERS {
+        let modifiers = QualityModifiers {
+            treasure_accessory_multiplier: multiplier,
+            ..current
+        };
+        add_step(
+            ModifierType::TreasureAccessory,
+            format_treasure_accessory_tier(multiplier, name),
+            modifiers,
+            multiplier == current.treasure_accessory_multiplier,
+            multiplier > current.treasure_accessory_multiplier,
+        );
+    }
+
+    for (increase, name) in BOSS_LUCK_TIERS {
+        let modifiers = QualityModifiers {
+            boss_luck_increase: increase,
+            ..current
+        };
+        add_step(
+            ModifierType::BossLuck,
+            format_boss_luck_tier(increase, name),
+            modifiers,
+            increase == current.boss_luck_increase,
+            increase > current.boss_luck_increase,
+        );
+    }
+
+    for increase in 0..=MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE {
+        let modifiers = QualityModifiers {
+            catacombs_box_attribute_increase: increase,
+            ..current
+        };
+        add_step(
+            ModifierType::CatacombsBox,
+            format!("+{increase}"),
+            modifiers,
+            increase == current.catacombs_box_attribute_increase,
+            increase > current.catacombs_box_attribute_increase,
+        );
+    }
+
+    // S+ can't be toggled for chests that require it
+    if !chest.require_s_plus() {
+        for s_plus in [false, true] {
+            let modifiers = QualityModifiers { s_plus, ..current };
+            add_step(
+                ModifierType::SPlus,
+                if s_plus { "S+".to_string() } else { "Below S+".to_string() },
+                modifiers,
+                s_plus == current.s_plus,
+                s_plus && !current.s_plus,
+            );
+        }
+    }
+
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_modifier_sensitivity, ModifierType};
+    use crate::catacombs::catacombs_loot::read_all_chests;
+    use crate::catacombs::catacombs_loot_calculator::{calculate_average_chances, RngMeterData};
+    use crate::catacombs::quality_modifiers::{QualityModifiers, BOSS_LUCK_TIERS, MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE, TREASURE_ACCESSORY_TIERS};
+
+    #[test]
+    fn step_chances_match_the_average_chances() {
+        let chest = read_all_chests(&crate::app::ASSETS_DIR).remove("f5").unwrap().pop().unwrap();
+        let rng_meter_data = RngMeterData::default();
+        // the highest quality item, so the chance changes between most steps
+        let identifier = chest
+            .loot
+            .iter()
+            .filter(|entry| !entry.is_essence_and_can_roll_multiple_times())
+            .max_by_key(|entry| entry.get_quality())
+            .unwrap()
+            .to_string();
+        let current = QualityModifiers {
+            treasure_accessory_multiplier: 1.01,
+            boss_luck_increase: 3,
+            catacombs_box_attribute_increase: 5,
+            s_plus: false,
+        };
+
+        let steps = calculate_modifier_sensitivity(&chest, &identifier, current, &rng_meter_data);
+        let s_plus_steps = if chest.require_s_plus() { 0 } else { 2 };
+        assert_eq!(
+            steps.len(),
+            TREASURE_ACCESSORY_TIERS.len() + BOSS_LUCK_TIERS.len() + MAX_CATACOMBS_BOX_ATTRIBUTE_INCREASE as usize + 1 + s_plus_steps
+        );
+
+        for step in steps.iter() {
+            let chance = calculate_average_chances(&chest, step.quality, &rng_meter_data)
+                .entries
+                .iter()
+                .find(|e| e.borrow().entry.to_string() == identifier)
+                .map_or(0.0, |e| e.borrow().chance);
+            assert!((step.chance - chance).abs() < 1e-12, "{} {}: {} instead of {chance}", step.modifier_type.get_label_name(), step.label, step.chance);
+        }
+
+        // one current step per modifier, and it's at the current quality
+        let current_quality = current.calculate_quality(&chest);
+        for modifier_type in [ModifierType::TreasureAccessory, ModifierType::BossLuck, ModifierType::CatacombsBox] {
+            let current_steps = steps.iter().filter(|s| s.modifier_type == modifier_type && s.is_current).collect::<Vec<_>>();
+            assert_eq!(current_steps.len(), 1);
+            assert_eq!(current_steps[0].quality, current_quality);
+        }
+        assert!(steps.iter().filter(|s| s.is_upgrade).all(|s| s.quality >= current_quality));
+    }
+}
@@ -1,9 +1,24 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod bazaar;
 mod catacombs;
 mod images;
 mod slayer;
 mod shards;
 
 pub use app::CalculatorApp;
+pub use bazaar::auction_data::{get_pet_auction_id, read_inventory_bytes, read_item_bytes, AuctionData, AuctionPrice};
+pub use bazaar::auction_sources::{parse_auction_responses, AuctionSource, HypixelAuctionSource, JsonAuctionSource, UrlAuctionSource};
+#[cfg(not(target_arch = "wasm32"))]
+pub use bazaar::auction_sources::load_auctions_blocking;
+pub use bazaar::bazaar_data::{BazaarData, BazaarProduct, QuickStatus};
+pub use bazaar::price_sources::{
+    parse_bazaar_json, HypixelBazaarSource, JsonPriceSource, ManualPriceSource, PriceFuture, PriceOverride, PriceSource, UrlPriceSource,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use bazaar::price_sources::load_prices_blocking;
+pub use shards::shard_validator::{validate_bundled_shard_data, validate_shard_data, ShardValidationIssue};
+pub use catacombs::profile_import::{parse_dungeon_profile, DungeonProfile};
+pub use catacombs::loot_validator::{validate_bundled_loot_data, validate_loot_data, IssueSeverity, LootValidationIssue};
+pub use slayer::slayer_loot_validator::{validate_bundled_slayer_loot_data, validate_slayer_loot_data, SlayerLootValidationIssue};
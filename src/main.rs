@@ -6,6 +6,26 @@
 fn main() -> eframe::Result {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    let mut validation_reports: Vec<(&str, Vec<(bool, String)>)> = Vec::new();
+    if std::env::args().any(|arg| arg == "--validate-loot") {
+        let issues = cata_calc::validate_bundled_loot_data();
+        validation_reports.push(("loot", issues.iter().map(|i| (i.is_error(), i.to_string())).collect()));
+    }
+    if std::env::args().any(|arg| arg == "--validate-shards") {
+        let issues = cata_calc::validate_bundled_shard_data();
+        validation_reports.push(("shard", issues.iter().map(|i| (i.is_error(), i.to_string())).collect()));
+    }
+    if std::env::args().any(|arg| arg == "--validate-slayer-loot") {
+        let issues = cata_calc::validate_bundled_slayer_loot_data();
+        validation_reports.push(("slayer loot", issues.iter().map(|i| (i.is_error(), i.to_string())).collect()));
+    }
+    if !validation_reports.is_empty() {
+        report_validation_issues(validation_reports);
+    }
+
+    let price_source = get_price_source();
+    let auction_source = get_auction_source();
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             // figure this out
@@ -22,13 +42,134 @@ fn main() -> eframe::Result {
     eframe::run_native(
         "SkyBlock Calculators",
         native_options,
-        Box::new(|cc| {
+        Box::new(move |cc| {
             egui_extras::install_image_loaders(&cc.egui_ctx);
-            Ok(Box::new(cata_calc::CalculatorApp::new(&cc.egui_ctx)))
+            let mut app = cata_calc::CalculatorApp::new(&cc.egui_ctx, cc.storage);
+            if let Some(price_source) = price_source {
+                app.set_price_source(price_source);
+            }
+            if let Some(auction_source) = auction_source {
+                app.set_auction_source(auction_source);
+            }
+            Ok(Box::new(app))
         }),
     )
 }
 
+/// Picks where prices come from, for pointing the app at a local stand-in instead of the live bazaar:
+/// - `--price-url <url>` fetches from another server that serves the bazaar API's response format
+/// - `--price-file <path>` loads a saved bazaar API response
+/// - `--price-overrides <path>` replaces some prices from either of those (or the live bazaar), using a JSON map of
+///   product ids to `{"buy_price": .., "sell_price": ..}`
+///
+/// Returns None when none of them are passed, leaving the live bazaar in place.
+#[cfg(not(target_arch = "wasm32"))]
+fn get_price_source() -> Option<Box<dyn cata_calc::PriceSource>> {
+    use cata_calc::{HypixelBazaarSource, JsonPriceSource, ManualPriceSource, PriceSource, UrlPriceSource};
+
+    let exit_with_error = |error: String| -> ! {
+        eprintln!("{error}");
+        std::process::exit(1);
+    };
+
+    let base: Option<Box<dyn PriceSource>> = match (get_arg_value("--price-url"), get_arg_value("--price-file")) {
+        (Some(_), Some(_)) => exit_with_error("--price-url and --price-file can't be used together".to_string()),
+        (Some(url), None) => Some(Box::new(UrlPriceSource { url })),
+        (None, Some(path)) => Some(Box::new(JsonPriceSource::from_path(&path).unwrap_or_else(|e| exit_with_error(e)))),
+        (None, None) => None,
+    };
+
+    match get_arg_value("--price-overrides") {
+        Some(path) => {
+            let overrides = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read {path}: {e}"))
+                .and_then(|json| ManualPriceSource::parse_overrides(&json).map_err(|e| format!("Invalid overrides in {path}: {e}")))
+                .unwrap_or_else(|e| exit_with_error(e));
+            Some(Box::new(ManualPriceSource {
+                base: base.unwrap_or_else(|| Box::new(HypixelBazaarSource)),
+                overrides,
+            }))
+        }
+        None => base,
+    }
+}
+
+/// Picks where auction house prices come from, like `get_price_source`:
+/// - `--auction-url <url>` pages through another server that serves the auctions API's response format, with
+///   `--auction-ended-url <url>` for its ended auctions (there won't be median sold prices without it)
+/// - `--auction-file <path>` loads a saved auctions or ended auctions response, or a folder of them
+///
+/// Returns None when neither is passed, leaving the live auction house in place.
+#[cfg(not(target_arch = "wasm32"))]
+fn get_auction_source() -> Option<Box<dyn cata_calc::AuctionSource>> {
+    use cata_calc::{JsonAuctionSource, UrlAuctionSource};
+
+    match (get_arg_value("--auction-url"), get_arg_value("--auction-file")) {
+        (Some(_), Some(_)) => {
+            eprintln!("--auction-url and --auction-file can't be used together");
+            std::process::exit(1);
+        }
+        (Some(auctions_url), None) => Some(Box::new(UrlAuctionSource {
+            auctions_url,
+            ended_auctions_url: get_arg_value("--auction-ended-url"),
+        })),
+        (None, Some(path)) => match JsonAuctionSource::from_path(&path) {
+            Ok(source) => Some(Box::new(source)),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
+        },
+        (None, None) => None,
+    }
+}
+
+/// The value after a `--flag value` argument, exiting if the flag is the last argument.
+#[cfg(not(target_arch = "wasm32"))]
+fn get_arg_value(flag: &str) -> Option<String> {
+    let mut args = std::env::args().skip_while(|arg| arg != flag);
+    args.next()?;
+    match args.next() {
+        Some(value) => Some(value),
+        None => {
+            eprintln!("{flag} needs a value");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Prints the issues found by each of the requested data validators and exits, with a non-zero exit code if any of
+/// them found errors. Warnings are only listed when `--show-warnings` is passed as well, as a lot of them are
+/// intentional.
+#[cfg(not(target_arch = "wasm32"))]
+fn report_validation_issues(reports: Vec<(&str, Vec<(bool, String)>)>) -> ! {
+    let show_warnings = std::env::args().any(|arg| arg == "--show-warnings");
+    let mut found_errors = false;
+    let mut hidden_warnings = false;
+
+    for (data_name, issues) in reports {
+        let (errors, warnings): (Vec<_>, Vec<_>) = issues.into_iter().partition(|(is_error, _)| *is_error);
+
+        for (_, error) in errors.iter() {
+            println!("error: {error}");
+        }
+        if show_warnings {
+            for (_, warning) in warnings.iter() {
+                println!("warning: {warning}");
+            }
+        }
+
+        println!("Found {} error(s) and {} warning(s) in the {data_name} data", errors.len(), warnings.len());
+        found_errors |= !errors.is_empty();
+        hidden_warnings |= !show_warnings && !warnings.is_empty();
+    }
+
+    if hidden_warnings {
+        println!("Run with --show-warnings to list the warnings");
+    }
+    std::process::exit(if found_errors { 1 } else { 0 });
+}
+
 // When compiling to web using trunk:
 #[cfg(target_arch = "wasm32")]
 fn main() {
@@ -55,7 +196,7 @@ fn main() {
             .start(
                 canvas,
                 web_options,
-                Box::new(|cc| Ok(Box::new(cata_calc::CalculatorApp::new(&cc.egui_ctx)))),
+                Box::new(|cc| Ok(Box::new(cata_calc::CalculatorApp::new(&cc.egui_ctx, cc.storage)))),
             )
             .await;
 
@@ -1,5 +1,10 @@
+pub mod attribute_levels;
+pub mod farming_profits;
 pub mod shard_data;
+pub mod shard_description;
+pub mod shard_inventory;
+pub mod shard_sources;
 pub mod shards_page;
-mod fusion;
-mod bazaar_data;
-mod bazaar_api;
\ No newline at end of file
+pub mod shard_validator;
+pub mod trap_profits;
+mod fusion;
\ No newline at end of file
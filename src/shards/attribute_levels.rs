@@ -0,0 +1,86 @@
+use crate::shards::shard_data::{Rarity, ShardData};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const ATTRIBUTE_LEVELS_JSON: &str = include_str!("../../assets/attribute_shards/attribute_levels.json");
+
+#[derive(Deserialize, Debug)]
+pub struct AttributeLevels {
+    /// Where the requirements come from, shown next to the calculator.
+    pub source: String,
+    pub max_level: u8,
+    // key: rarity, value: shards needed to go from the previous level to each level, starting at level 1
+    shards_per_level: BTreeMap<Rarity, Vec<u32>>,
+}
+
+impl AttributeLevels {
+    pub fn get_shards_for_level(&self, rarity: &Rarity, level: u8) -> u32 {
+        if level == 0 {
+            return 0;
+        }
+
+        self.shards_per_level
+            .get(rarity)
+            .and_then(|shards| shards.get(level as usize - 1))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Total shards that have to be absorbed to reach the level from nothing.
+    pub fn get_total_shards_for_level(&self, rarity: &Rarity, level: u8) -> u32 {
+        (1..=level).map(|level| self.get_shards_for_level(rarity, level)).sum()
+    }
+
+    pub fn get_shards_between_levels(&self, rarity: &Rarity, current_level: u8, target_level: u8) -> u32 {
+        self.get_total_shards_for_level(rarity, target_level)
+            .saturating_sub(self.get_total_shards_for_level(rarity, current_level))
+    }
+}
+
+pub fn read_attribute_levels() -> AttributeLevels {
+    serde_json::from_str(ATTRIBUTE_LEVELS_JSON).expect("Failed to parse attribute level data")
+}
+
+/// The value of each `%s` in the shard's description at the level. Shards with a single scaling value that use it
+/// more than once repeat it.
+pub fn get_stat_values(shard: &ShardData, level: u8) -> Vec<f64> {
+    shard.scaling.iter().map(|scaling| *scaling as f64 * level as f64).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_stat_values, read_attribute_levels};
+    use crate::shards::shard_data::{read_all_shards, Rarity};
+
+    #[test]
+    fn maxing_an_attribute_takes_the_listed_amount_of_shards() {
+        let levels = read_attribute_levels();
+        let max_level = levels.max_level;
+
+        assert_eq!(levels.get_total_shards_for_level(&Rarity::Common, max_level), 96);
+        assert_eq!(levels.get_total_shards_for_level(&Rarity::Uncommon, max_level), 64);
+        assert_eq!(levels.get_total_shards_for_level(&Rarity::Rare, max_level), 48);
+        assert_eq!(levels.get_total_shards_for_level(&Rarity::Epic, max_level), 32);
+        assert_eq!(levels.get_total_shards_for_level(&Rarity::Legendary, max_level), 24);
+    }
+
+    #[test]
+    fn shards_between_levels() {
+        let levels = read_attribute_levels();
+
+        assert_eq!(levels.get_shards_for_level(&Rarity::Common, 0), 0);
+        assert_eq!(levels.get_shards_for_level(&Rarity::Common, levels.max_level + 1), 0);
+        assert_eq!(levels.get_shards_between_levels(&Rarity::Rare, 3, 5), 7);
+        // going down doesn't take any shards
+        assert_eq!(levels.get_shards_between_levels(&Rarity::Rare, 5, 3), 0);
+    }
+
+    #[test]
+    fn stat_values_scale_with_the_level() {
+        let shards = read_all_shards();
+        let shard = &shards["Falcon"];
+
+        assert_eq!(get_stat_values(shard, 0), vec![0.0]);
+        assert_eq!(get_stat_values(shard, 10), vec![10.0]);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::bazaar::bazaar_data::ProfitType;
+use crate::shards::shard_data::{ShardData, Shards};
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum FarmingMethod {
+    /// Killing the mob without any of the other methods.
+    Hunting,
+    BlackHole,
+    Salts,
+    Lasso,
+    FishingNet,
+    Fishing,
+}
+
+impl FarmingMethod {
+    pub fn get_label_name(&self) -> &str {
+        match self {
+            FarmingMethod::Hunting => "Hunting",
+            FarmingMethod::BlackHole => "Black Hole",
+            FarmingMethod::Salts => "Salts",
+            FarmingMethod::Lasso => "Lasso",
+            FarmingMethod::FishingNet => "Fishing Net",
+            FarmingMethod::Fishing => "Fishing",
+        }
+    }
+
+    /// What a single action is for the method, used for the rate column.
+    pub fn get_action_name(&self) -> &str {
+        match self {
+            FarmingMethod::Fishing | FarmingMethod::FishingNet => "Catches",
+            _ => "Kills",
+        }
+    }
+
+    pub fn values() -> Vec<Self> {
+        vec![
+            FarmingMethod::Hunting,
+            FarmingMethod::BlackHole,
+            FarmingMethod::Salts,
+            FarmingMethod::Lasso,
+            FarmingMethod::FishingNet,
+            FarmingMethod::Fishing,
+        ]
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FarmingKey {
+    pub method: FarmingMethod,
+    pub location: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FarmingRate {
+    pub actions_per_hour: f64,
+    pub shards_per_action: f64,
+}
+
+impl Default for FarmingRate {
+    /// Where the rate inputs start. They aren't based on real data, so nothing is ranked with them.
+    fn default() -> Self {
+        Self {
+            actions_per_hour: 60.0,
+            shards_per_action: 1.0,
+        }
+    }
+}
+
+impl FarmingRate {
+    pub fn get_shards_per_hour(&self) -> f64 {
+        self.actions_per_hour * self.shards_per_action
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FarmingProfit {
+    pub shard_name: String,
+    pub key: FarmingKey,
+    /// None until a rate is entered for the method and location.
+    pub shards_per_hour: Option<f64>,
+    /// The after tax sell price of one shard, or None if it has no bazaar price.
+    pub shard_value: Option<f64>,
+    /// None until a rate is entered for the method and location.
+    pub coins_per_hour: Option<f64>,
+}
+
+/// Every way of farming the shard from mobs or fishing, along with where. Mobs without any of the special methods
+/// are listed as hunting.
+pub fn get_farming_methods(shard: &ShardData) -> Vec<FarmingKey> {
+    let mut keys = Vec::new();
+
+    if let Some(mobs) = &shard.sources.mobs {
+        let location = mobs.location.clone().unwrap_or_else(|| "Anywhere".to_string());
+        let methods = [
+            (mobs.black_hole, FarmingMethod::BlackHole),
+            (mobs.salts, FarmingMethod::Salts),
+            (mobs.lasso, FarmingMethod::Lasso),
+            (mobs.fishing_net, FarmingMethod::FishingNet),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| enabled.unwrap_or(false))
+        .map(|(_, method)| method)
+        .collect::<Vec<FarmingMethod>>();
+
+        if methods.is_empty() {
+            keys.push(FarmingKey { method: FarmingMethod::Hunting, location });
+        } else {
+            for method in methods {
+                keys.push(FarmingKey { method, location: location.clone() });
+            }
+        }
+    }
+
+    if let Some(fishing_loot) = &shard.sources.fishing_loot {
+        keys.push(FarmingKey {
+            method: FarmingMethod::Fishing,
+            location: fishing_loot.location.clone().unwrap_or_else(|| "Anywhere".to_string()),
+        });
+    }
+
+    keys
+}
+
+/// Coins per hour for every farmable shard and method, sorted from the most to the least profitable. Rates are shared
+/// by every shard with the same method and location. Without an entered rate there's nothing to rank by, so those are
+/// left without coins per hour and sorted after the rest.
+pub fn calculate_farming_profits(
+    shards: &Shards,
+    rates: &HashMap<FarmingKey, FarmingRate>,
+    profit_type: ProfitType,
+    bazaar_tax_rate: f64,
+) -> Vec<FarmingProfit> {
+    let mut profits = Vec::new();
+
+    for shard in shards.values() {
+        let shard_value = shard
+            .cached_bazaar_data
+            .as_ref()
+            .map(|quick_status| quick_status.get_sell_price(profit_type) * (1.0 - bazaar_tax_rate));
+
+        for key in get_farming_methods(shard) {
+            let shards_per_hour = rates.get(&key).map(|rate| rate.get_shards_per_hour());
+            profits.push(FarmingProfit {
+                shard_name: shard.shard_name.clone(),
+                key,
+                shards_per_hour,
+                shard_value,
+                coins_per_hour: shards_per_hour.map(|shards_per_hour| shards_per_hour * shard_value.unwrap_or(0.0)),
+            });
+        }
+    }
+
+    profits.sort_by(|a, b| {
+        let a_coins_per_hour = a.coins_per_hour.unwrap_or(f64::NEG_INFINITY);
+        let b_coins_per_hour = b.coins_per_hour.unwrap_or(f64::NEG_INFINITY);
+        b_coins_per_hour.total_cmp(&a_coins_per_hour)
+            .then(a.key.cmp(&b.key))
+            .then(a.shard_name.cmp(&b.shard_name))
+    });
+    profits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_farming_profits, get_farming_methods, FarmingMethod, FarmingRate};
+    use crate::bazaar::bazaar_data::ProfitType;
+    use crate::shards::shard_data::{read_all_shards, read_all_shards_priced_at};
+    use std::collections::HashMap;
+
+    #[test]
+    fn only_entered_rates_are_ranked() {
+        let shards = read_all_shards_priced_at(1000.0);
+
+        let profits = calculate_farming_profits(&shards, &HashMap::new(), ProfitType::InstaSell, 0.0);
+        assert!(!profits.is_empty());
+        assert!(profits.iter().all(|profit| profit.coins_per_hour.is_none() && profit.shards_per_hour.is_none()));
+
+        let key = profits.last().unwrap().key.clone();
+        let rates = HashMap::from([(key.clone(), FarmingRate { actions_per_hour: 100.0, shards_per_action: 0.5 })]);
+        let profits = calculate_farming_profits(&shards, &rates, ProfitType::InstaSell, 0.01);
+        let ranked = profits.iter().take_while(|profit| profit.coins_per_hour.is_some()).collect::<Vec<_>>();
+        assert!(!ranked.is_empty());
+        for profit in ranked.iter() {
+            assert_eq!(profit.key, key);
+            assert_eq!(profit.shards_per_hour, Some(50.0));
+            assert!((profit.coins_per_hour.unwrap() - 50.0 * 990.0).abs() < 1e-9);
+        }
+        assert!(profits[ranked.len()..].iter().all(|profit| profit.coins_per_hour.is_none()));
+    }
+
+    #[test]
+    fn mobs_without_special_methods_are_hunted() {
+        for shard in read_all_shards().values() {
+            let methods = get_farming_methods(shard).into_iter().map(|key| key.method).collect::<Vec<FarmingMethod>>();
+            if let Some(mobs) = &shard.sources.mobs {
+                let special_methods = [mobs.black_hole, mobs.salts, mobs.lasso, mobs.fishing_net];
+                let hunted = special_methods.iter().all(|enabled| !enabled.unwrap_or(false));
+                assert_eq!(methods.contains(&FarmingMethod::Hunting), hunted, "{}", shard.shard_name);
+            } else {
+                assert!(!methods.contains(&FarmingMethod::Hunting), "{}", shard.shard_name);
+            }
+            assert_eq!(methods.contains(&FarmingMethod::Fishing), shard.sources.fishing_loot.is_some(), "{}", shard.shard_name);
+        }
+    }
+}
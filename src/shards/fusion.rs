@@ -1,9 +1,30 @@
+use crate::bazaar::bazaar_data::{BuyType, ProfitType};
 use crate::shards::shard_data::{ShardData, Shards};
-use crate::shards::shards_page::{BuyType, ProfitType};
 use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 
 pub type ShardFusionCombinations = HashMap<String, HashSet<FusionResults>>;
+// key: input shard name, the other input of each result is the partner shard
+pub type ShardFusionPartners = HashMap<String, Vec<FusionResults>>;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FusionPartnersSortType {
+    Profit,
+    Rarity,
+}
+
+impl FusionPartnersSortType {
+    pub fn get_label_name(&self) -> &str {
+        match self {
+            FusionPartnersSortType::Profit => "Profit",
+            FusionPartnersSortType::Rarity => "Output Rarity",
+        }
+    }
+
+    pub fn values() -> Vec<Self> {
+        vec![FusionPartnersSortType::Profit, FusionPartnersSortType::Rarity]
+    }
+}
 
 #[derive(Clone)]
 pub struct FusionResults {
@@ -241,6 +262,59 @@ pub fn generate_all_possible_combinations_per_shard(shards: &Shards) -> ShardFus
     combinations
 }
 
+/// The opposite of [`generate_all_possible_combinations_per_shard`], indexed by the first input instead of the outputs.
+/// Every result for a shard has that shard as its first input.
+pub fn generate_all_possible_combinations_per_input_shard(shards: &Shards) -> ShardFusionPartners {
+    let mut combinations = HashMap::with_capacity(shards.len());
+
+    for left_shard in shards.values() {
+        let partners = combinations.entry(left_shard.shard_name.clone()).or_insert_with(Vec::new);
+        for right_shard in shards.values() {
+            let results = generate_outputs(left_shard, right_shard, shards);
+            if !results.listed_fusions.is_empty() {
+                partners.push(results);
+            }
+        }
+    }
+
+    combinations
+}
+
+/// The rarest output of the fusion, the one with the lowest id if several are as rare.
+pub fn get_highest_rarity_output<'a>(combination: &FusionResults, shards: &'a Shards) -> &'a ShardData {
+    combination.listed_fusions
+        .iter()
+        .map(|output| shards.get(output).unwrap())
+        .max_by(|a, b| a.rarity.cmp(&b.rarity).then(b.id.cmp(&a.id)))
+        .unwrap()
+}
+
+/// Sorts a shard's fusion partners, each with the profit of its best output. Ties are broken by the partner's rarity
+/// and id, and fusions without price data go last when sorting by profit.
+pub fn sort_fusion_partners(partners: &mut [(&FusionResults, Option<(&String, f64)>)], sort_type: FusionPartnersSortType, shards: &Shards) {
+    partners.sort_by(|(a_results, a_profit), (b_results, b_profit)| {
+        let a_partner = shards.get(&a_results.second_input_shard_name).unwrap();
+        let b_partner = shards.get(&b_results.second_input_shard_name).unwrap();
+        let partner_cmp = a_partner.rarity.cmp(&b_partner.rarity).then(a_partner.id.cmp(&b_partner.id));
+
+        match sort_type {
+            FusionPartnersSortType::Profit => match (a_profit, b_profit) {
+                (Some((_, a)), Some((_, b))) => b.total_cmp(a),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }.then(partner_cmp),
+            FusionPartnersSortType::Rarity => {
+                let a_best = get_highest_rarity_output(a_results, shards);
+                let b_best = get_highest_rarity_output(b_results, shards);
+                b_best.rarity.cmp(&a_best.rarity)
+                    .then(a_best.id.cmp(&b_best.id))
+                    .then(partner_cmp)
+            }
+        }
+    });
+}
+
 pub fn generate_all_possible_combinations(shards: &Shards) -> Vec<FusionResults> {
     let mut combinations = Vec::new();
 
@@ -266,4 +340,100 @@ pub fn generate_all_possible_combinations(shards: &Shards) -> Vec<FusionResults>
     });
 
     combinations
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_all_possible_combinations_per_input_shard, generate_outputs, get_highest_rarity_output, sort_fusion_partners, FusionPartnersSortType, FusionResults};
+    use crate::shards::shard_data::read_all_shards;
+    use serde::Deserialize;
+
+    const GOLDEN_FILE: &str = include_str!("../../tests/data/fusion_outputs.json");
+
+    #[derive(Deserialize)]
+    struct GoldenFile {
+        fusions: Vec<GoldenFusion>,
+    }
+
+    #[derive(Deserialize)]
+    struct GoldenFusion {
+        first: String,
+        second: String,
+        outputs: Vec<String>,
+        was_chameleon: bool,
+        is_reptile_fusion: bool,
+        // where the result comes from
+        source: String,
+    }
+
+    /// Checks `generate_outputs` against the known fusion results. The file is never written by the tests, a mismatch
+    /// means the fusion rules or shard ids changed.
+    #[test]
+    fn fusion_outputs_match_golden_file() {
+        let shards = read_all_shards();
+        let golden: GoldenFile = serde_json::from_str(GOLDEN_FILE).expect("Failed to parse golden file");
+        assert!(!golden.fusions.is_empty(), "the golden file has no fusions");
+
+        let mut mismatches = Vec::new();
+        for fusion in golden.fusions.iter() {
+            assert!(!fusion.source.trim().is_empty(), "{} + {} has no source", fusion.first, fusion.second);
+            let first = shards.get(&fusion.first).unwrap_or_else(|| panic!("Unknown shard {}", fusion.first));
+            let second = shards.get(&fusion.second).unwrap_or_else(|| panic!("Unknown shard {}", fusion.second));
+
+            let results = generate_outputs(first, second, &shards);
+            let expected = (&fusion.outputs, fusion.was_chameleon, fusion.is_reptile_fusion);
+            let actual = (&results.listed_fusions, results.was_chameleon, results.is_reptile_fusion);
+            if actual != expected {
+                mismatches.push(format!(
+                    "{} + {} ({}): expected {:?}, got {:?}",
+                    fusion.first, fusion.second, fusion.source, expected, actual
+                ));
+            }
+        }
+
+        assert!(mismatches.is_empty(), "fusion outputs don't match the golden file:\n{}", mismatches.join("\n"));
+    }
+
+    #[test]
+    fn partners_have_the_shard_as_first_input() {
+        let shards = read_all_shards();
+        let partners_per_shard = generate_all_possible_combinations_per_input_shard(&shards);
+        assert_eq!(partners_per_shard.len(), shards.len());
+
+        for (shard_name, partners) in partners_per_shard.iter() {
+            for results in partners.iter() {
+                assert_eq!(&results.first_input_shard_name, shard_name);
+                assert!(!results.listed_fusions.is_empty());
+                let expected = generate_outputs(&shards[shard_name], &shards[&results.second_input_shard_name], &shards);
+                assert_eq!(results.listed_fusions, expected.listed_fusions);
+            }
+        }
+    }
+
+    #[test]
+    fn partners_sort_by_profit_then_rarity() {
+        let shards = read_all_shards();
+        let partners_per_shard = generate_all_possible_combinations_per_input_shard(&shards);
+        let partners = partners_per_shard.values().max_by_key(|partners| partners.len()).unwrap();
+        assert!(partners.len() > 2);
+
+        // made up profits, with every third fusion lacking price data
+        let mut with_profits = partners
+            .iter()
+            .enumerate()
+            .map(|(index, results)| (results, (index % 3 != 0).then(|| (&results.listed_fusions[0], (index % 7) as f64))))
+            .collect::<Vec<(&FusionResults, Option<(&String, f64)>)>>();
+        sort_fusion_partners(&mut with_profits, FusionPartnersSortType::Profit, &shards);
+        let profits = with_profits.iter().map(|(_, profit)| profit.map(|(_, profit)| profit)).collect::<Vec<Option<f64>>>();
+        let priced = profits.iter().take_while(|profit| profit.is_some()).count();
+        assert!(profits[priced..].iter().all(|profit| profit.is_none()));
+        assert!(profits[..priced].windows(2).all(|pair| pair[0] >= pair[1]));
+
+        sort_fusion_partners(&mut with_profits, FusionPartnersSortType::Rarity, &shards);
+        let rarities = with_profits
+            .iter()
+            .map(|(results, _)| &get_highest_rarity_output(results, &shards).rarity)
+            .collect::<Vec<_>>();
+        assert!(rarities.windows(2).all(|pair| pair[0] >= pair[1]));
+    }
+}
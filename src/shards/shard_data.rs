@@ -1,9 +1,10 @@
+use crate::bazaar::bazaar_data::QuickStatus;
+use crate::shards::shard_description::get_minecraft_color;
 use egui::Color32;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, OneOrMany};
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
-use crate::shards::bazaar_data::QuickStatus;
 
 // key: shard name
 pub type Shards = HashMap<String, ShardData>;
@@ -133,6 +134,11 @@ impl ShardData {
         format!("{} ({:?} {:?}-{})", self.shard_name, self.category, self.rarity, self.id)
     }
     
+    pub fn get_image_file_name(&self) -> String {
+        let id = self.id_override.clone().unwrap_or_else(|| self.attribute_name.to_lowercase().replace("'", "").replace(" ", "_"));
+        format!("attribute_{id}.png")
+    }
+
     pub fn get_bazaar_id(&self) -> String {
         if let Some(bazaar_id_override) = &self.bazaar_id_override {
             format!("SHARD_{}", bazaar_id_override.to_uppercase())
@@ -162,7 +168,7 @@ pub enum Skill {
     Global
 }
 
-#[derive(Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+#[derive(Deserialize, Serialize, Clone, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub enum Rarity {
     Common,
     Uncommon,
@@ -173,12 +179,17 @@ pub enum Rarity {
 
 impl Rarity {
     pub fn get_color(&self) -> Color32 {
+        get_minecraft_color(self.get_color_name()).unwrap()
+    }
+
+    /// The Minecraft formatting code name of the rarity's colour.
+    pub fn get_color_name(&self) -> &'static str {
         match self {
-            Rarity::Common => Color32::WHITE,
-            Rarity::Uncommon => Color32::from_rgb(85, 255, 85),
-            Rarity::Rare => Color32::from_rgb(85, 85, 255),
-            Rarity::Epic => Color32::from_rgb(170, 0, 170),
-            Rarity::Legendary => Color32::from_rgb(255, 170, 0),
+            Rarity::Common => "white",
+            Rarity::Uncommon => "green",
+            Rarity::Rare => "blue",
+            Rarity::Epic => "dark_purple",
+            Rarity::Legendary => "gold",
         }
     }
     
@@ -220,14 +231,82 @@ pub struct Sources {
     pub blacklisted_from_regular_fusion: Option<bool>,
 }
 
+impl Sources {
+    /// A short line for each way of getting the shard.
+    pub fn describe(&self) -> Vec<String> {
+        let mut descriptions = Vec::new();
+
+        if let Some(mobs) = &self.mobs {
+            let mut text = match &mobs.names {
+                Some(names) => format!("Killing {}", names.join(", ")),
+                None => "Killing mobs".to_string(),
+            };
+            if let Some(location) = &mobs.location {
+                text += &*format!(" in {location}");
+            }
+            let mut methods = Vec::new();
+            if mobs.black_hole.unwrap_or(false) {
+                methods.push("Black Hole");
+            }
+            if mobs.salts.unwrap_or(false) {
+                methods.push("Salts");
+            }
+            if mobs.lasso.unwrap_or(false) {
+                methods.push("Lasso");
+            }
+            if mobs.fishing_net.unwrap_or(false) {
+                methods.push("Fishing Net");
+            }
+            if !methods.is_empty() {
+                text += &*format!(" ({})", methods.join(", "));
+            }
+            descriptions.push(text);
+        }
+        if let Some(traps) = &self.traps {
+            for trap in traps {
+                descriptions.push(format!("Trap: {}", trap.to_string()));
+            }
+        }
+        if let Some(fishing_loot) = &self.fishing_loot {
+            match &fishing_loot.location {
+                Some(location) => descriptions.push(format!("Fishing in {location}")),
+                None => descriptions.push("Fishing".to_string()),
+            }
+        }
+        if let Some(crafting) = &self.crafting {
+            descriptions.push(format!("Crafting ({} collection)", crafting.collection));
+        }
+        if let Some(shop_purchase) = &self.shop_purchase {
+            descriptions.push(format!("Bought from {}", shop_purchase.npc_name));
+        }
+        if let Some(dungeon_loot) = &self.dungeon_loot {
+            descriptions.push(format!("Floor {} dungeon loot", dungeon_loot.floor));
+        }
+        if self.kuudra.unwrap_or(false) {
+            descriptions.push("Kuudra".to_string());
+        }
+        if self.beacon.unwrap_or(false) {
+            descriptions.push("Beacon".to_string());
+        }
+        if self.tree_gifts.unwrap_or(false) {
+            descriptions.push("Tree Gifts".to_string());
+        }
+        if self.special_fusion.is_some() {
+            descriptions.push("Special fusion".to_string());
+        }
+
+        descriptions
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
 pub struct MobSource {
-    names: Option<Vec<String>>,
-    black_hole: Option<bool>,
-    salts: Option<bool>,
-    location: Option<String>,
-    lasso: Option<bool>,
-    fishing_net: Option<bool>,
+    pub names: Option<Vec<String>>,
+    pub black_hole: Option<bool>,
+    pub salts: Option<bool>,
+    pub location: Option<String>,
+    pub lasso: Option<bool>,
+    pub fishing_net: Option<bool>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq)]
@@ -293,14 +372,33 @@ impl TrapSource {
     }
 }
 
+pub(crate) const SHARD_DATA_JSON: &str = include_str!("../../assets/attribute_shards/shard_data.json");
+
 pub fn read_all_shards() -> Shards {
-    let shard_data_path = include_str!("../../assets/attribute_shards/shard_data.json");
-    let shards_list: Vec<ShardData> = serde_json::from_str(shard_data_path).expect("Failed to parse shard data");
-    
+    parse_shard_list(SHARD_DATA_JSON)
+        .map(shards_by_name)
+        .expect("Failed to parse shard data")
+}
+
+/// The bundled shards, all bought and sold for the same price, for tests that need every shard to have a price.
+#[cfg(test)]
+pub(crate) fn read_all_shards_priced_at(price: f64) -> Shards {
+    let mut shards = read_all_shards();
+    for shard in shards.values_mut() {
+        shard.cached_bazaar_data = Some(QuickStatus { buy_price: price, sell_price: price, ..Default::default() });
+    }
+    shards
+}
+
+pub fn parse_shard_list(json: &str) -> Result<Vec<ShardData>, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+pub fn shards_by_name(shards_list: Vec<ShardData>) -> Shards {
     let mut shards = HashMap::new();
     for shard in shards_list {
         shards.insert(shard.shard_name.clone(), shard);
     }
-    
+
     shards
-}
\ No newline at end of file
+}
@@ -0,0 +1,96 @@
+use crate::shards::attribute_levels::get_stat_values;
+use crate::shards::shard_data::ShardData;
+use egui::text::LayoutJob;
+use egui::{Color32, FontId, TextFormat};
+
+/// The colours Minecraft uses for its named formatting codes.
+pub fn get_minecraft_color(name: &str) -> Option<Color32> {
+    match name {
+        "black" => Some(Color32::from_rgb(0, 0, 0)),
+        "dark_blue" => Some(Color32::from_rgb(0, 0, 170)),
+        "dark_green" => Some(Color32::from_rgb(0, 170, 0)),
+        "dark_aqua" => Some(Color32::from_rgb(0, 170, 170)),
+        "dark_red" => Some(Color32::from_rgb(170, 0, 0)),
+        "dark_purple" => Some(Color32::from_rgb(170, 0, 170)),
+        "gold" => Some(Color32::from_rgb(255, 170, 0)),
+        "gray" => Some(Color32::from_rgb(170, 170, 170)),
+        "dark_gray" => Some(Color32::from_rgb(85, 85, 85)),
+        "blue" => Some(Color32::from_rgb(85, 85, 255)),
+        "green" => Some(Color32::from_rgb(85, 255, 85)),
+        "aqua" => Some(Color32::from_rgb(85, 255, 255)),
+        "red" => Some(Color32::from_rgb(255, 85, 85)),
+        "light_purple" => Some(Color32::from_rgb(255, 85, 255)),
+        "yellow" => Some(Color32::from_rgb(255, 255, 85)),
+        "white" => Some(Color32::WHITE),
+        _ => None,
+    }
+}
+
+/// Fills each `%s` in the shard's description with the stat at the level and colours it. Text after `%%reset%%` (or
+/// before any colour) uses the default colour.
+pub fn create_description_layout_job(shard: &ShardData, level: u8, default_color: Color32) -> LayoutJob {
+    create_formatted_layout_job(&shard.description, &get_stat_values(shard, level), default_color)
+}
+
+/// Turns text like `Grants %%red%%+%s Health%%reset%%.` into a coloured layout, filling the `%s` placeholders with
+/// the values in order. Extra placeholders reuse the last value, and unknown colour names use the default colour.
+pub fn create_formatted_layout_job(formatted_text: &str, values: &[f64], default_color: Color32) -> LayoutJob {
+    let mut job = LayoutJob::default();
+    let mut color = default_color;
+    let mut text = String::new();
+    let mut value_index = 0;
+
+    let mut index = 0;
+    while index < formatted_text.len() {
+        let remaining = &formatted_text[index..];
+
+        if let Some((code, length)) = parse_formatting_code(remaining) {
+            append_text(&mut job, &mut text, color);
+            color = if code == "reset" { default_color } else { get_minecraft_color(code).unwrap_or(default_color) };
+            index += length;
+        } else if remaining.starts_with("%s") {
+            let value = values.get(value_index).or(values.last()).copied().unwrap_or(0.0);
+            text.push_str(&format_stat_value(value));
+            value_index += 1;
+            index += 2;
+        } else {
+            let character = remaining.chars().next().unwrap();
+            text.push(character);
+            index += character.len_utf8();
+        }
+    }
+    append_text(&mut job, &mut text, color);
+
+    job
+}
+
+/// Rounds away floating point noise, so 0.2 * 3 shows as 0.6 and whole numbers have no decimals.
+pub fn format_stat_value(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    rounded.to_string()
+}
+
+/// Matches `%%name%%` at the start of the text, returning the name and the length of the whole code.
+fn parse_formatting_code(text: &str) -> Option<(&str, usize)> {
+    let rest = text.strip_prefix("%%")?;
+    let end = rest.find("%%")?;
+    let name = &rest[..end];
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_lowercase() || c == '_') {
+        return None;
+    }
+
+    Some((name, end + 4))
+}
+
+fn append_text(job: &mut LayoutJob, text: &mut String, color: Color32) {
+    if text.is_empty() {
+        return;
+    }
+
+    job.append(text, 0.0, TextFormat {
+        font_id: FontId::proportional(14.0),
+        color,
+        ..Default::default()
+    });
+    text.clear();
+}
@@ -0,0 +1,371 @@
+use crate::bazaar::bazaar_data::{BuyType, ProfitType};
+use crate::shards::fusion::generate_outputs;
+use crate::shards::shard_data::{ShardData, Shards};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const INVENTORY_STORAGE_KEY: &str = "shard_inventory";
+
+// key: shard name, value: amount owned
+#[derive(Default, Clone, Debug, Hash, Deserialize, Serialize)]
+pub struct ShardInventory {
+    counts: BTreeMap<String, u32>,
+}
+
+impl ShardInventory {
+    pub fn get_count(&self, shard_name: &String) -> u32 {
+        self.counts.get(shard_name).copied().unwrap_or(0)
+    }
+
+    pub fn contains(&self, shard_name: &String) -> bool {
+        self.get_count(shard_name) > 0
+    }
+
+    pub fn set_count(&mut self, shard_name: String, count: u32) {
+        if count == 0 {
+            self.counts.remove(&shard_name);
+        } else {
+            self.counts.insert(shard_name, count);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &u32)> {
+        self.counts.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+pub struct InventoryImport {
+    pub counts: Vec<(String, u32)>,
+    pub errors: Vec<String>,
+}
+
+/// Reads one shard per line, as either `name,count` (CSV, a header row is skipped), `name: count`, `count x name` or
+/// `countx name`. Names are matched without caring about case, and bazaar IDs (`SHARD_SEA_SERPENT`) work too. A shard
+/// listed more than once has its counts added up.
+pub fn parse_inventory_text(text: &str, shards: &Shards) -> InventoryImport {
+    let mut counts = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let parsed = split_inventory_line(line).and_then(|(name, count)| {
+            let count = count.trim().replace(",", "").parse::<u32>().ok()?;
+            Some((name.trim().trim_matches('"').to_string(), count))
+        });
+
+        match parsed {
+            Some((name, count)) => match find_shard_by_name(&name, shards) {
+                Some(shard) => match counts.iter_mut().find(|(shard_name, _)| *shard_name == shard.shard_name) {
+                    Some((_, total)) => *total = count.saturating_add(*total),
+                    None => counts.push((shard.shard_name.clone(), count)),
+                },
+                None => errors.push(format!("Line {}: unknown shard \"{name}\"", index + 1)),
+            },
+            // most likely a CSV header, but it's still reported in case it wasn't
+            None if index == 0 => errors.push(format!("Line 1: skipped \"{line}\" as a header")),
+            None => errors.push(format!("Line {}: couldn't read \"{line}\"", index + 1)),
+        }
+    }
+
+    InventoryImport { counts, errors }
+}
+
+fn split_inventory_line(line: &str) -> Option<(&str, &str)> {
+    if let Some((name, count)) = line.rsplit_once(',').or_else(|| line.rsplit_once(':')).or_else(|| line.rsplit_once('\t')) {
+        return Some((name, count));
+    }
+
+    let (count, name) = line.split_once(' ')?;
+    let name = name.trim_start().strip_prefix("x ").unwrap_or(name);
+    Some((name, count.trim_end_matches('x')))
+}
+
+fn find_shard_by_name<'a>(name: &str, shards: &'a Shards) -> Option<&'a ShardData> {
+    let name = name.to_lowercase();
+    let name = name.strip_suffix(" shard").unwrap_or(&name);
+    shards.values().find(|shard| shard.shard_name.to_lowercase() == name || shard.get_bazaar_id().to_lowercase() == name)
+}
+
+#[derive(Clone)]
+pub struct OwnedFusion {
+    pub first_input_shard_name: String,
+    pub second_input_shard_name: String,
+    pub output_shard_name: String,
+    pub times_possible: u32,
+    /// Output value minus what the inputs would've sold for, per fusion.
+    pub gain_per_fusion: f64,
+}
+
+pub struct StackDecision {
+    pub shard_name: String,
+    pub count: u32,
+    pub sell_value: f64,
+    pub best_fusion: Option<OwnedFusion>,
+}
+
+impl StackDecision {
+    pub fn should_fuse(&self) -> bool {
+        self.best_fusion.as_ref().is_some_and(|fusion| fusion.gain_per_fusion > 0.0)
+    }
+}
+
+pub struct FusionPurchase {
+    pub owned_shard_name: String,
+    pub owned_is_first_input: bool,
+    pub missing_shard_name: String,
+    pub missing_amount: u32,
+    pub purchase_cost: f64,
+    pub output_shard_name: String,
+    /// Output value minus the owned input's sell value and the purchase cost.
+    pub profit: f64,
+}
+
+pub struct InventorySuggestions {
+    pub owned_fusions: Vec<OwnedFusion>,
+    pub stack_decisions: Vec<StackDecision>,
+    pub purchases: Vec<FusionPurchase>,
+}
+
+impl InventorySuggestions {
+    pub fn generate(inventory: &ShardInventory, shards: &Shards, prices: &PriceSettings) -> Self {
+        let owned_fusions = find_owned_fusions(inventory, shards, prices);
+        let stack_decisions = decide_stacks(inventory, &owned_fusions, shards, prices);
+        let purchases = find_fusion_purchases(inventory, shards, prices, MAX_PURCHASE_SUGGESTIONS);
+        Self {
+            owned_fusions,
+            stack_decisions,
+            purchases,
+        }
+    }
+}
+
+const MAX_PURCHASE_SUGGESTIONS: usize = 100;
+
+pub struct PriceSettings {
+    pub buy_type: BuyType,
+    pub profit_type: ProfitType,
+    pub bazaar_tax_rate: f64,
+    pub pure_reptile_attribute_level: u8,
+}
+
+impl PriceSettings {
+    fn get_sell_value(&self, shard: &ShardData) -> Option<f64> {
+        shard
+            .cached_bazaar_data
+            .as_ref()
+            .map(|data| data.get_sell_price(self.profit_type) * (1.0 - self.bazaar_tax_rate))
+    }
+
+    fn get_buy_cost(&self, shard: &ShardData) -> Option<f64> {
+        shard
+            .cached_bazaar_data
+            .as_ref()
+            .map(|data| data.get_buy_price(self.buy_type))
+            .filter(|price| *price > 0.0)
+    }
+
+    /// Same as [`Self::get_best_output`], but also tries the shards the other way around since the input order can
+    /// change the outputs. The bool is true if the reversed order was better.
+    fn get_best_output_in_either_order<'a>(&self, first: &ShardData, second: &ShardData, shards: &'a Shards) -> Option<(bool, &'a ShardData, f64)> {
+        let in_order = self.get_best_output(first, second, shards);
+        let reversed = self.get_best_output(second, first, shards);
+        match (in_order, reversed) {
+            (Some(a), Some(b)) if b.1 > a.1 => Some((true, b.0, b.1)),
+            (Some(a), _) => Some((false, a.0, a.1)),
+            (None, Some(b)) => Some((true, b.0, b.1)),
+            (None, None) => None,
+        }
+    }
+
+    /// The most valuable output of fusing the two shards and what it sells for.
+    fn get_best_output<'a>(&self, first: &ShardData, second: &ShardData, shards: &'a Shards) -> Option<(&'a ShardData, f64)> {
+        let results = generate_outputs(first, second, shards);
+        results
+            .listed_fusions
+            .iter()
+            .filter_map(|output| {
+                let output = shards.get(output)?;
+                let mut amount = if results.was_chameleon { 1 } else { output.get_default_amount_made_in_fusion() } as f64;
+                if results.is_reptile_fusion {
+                    amount *= 1.0 + (self.pure_reptile_attribute_level as f64 * 0.02);
+                }
+                Some((output, self.get_sell_value(output)? * amount))
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+}
+
+/// Every fusion that can be done using only shards from the inventory, best gain first.
+fn find_owned_fusions(inventory: &ShardInventory, shards: &Shards, prices: &PriceSettings) -> Vec<OwnedFusion> {
+    let owned = inventory
+        .iter()
+        .filter_map(|(name, count)| shards.get(name).map(|shard| (shard, *count)))
+        .collect::<Vec<(&ShardData, u32)>>();
+
+    let mut fusions = Vec::new();
+    for (first_index, (first, first_count)) in owned.iter().enumerate() {
+        // each pair only once, both orders are tried when creating the fusion
+        for (second, second_count) in owned.iter().skip(first_index) {
+            let first_needed = first.get_amount_consumed_in_fusion() as u32;
+            let second_needed = second.get_amount_consumed_in_fusion() as u32;
+            let times_possible = if first.shard_name == second.shard_name {
+                first_count / (first_needed + second_needed)
+            } else {
+                (first_count / first_needed).min(second_count / second_needed)
+            };
+            if times_possible == 0 {
+                continue;
+            }
+
+            if let Some(fusion) = create_owned_fusion(first, second, times_possible, shards, prices) {
+                fusions.push(fusion);
+            }
+        }
+    }
+
+    fusions.sort_by(|a, b| b.gain_per_fusion.total_cmp(&a.gain_per_fusion));
+    fusions
+}
+
+fn create_owned_fusion(first: &ShardData, second: &ShardData, times_possible: u32, shards: &Shards, prices: &PriceSettings) -> Option<OwnedFusion> {
+    let (reversed, output, output_value) = prices.get_best_output_in_either_order(first, second, shards)?;
+    let (first, second) = if reversed { (second, first) } else { (first, second) };
+
+    let inputs_value = prices.get_sell_value(first)? * first.get_amount_consumed_in_fusion() as f64
+        + prices.get_sell_value(second)? * second.get_amount_consumed_in_fusion() as f64;
+
+    Some(OwnedFusion {
+        first_input_shard_name: first.shard_name.clone(),
+        second_input_shard_name: second.shard_name.clone(),
+        output_shard_name: output.shard_name.clone(),
+        times_possible,
+        gain_per_fusion: output_value - inputs_value,
+    })
+}
+
+/// For every stack in the inventory, whether fusing it (with another owned shard) beats selling it.
+fn decide_stacks(inventory: &ShardInventory, owned_fusions: &[OwnedFusion], shards: &Shards, prices: &PriceSettings) -> Vec<StackDecision> {
+    let mut decisions = inventory
+        .iter()
+        .filter_map(|(name, count)| {
+            let shard = shards.get(name)?;
+            let best_fusion = owned_fusions
+                .iter()
+                .filter(|f| &f.first_input_shard_name == name || &f.second_input_shard_name == name)
+                .max_by(|a, b| a.gain_per_fusion.total_cmp(&b.gain_per_fusion))
+                .cloned();
+
+            Some(StackDecision {
+                shard_name: name.clone(),
+                count: *count,
+                sell_value: prices.get_sell_value(shard).unwrap_or(0.0) * *count as f64,
+                best_fusion,
+            })
+        })
+        .collect::<Vec<StackDecision>>();
+
+    decisions.sort_by(|a, b| b.sell_value.total_cmp(&a.sell_value));
+    decisions
+}
+
+/// Fusions that use an owned stack but need (some of) the other input bought, most profitable first.
+fn find_fusion_purchases(inventory: &ShardInventory, shards: &Shards, prices: &PriceSettings, limit: usize) -> Vec<FusionPurchase> {
+    let mut purchases = Vec::new();
+
+    for (owned_name, owned_count) in inventory.iter() {
+        let owned = match shards.get(owned_name) {
+            Some(owned) => owned,
+            None => continue,
+        };
+        let owned_needed = owned.get_amount_consumed_in_fusion() as u32;
+        if *owned_count < owned_needed {
+            continue;
+        }
+        let owned_value = match prices.get_sell_value(owned) {
+            Some(value) => value * owned_needed as f64,
+            None => continue,
+        };
+
+        for partner in shards.values() {
+            let partner_needed = partner.get_amount_consumed_in_fusion() as u32;
+            let partner_owned = if partner.shard_name == *owned_name {
+                owned_count - owned_needed
+            } else {
+                inventory.get_count(&partner.shard_name)
+            };
+            if partner_owned >= partner_needed {
+                // nothing to buy, already covered by the owned fusions
+                continue;
+            }
+
+            let missing_amount = partner_needed - partner_owned;
+            let purchase_cost = match prices.get_buy_cost(partner) {
+                Some(cost) => cost * missing_amount as f64,
+                None => continue,
+            };
+            let partner_owned_value = prices.get_sell_value(partner).unwrap_or(0.0) * partner_owned as f64;
+
+            if let Some((reversed, output, output_value)) = prices.get_best_output_in_either_order(owned, partner, shards) {
+                purchases.push(FusionPurchase {
+                    owned_shard_name: owned_name.clone(),
+                    owned_is_first_input: !reversed,
+                    missing_shard_name: partner.shard_name.clone(),
+                    missing_amount,
+                    purchase_cost,
+                    output_shard_name: output.shard_name.clone(),
+                    profit: output_value - owned_value - partner_owned_value - purchase_cost,
+                });
+            }
+        }
+    }
+
+    purchases.sort_by(|a, b| b.profit.total_cmp(&a.profit));
+    purchases.truncate(limit);
+    purchases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_inventory_text, split_inventory_line};
+    use crate::shards::shard_data::read_all_shards;
+
+    #[test]
+    fn lines_are_split_on_every_separator() {
+        assert_eq!(split_inventory_line("Sea Serpent,12"), Some(("Sea Serpent", "12")));
+        assert_eq!(split_inventory_line("Sea Serpent: 12"), Some(("Sea Serpent", " 12")));
+        assert_eq!(split_inventory_line("Sea Serpent\t12"), Some(("Sea Serpent", "12")));
+        assert_eq!(split_inventory_line("12x Sea Serpent"), Some(("Sea Serpent", "12")));
+        assert_eq!(split_inventory_line("12 x Sea Serpent"), Some(("Sea Serpent", "12")));
+        assert_eq!(split_inventory_line("Falcon"), None);
+    }
+
+    #[test]
+    fn inventory_text_is_parsed() {
+        let shards = read_all_shards();
+        let text = "Shard,Count\nFalcon,12\n\"falcon shard\": 3\n2x SHARD_INFERNO_KOI\nFalcon,four\nNot A Shard,1\nInferno Koi,4294967295";
+        let import = parse_inventory_text(text, &shards);
+
+        // duplicates are added up, without overflowing
+        assert_eq!(import.counts, vec![("Falcon".to_string(), 15), ("Inferno Koi".to_string(), u32::MAX)]);
+        assert_eq!(
+            import.errors,
+            vec![
+                "Line 1: skipped \"Shard,Count\" as a header".to_string(),
+                "Line 5: couldn't read \"Falcon,four\"".to_string(),
+                "Line 6: unknown shard \"Not A Shard\"".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use crate::bazaar::bazaar_data::BuyType;
+use crate::shards::fusion::ShardFusionCombinations;
+use crate::shards::shard_data::{ShardData, Shards};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SourceType {
+    Mobs,
+    Traps,
+    FishingLoot,
+    DungeonLoot,
+    Crafting,
+    ShopPurchase,
+    Beacon,
+    TreeGifts,
+    Kuudra,
+    SpecialFusion,
+}
+
+impl SourceType {
+    pub fn get_label_name(&self) -> &str {
+        match self {
+            SourceType::Mobs => "Mobs",
+            SourceType::Traps => "Traps",
+            SourceType::FishingLoot => "Fishing",
+            SourceType::DungeonLoot => "Dungeons",
+            SourceType::Crafting => "Crafting",
+            SourceType::ShopPurchase => "Shops",
+            SourceType::Beacon => "Beacon",
+            SourceType::TreeGifts => "Tree Gifts",
+            SourceType::Kuudra => "Kuudra",
+            SourceType::SpecialFusion => "Special Fusion",
+        }
+    }
+
+    pub fn values() -> Vec<Self> {
+        vec![
+            SourceType::Mobs,
+            SourceType::Traps,
+            SourceType::FishingLoot,
+            SourceType::DungeonLoot,
+            SourceType::Crafting,
+            SourceType::ShopPurchase,
+            SourceType::Beacon,
+            SourceType::TreeGifts,
+            SourceType::Kuudra,
+            SourceType::SpecialFusion,
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum AcquisitionPath {
+    Buy {
+        price: f64,
+    },
+    /// The cheapest fusion that can produce the shard, with the cost split over the amount it makes.
+    Fuse {
+        first_input_shard_name: String,
+        second_input_shard_name: String,
+        cost_per_shard: f64,
+    },
+}
+
+impl AcquisitionPath {
+    pub fn get_cost(&self) -> f64 {
+        match self {
+            AcquisitionPath::Buy { price } => *price,
+            AcquisitionPath::Fuse { cost_per_shard, .. } => *cost_per_shard,
+        }
+    }
+
+    pub fn get_label_name(&self) -> String {
+        match self {
+            AcquisitionPath::Buy { .. } => "Buy".to_string(),
+            AcquisitionPath::Fuse { first_input_shard_name, second_input_shard_name, .. } => {
+                format!("Fuse {first_input_shard_name} + {second_input_shard_name}")
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SourceExplorerRow {
+    pub source_type: SourceType,
+    pub location: String,
+    pub shard_name: String,
+    /// Every way of buying the shard or its fusion inputs, cheapest first.
+    pub acquisition_paths: Vec<AcquisitionPath>,
+    /// Every location the shard can be trapped at. Traps only cost time, which has no coin cost to compare against, so
+    /// they're kept out of the cheapest path.
+    pub trap_locations: Vec<String>,
+}
+
+impl SourceExplorerRow {
+    pub fn get_cheapest_path(&self) -> Option<&AcquisitionPath> {
+        self.acquisition_paths.first()
+    }
+}
+
+/// One row for every source of every shard, grouped by source type and then location. A shard shows up once per
+/// source, so trappable shards that also drop from mobs are listed under both.
+pub fn generate_source_explorer_rows(shards: &Shards, combinations_per_shard: &ShardFusionCombinations, buy_type: BuyType) -> Vec<SourceExplorerRow> {
+    let mut rows = Vec::new();
+
+    for shard in shards.values() {
+        let acquisition_paths = find_acquisition_paths(shard, shards, combinations_per_shard, buy_type);
+        let trap_locations = shard.sources.traps.iter().flatten().map(|trap| trap.to_string()).collect::<Vec<String>>();
+        for (source_type, location) in get_sources_with_locations(shard) {
+            rows.push(SourceExplorerRow {
+                source_type,
+                location,
+                shard_name: shard.shard_name.clone(),
+                acquisition_paths: acquisition_paths.clone(),
+                trap_locations: trap_locations.clone(),
+            });
+        }
+    }
+
+    rows.sort_by(|a, b| {
+        let a_shard = shards.get(&a.shard_name).unwrap();
+        let b_shard = shards.get(&b.shard_name).unwrap();
+        a.source_type.cmp(&b.source_type)
+            .then(a.location.cmp(&b.location))
+            .then(a_shard.rarity.cmp(&b_shard.rarity))
+            .then(a_shard.id.cmp(&b_shard.id))
+    });
+
+    rows
+}
+
+pub fn get_sources_with_locations(shard: &ShardData) -> Vec<(SourceType, String)> {
+    let sources = &shard.sources;
+    let mut sources_with_locations = Vec::new();
+
+    if let Some(mobs) = &sources.mobs {
+        sources_with_locations.push((SourceType::Mobs, mobs.location.clone().unwrap_or_else(|| "Anywhere".to_string())));
+    }
+    if let Some(traps) = &sources.traps {
+        for trap in traps {
+            sources_with_locations.push((SourceType::Traps, trap.to_string()));
+        }
+    }
+    if let Some(fishing_loot) = &sources.fishing_loot {
+        sources_with_locations.push((SourceType::FishingLoot, fishing_loot.location.clone().unwrap_or_else(|| "Anywhere".to_string())));
+    }
+    if let Some(dungeon_loot) = &sources.dungeon_loot {
+        sources_with_locations.push((SourceType::DungeonLoot, format!("Floor {}", dungeon_loot.floor)));
+    }
+    if let Some(crafting) = &sources.crafting {
+        sources_with_locations.push((SourceType::Crafting, format!("{} Collection", crafting.collection)));
+    }
+    if let Some(shop_purchase) = &sources.shop_purchase {
+        sources_with_locations.push((SourceType::ShopPurchase, shop_purchase.npc_name.clone()));
+    }
+    if sources.beacon.unwrap_or(false) {
+        sources_with_locations.push((SourceType::Beacon, String::new()));
+    }
+    if sources.tree_gifts.unwrap_or(false) {
+        sources_with_locations.push((SourceType::TreeGifts, String::new()));
+    }
+    if sources.kuudra.unwrap_or(false) {
+        sources_with_locations.push((SourceType::Kuudra, String::new()));
+    }
+    if sources.special_fusion.is_some() {
+        sources_with_locations.push((SourceType::SpecialFusion, String::new()));
+    }
+
+    sources_with_locations
+}
+
+/// Buying and the cheapest fusion, sorted from cheapest to most expensive. Fusions with an input that has no bazaar
+/// price are skipped.
+pub fn find_acquisition_paths(shard: &ShardData, shards: &Shards, combinations_per_shard: &ShardFusionCombinations, buy_type: BuyType) -> Vec<AcquisitionPath> {
+    let mut paths = Vec::new();
+
+    if let Some(quick_status) = &shard.cached_bazaar_data {
+        let price = quick_status.get_buy_price(buy_type);
+        if price > 0.0 {
+            paths.push(AcquisitionPath::Buy { price });
+        }
+    }
+
+    let cheapest_fusion = combinations_per_shard
+        .get(&shard.shard_name)
+        .into_iter()
+        .flatten()
+        .filter(|combination| combination.has_both_input_costs_above_zero(buy_type, shards))
+        .filter_map(|combination| {
+            let amount_made = if combination.was_chameleon { 1 } else { shard.get_default_amount_made_in_fusion() } as f64;
+            combination
+                .get_total_cost(buy_type, shards)
+                .ok()
+                .map(|cost| (combination, cost as f64 / amount_made))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((combination, cost_per_shard)) = cheapest_fusion {
+        paths.push(AcquisitionPath::Fuse {
+            first_input_shard_name: combination.first_input_shard_name.clone(),
+            second_input_shard_name: combination.second_input_shard_name.clone(),
+            cost_per_shard,
+        });
+    }
+
+    paths.sort_by(|a, b| a.get_cost().total_cmp(&b.get_cost()));
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_acquisition_paths, generate_source_explorer_rows, AcquisitionPath, SourceExplorerRow, SourceType};
+    use crate::bazaar::bazaar_data::BuyType;
+    use crate::shards::fusion::generate_all_possible_combinations_per_shard;
+    use crate::shards::shard_data::read_all_shards_priced_at;
+
+    #[test]
+    fn cheapest_path_follows_the_prices() {
+        let mut shards = read_all_shards_priced_at(100.0);
+        let combinations_per_shard = generate_all_possible_combinations_per_shard(&shards);
+        let shard_name = "Inferno Koi".to_string();
+
+        shards.get_mut(&shard_name).unwrap().cached_bazaar_data.as_mut().unwrap().buy_price = 1_000_000.0;
+        let paths = find_acquisition_paths(&shards[&shard_name], &shards, &combinations_per_shard, BuyType::InstaBuy);
+        assert!(matches!(paths.first(), Some(AcquisitionPath::Fuse { .. })));
+        assert!(matches!(paths.last(), Some(AcquisitionPath::Buy { .. })));
+
+        shards.get_mut(&shard_name).unwrap().cached_bazaar_data.as_mut().unwrap().buy_price = 1.0;
+        let paths = find_acquisition_paths(&shards[&shard_name], &shards, &combinations_per_shard, BuyType::InstaBuy);
+        assert!(matches!(paths.first(), Some(AcquisitionPath::Buy { .. })));
+        assert!(paths.windows(2).all(|pair| pair[0].get_cost() <= pair[1].get_cost()));
+    }
+
+    #[test]
+    fn traps_are_listed_apart_from_the_cheapest_path() {
+        let shards = read_all_shards_priced_at(100.0);
+        let combinations_per_shard = generate_all_possible_combinations_per_shard(&shards);
+        let rows = generate_source_explorer_rows(&shards, &combinations_per_shard, BuyType::InstaBuy);
+
+        let trap_row = rows.iter().find(|row| row.source_type == SourceType::Traps && row.shard_name == "Inferno Koi").unwrap();
+        assert_eq!(trap_row.trap_locations, vec![trap_row.location.clone()]);
+        assert!(matches!(trap_row.get_cheapest_path(), Some(AcquisitionPath::Buy { .. } | AcquisitionPath::Fuse { .. })));
+    }
+
+    #[test]
+    fn every_trap_location_is_listed() {
+        let shards = read_all_shards_priced_at(100.0);
+        let combinations_per_shard = generate_all_possible_combinations_per_shard(&shards);
+        let rows = generate_source_explorer_rows(&shards, &combinations_per_shard, BuyType::InstaBuy);
+
+        let cod_rows = rows.iter().filter(|row| row.shard_name == "Cod").collect::<Vec<&SourceExplorerRow>>();
+        let trap_rows = cod_rows.iter().filter(|row| row.source_type == SourceType::Traps).collect::<Vec<_>>();
+        assert_eq!(trap_rows.len(), 2);
+        assert_ne!(trap_rows[0].location, trap_rows[1].location);
+        for row in cod_rows.iter() {
+            assert_eq!(row.trap_locations, trap_rows.iter().map(|row| row.location.clone()).collect::<Vec<String>>());
+        }
+    }
+}
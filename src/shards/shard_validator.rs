@@ -0,0 +1,172 @@
+use crate::app;
+use crate::catacombs::loot_validator::IssueSeverity;
+use crate::shards::shard_data::{parse_shard_list, Rarity, ShardData, SHARD_DATA_JSON};
+use include_dir::Dir;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq)]
+pub enum ShardValidationIssue {
+    ParseError {
+        error: String,
+    },
+    DuplicateShardName {
+        shard_name: String,
+    },
+    DuplicateRarityAndId {
+        rarity: Rarity,
+        id: u8,
+        shard_names: Vec<String>,
+    },
+    UnknownFusionConditionShard {
+        shard_name: String,
+        unknown_shard_name: String,
+    },
+    MalformedBazaarId {
+        shard_name: String,
+        bazaar_id: String,
+    },
+    MissingImage {
+        shard_name: String,
+        file_name: String,
+    },
+}
+
+impl ShardValidationIssue {
+    pub fn get_severity(&self) -> IssueSeverity {
+        match self {
+            ShardValidationIssue::MissingImage { .. } => IssueSeverity::Warning,
+            _ => IssueSeverity::Error,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.get_severity() == IssueSeverity::Error
+    }
+}
+
+impl Display for ShardValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShardValidationIssue::ParseError { error } => write!(f, "failed to parse shard data: {error}"),
+            ShardValidationIssue::DuplicateShardName { shard_name } => write!(f, "{shard_name} is listed more than once"),
+            ShardValidationIssue::DuplicateRarityAndId { rarity, id, shard_names } => write!(
+                f,
+                "{} all use {}{id}",
+                shard_names.join(", "),
+                rarity.get_char()
+            ),
+            ShardValidationIssue::UnknownFusionConditionShard { shard_name, unknown_shard_name } => write!(
+                f,
+                "{shard_name} has a special fusion condition for {unknown_shard_name}, which doesn't exist"
+            ),
+            ShardValidationIssue::MalformedBazaarId { shard_name, bazaar_id } => write!(
+                f,
+                "{shard_name} has a malformed bazaar ID ({bazaar_id})"
+            ),
+            ShardValidationIssue::MissingImage { shard_name, file_name } => write!(f, "no image for {shard_name} (looked for {file_name})"),
+        }
+    }
+}
+
+/// Checks the shard data bundled with the app.
+pub fn validate_bundled_shard_data() -> Vec<ShardValidationIssue> {
+    validate_shard_data(SHARD_DATA_JSON, &app::ASSETS_DIR)
+}
+
+/// Reports anything in the shard data that would either fail to load, silently overwrite another shard, or make
+/// fusions, prices or images come out wrong. Images are looked up by file name anywhere in the assets directory, the
+/// same way the app does.
+pub fn validate_shard_data(json: &str, assets_dir: &Dir<'_>) -> Vec<ShardValidationIssue> {
+    let shards = match parse_shard_list(json) {
+        Ok(shards) => shards,
+        Err(error) => return vec![ShardValidationIssue::ParseError { error }],
+    };
+
+    let image_file_names = assets_dir
+        .find("**/*.png")
+        .unwrap()
+        .filter_map(|file| file.path().file_name().and_then(|n| n.to_str()).map(String::from))
+        .collect::<HashSet<String>>();
+
+    let mut issues = Vec::new();
+    let shard_names = shards.iter().map(|s| s.shard_name.clone()).collect::<HashSet<String>>();
+
+    let mut seen_names = HashSet::new();
+    let mut shards_per_rarity_and_id: BTreeMap<(&Rarity, u8), Vec<String>> = BTreeMap::new();
+    for shard in shards.iter() {
+        if !seen_names.insert(&shard.shard_name) {
+            issues.push(ShardValidationIssue::DuplicateShardName { shard_name: shard.shard_name.clone() });
+        }
+        shards_per_rarity_and_id
+            .entry((&shard.rarity, shard.id))
+            .or_default()
+            .push(shard.shard_name.clone());
+
+        for unknown_shard_name in find_unknown_condition_shards(shard, &shard_names) {
+            issues.push(ShardValidationIssue::UnknownFusionConditionShard {
+                shard_name: shard.shard_name.clone(),
+                unknown_shard_name,
+            });
+        }
+
+        let bazaar_id = shard.get_bazaar_id();
+        if !is_well_formed_bazaar_id(&bazaar_id) {
+            issues.push(ShardValidationIssue::MalformedBazaarId {
+                shard_name: shard.shard_name.clone(),
+                bazaar_id,
+            });
+        }
+
+        let file_name = shard.get_image_file_name();
+        if !image_file_names.contains(&file_name) {
+            issues.push(ShardValidationIssue::MissingImage {
+                shard_name: shard.shard_name.clone(),
+                file_name,
+            });
+        }
+    }
+
+    for ((rarity, id), shard_names) in shards_per_rarity_and_id {
+        if shard_names.len() > 1 {
+            issues.push(ShardValidationIssue::DuplicateRarityAndId {
+                rarity: rarity.clone(),
+                id,
+                shard_names,
+            });
+        }
+    }
+
+    issues
+}
+
+fn find_unknown_condition_shards(shard: &ShardData, shard_names: &HashSet<String>) -> Vec<String> {
+    let special_fusion = shard.sources.special_fusion.as_ref();
+    if special_fusion.is_none() {
+        return Vec::new();
+    }
+    let special_fusion = special_fusion.unwrap();
+
+    special_fusion
+        .first
+        .iter()
+        .chain(special_fusion.second.iter())
+        .filter_map(|conditions| conditions.shards.as_ref())
+        .flatten()
+        .filter(|name| !shard_names.contains(*name))
+        .cloned()
+        .collect()
+}
+
+/// Bazaar IDs look like `SHARD_SEA_SERPENT`, so anything other than uppercase letters, digits and single underscores
+/// won't match a product.
+fn is_well_formed_bazaar_id(bazaar_id: &str) -> bool {
+    let name = bazaar_id.strip_prefix("SHARD_");
+    name.is_some_and(|name| {
+        !name.is_empty()
+            && !name.starts_with('_')
+            && !name.ends_with('_')
+            && !name.contains("__")
+            && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    })
+}
@@ -1,23 +1,29 @@
 use crate::images;
-use crate::shards::bazaar_data::BazaarData;
-use crate::shards::fusion::{generate_all_possible_combinations, generate_all_possible_combinations_per_shard, FusionResults, ShardFusionCombinations};
+use crate::shards::attribute_levels::{read_attribute_levels, AttributeLevels};
+use crate::bazaar::bazaar_client::{self, SharedBazaarClient};
+use crate::shards::fusion::{generate_all_possible_combinations, generate_all_possible_combinations_per_input_shard, generate_all_possible_combinations_per_shard, sort_fusion_partners, FusionPartnersSortType, FusionResults, ShardFusionCombinations, ShardFusionPartners};
 use crate::shards::shard_data::{ShardData, Shards};
 use crate::shards::shards_page::AmountType::{ConsumedInFusion, MadeInFusion};
-use crate::shards::shards_page::BuyType::{BuyOrder, InstaBuy};
-use crate::shards::shards_page::ProfitType::{InstaSell, SellOffer};
-use crate::shards::shards_page::ShardCalculatorType::{AllFusionOutputs, BestTrapPlacements, FusionOutputs, FusionProfits};
-use crate::shards::{bazaar_api, fusion, shard_data};
-use crossbeam_channel::{unbounded, Receiver, Sender};
+use crate::bazaar::bazaar_data::BuyType::{BuyOrder, InstaBuy};
+use crate::bazaar::bazaar_data::{BuyType, ProfitType};
+use crate::bazaar::bazaar_data::ProfitType::{InstaSell, SellOffer};
+use crate::shards::shard_inventory::{parse_inventory_text, InventorySuggestions, PriceSettings, ShardInventory, INVENTORY_STORAGE_KEY};
+use crate::shards::shards_page::ShardCalculatorType::{AllFusionOutputs, BestTrapPlacements, FusionOutputs, FusionProfits, FusionPartners, Inventory, AttributeLevelCalculator, SourceExplorer, FarmingComparison};
+use crate::shards::shard_description::create_description_layout_job;
+use crate::shards::farming_profits::{calculate_farming_profits, FarmingKey, FarmingMethod, FarmingProfit, FarmingRate};
+use crate::shards::trap_profits::{calculate_trap_location_profits, TrapLocationKey, TrapLocationProfit, TrapSettings};
+use crate::shards::shard_sources::{generate_source_explorer_rows, AcquisitionPath, SourceExplorerRow, SourceType};
+use crate::shards::{fusion, shard_data};
 use eframe::epaint::{FontId, TextureHandle};
 use egui::text::LayoutJob;
-use egui::{Button, Color32, Context, Grid, Layout, RichText, ScrollArea, Slider, TextFormat, Ui};
+use egui::{Color32, Context, DragValue, Grid, Layout, RichText, ScrollArea, Slider, TextEdit, TextFormat, Ui};
 use egui_extras::{Column, TableBuilder};
 use num_format::Locale::en;
 use num_format::ToFormattedString;
 use std::collections::HashMap;
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::rc::Rc;
-use web_time::{Duration, Instant};
+use web_time::Instant;
 
 pub struct ShardsPage {
     shards: Shards,
@@ -28,13 +34,10 @@ pub struct ShardsPage {
     left_shard_name: Option<String>,
     right_shard_name: Option<String>,
 
-    bazaar_data_sender: Sender<Option<BazaarData>>,
-    bazaar_data_receiver: Receiver<Option<BazaarData>>,
-    bazaar_data: Option<BazaarData>,
-    looking_up_bazaar_data: bool,
+    bazaar: SharedBazaarClient,
+    /// The bazaar data version the shard prices were last cached from.
+    cached_bazaar_data_version: u64,
     bazaar_tax_percent: f64,
-    bazaar_request_triggered: bool,
-    last_bazaar_request_ms: Option<Instant>,
 
     combinations_shard_name: Option<String>,
     buy_type: BuyType,
@@ -45,6 +48,44 @@ pub struct ShardsPage {
     all_combinations_by_shards: Option<ShardFusionCombinations>,
     all_combinations: Option<Vec<FusionResults>>,
     combination_profit_data: Option<Vec<(String, FusionResults)>>,
+
+    partners_shard_name: Option<String>,
+    partners_sort_type: FusionPartnersSortType,
+    all_combinations_by_input_shards: Option<ShardFusionPartners>,
+    only_held_partners: bool,
+
+    inventory: ShardInventory,
+    inventory_view: InventoryView,
+    inventory_shard_to_add: Option<String>,
+    inventory_amount_to_add: u32,
+    inventory_import_text: String,
+    inventory_import_path: String,
+    inventory_import_messages: Vec<String>,
+    inventory_suggestions_hash: Option<u64>,
+    inventory_suggestions: Option<InventorySuggestions>,
+
+    attribute_levels: AttributeLevels,
+    attribute_shard_name: Option<String>,
+    attribute_current_level: u8,
+    attribute_target_level: u8,
+
+    source_type_filter: Option<SourceType>,
+    source_explorer_hash: Option<u64>,
+    source_explorer_rows: Option<Vec<SourceExplorerRow>>,
+
+    trap_view: TrapView,
+    trap_settings: HashMap<TrapLocationKey, TrapSettings>,
+    trap_profits_hash: Option<u64>,
+    trap_profits: Option<Vec<TrapLocationProfit>>,
+    /// Whether a trap setting was being dragged or typed in last frame, the rows keep their order until it's done.
+    trap_settings_editing: bool,
+
+    farming_method_filter: Option<FarmingMethod>,
+    farming_rates: HashMap<FarmingKey, FarmingRate>,
+    farming_profits_hash: Option<u64>,
+    farming_profits: Option<Vec<FarmingProfit>>,
+    /// Whether a rate was being dragged or typed in last frame, the rows keep their order until it's done.
+    farming_rates_editing: bool,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -53,18 +94,11 @@ pub enum ShardCalculatorType {
     AllFusionOutputs,
     FusionOutputs,
     BestTrapPlacements,
-}
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum BuyType {
-    BuyOrder,
-    InstaBuy,
-}
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-pub enum ProfitType {
-    SellOffer,
-    InstaSell,
+    FusionPartners,
+    Inventory,
+    AttributeLevelCalculator,
+    SourceExplorer,
+    FarmingComparison,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -88,17 +122,25 @@ impl AllFusionsSortType {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TrapView {
+    PerShard,
+    PerLocation,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum InventoryView {
+    Stacks,
+    OwnedFusions,
+    Purchases,
+}
+
 impl eframe::App for ShardsPage {
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
         let system_time = Instant::now();
 
-        if let Ok(data) = self.bazaar_data_receiver.try_recv() {
-            self.bazaar_data = data;
-            self.last_bazaar_request_ms = Some(system_time);
-            self.looking_up_bazaar_data = false;
-            self.bazaar_request_triggered = false;
+        if self.bazaar.borrow().get_data_version() != self.cached_bazaar_data_version {
             self.cache_bazaar_prices();
-            ctx.request_repaint_after(Duration::from_secs(1));
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -107,6 +149,11 @@ impl eframe::App for ShardsPage {
                 ui.selectable_value(&mut self.calculator_type, AllFusionOutputs, "All Fusion Outputs");
                 ui.selectable_value(&mut self.calculator_type, FusionOutputs, "Fusion Outputs");
                 ui.selectable_value(&mut self.calculator_type, BestTrapPlacements, "Traps");
+                ui.selectable_value(&mut self.calculator_type, FusionPartners, "Fusion Partners");
+                ui.selectable_value(&mut self.calculator_type, Inventory, "Inventory");
+                ui.selectable_value(&mut self.calculator_type, AttributeLevelCalculator, "Attribute Levels");
+                ui.selectable_value(&mut self.calculator_type, SourceExplorer, "How to Get");
+                ui.selectable_value(&mut self.calculator_type, FarmingComparison, "Farming");
             });
             ui.separator();
 
@@ -123,10 +170,10 @@ impl eframe::App for ShardsPage {
                             ui.end_row();
 
                             ui.label("First Shard:");
-                            add_shard_option(ui, "first_shard", &mut self.left_shard_name, &self.shards, &self.search_query, &self.images);
+                            add_shard_option(ui, "first_shard", &mut self.left_shard_name, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
                             ui.end_row();
                             ui.label("Second Shard:");
-                            add_shard_option(ui, "second_shard", &mut self.right_shard_name, &self.shards, &self.search_query, &self.images);
+                            add_shard_option(ui, "second_shard", &mut self.right_shard_name, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
                             ui.end_row();
                         } else if self.calculator_type == FusionProfits {
                             ui.horizontal(|ui| {
@@ -190,20 +237,11 @@ impl eframe::App for ShardsPage {
                                 images::add_image(&self.images, ui, "eye_of_ender.png");
                                 ui.label("Output Filter:");
                             });
-                            add_shard_option(ui, "output_filter", &mut self.combinations_shard_name, &self.shards, &self.search_query, &self.images);
+                            add_shard_option(ui, "output_filter", &mut self.combinations_shard_name, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
                             ui.end_row();
 
                             ui.label(""); // to force button into row two
-                            if self.bazaar_request_triggered && self.looking_up_bazaar_data {
-                                ui.add_enabled(false, Button::new("Refreshing data..."));
-                            } else if self.last_bazaar_request_ms.is_some_and(|last_update| system_time.duration_since(last_update).as_millis() < 60000) {
-                                let difference_in_ms = (system_time - self.last_bazaar_request_ms.unwrap()).as_millis();
-                                let time_until_button_enabled = 60 - (difference_in_ms) / 1000;
-                                ctx.request_repaint_after(Duration::from_millis(1000 - (difference_in_ms % 1000) as u64));
-                                ui.add_enabled(false, Button::new(format!("Can refresh Bazaar data in {time_until_button_enabled}s")));
-                            } else if ui.button("Refresh Bazaar").clicked() {
-                                self.bazaar_request_triggered = true;
-                            }
+                            bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
                         } else if self.calculator_type == AllFusionOutputs {
                             ui.horizontal(|ui| {
                                 images::add_image(&self.images, ui, "attribute_pure_reptile.png");
@@ -211,29 +249,107 @@ impl eframe::App for ShardsPage {
                             });
                             ui.add(Slider::new(&mut self.pure_reptile_attribute_level, 0..=10));
                             ui.end_row();
-                        } else if self.calculator_type == ShardCalculatorType::BestTrapPlacements {
+                        } else if self.calculator_type == FusionPartners {
+                            ui.horizontal(|ui| {
+                                images::add_image(&self.images, ui, "oak_sign.png");
+                                ui.label("Search Shards:");
+                            });
+                            ui.text_edit_singleline(&mut self.search_query);
+                            ui.end_row();
+
+                            ui.horizontal(|ui| {
+                                images::add_image(&self.images, ui, "eye_of_ender.png");
+                                ui.label("Shard:");
+                            });
+                            add_shard_option(ui, "partners_shard", &mut self.partners_shard_name, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
+                            ui.end_row();
+
                             ui.horizontal(|ui| {
                                 images::add_image(&self.images, ui, "redstone_repeater.png");
                                 ui.label("Sort By:");
                             });
+                            ui.horizontal(|ui| {
+                                for sort_type in FusionPartnersSortType::values() {
+                                    ui.selectable_value(&mut self.partners_sort_type, sort_type, sort_type.get_label_name());
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.horizontal(|ui| {
+                                images::add_image(&self.images, ui, "golden_horse_armor.png");
+                                ui.label("Buying Method:");
+                            });
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(&mut self.buy_type, InstaBuy, "Insta-Buy");
+                                ui.selectable_value(&mut self.buy_type, BuyOrder, "Buy Order");
+                            });
+                            ui.end_row();
 
+                            ui.horizontal(|ui| {
+                                images::add_image(&self.images, ui, "hopper.png");
+                                ui.label("Selling Method:");
+                            });
                             ui.horizontal(|ui| {
                                 ui.selectable_value(&mut self.profit_type, InstaSell, "Insta-Sell");
                                 ui.selectable_value(&mut self.profit_type, SellOffer, "Sell Offer");
                             });
                             ui.end_row();
 
+                            ui.label("Only Held Partners:");
+                            ui.checkbox(&mut self.only_held_partners, "")
+                                .on_hover_text("Held shards are the ones in your inventory");
+                            ui.end_row();
+
                             ui.label(""); // to force button into row two
-                            if self.bazaar_request_triggered && self.looking_up_bazaar_data {
-                                ui.add_enabled(false, Button::new("Refreshing data..."));
-                            } else if self.last_bazaar_request_ms.is_some_and(|last_update| system_time.duration_since(last_update).as_millis() < 60000) {
-                                let difference_in_ms = (system_time - self.last_bazaar_request_ms.unwrap()).as_millis();
-                                let time_until_button_enabled = 60 - (difference_in_ms) / 1000;
-                                ctx.request_repaint_after(Duration::from_millis(1000 - (difference_in_ms % 1000) as u64));
-                                ui.add_enabled(false, Button::new(format!("Can refresh Bazaar data in {time_until_button_enabled}s")));
-                            } else if ui.button("Refresh Bazaar").clicked() {
-                                self.bazaar_request_triggered = true;
+                            bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
+                        } else if self.calculator_type == Inventory {
+                            self.add_inventory_options(ctx, ui, system_time);
+                        } else if self.calculator_type == AttributeLevelCalculator {
+                            self.add_attribute_level_options(ctx, ui, system_time);
+                        } else if self.calculator_type == SourceExplorer {
+                            self.add_source_explorer_options(ctx, ui, system_time);
+                        } else if self.calculator_type == FarmingComparison {
+                            self.add_farming_options(ctx, ui, system_time);
+                        } else if self.calculator_type == ShardCalculatorType::BestTrapPlacements {
+                            ui.label("View:");
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(&mut self.trap_view, TrapView::PerShard, "Per Shard");
+                                ui.selectable_value(&mut self.trap_view, TrapView::PerLocation, "Per Location");
+                            });
+                            ui.end_row();
+
+                            ui.horizontal(|ui| {
+                                images::add_image(&self.images, ui, "redstone_repeater.png");
+                                ui.label("Sort By:");
+                            });
+
+                            ui.horizontal(|ui| {
+                                ui.selectable_value(&mut self.profit_type, InstaSell, "Insta-Sell");
+                                ui.selectable_value(&mut self.profit_type, SellOffer, "Sell Offer");
+                            });
+                            ui.end_row();
+
+                            if self.trap_view == TrapView::PerLocation {
+                                ui.horizontal(|ui| {
+                                    images::add_image(&self.images, ui, "book.png");
+                                    ui.label("Bazaar Tax Rate:");
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.selectable_value(&mut self.bazaar_tax_percent, 0.01, "1%");
+                                    ui.selectable_value(&mut self.bazaar_tax_percent, 0.01125, "1.125%");
+                                    ui.selectable_value(&mut self.bazaar_tax_percent, 0.0125, "1.25%");
+                                });
+                                ui.end_row();
+
+                                ui.label("Reset Rates:");
+                                if ui.button("Use Defaults").clicked() {
+                                    self.trap_settings.clear();
+                                }
+                                ui.end_row();
                             }
+
+                            ui.label(""); // to force button into row two
+                            bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
                         }
                     });
             });
@@ -253,20 +369,54 @@ impl eframe::App for ShardsPage {
                 }
                 ShardCalculatorType::BestTrapPlacements => {
                     ScrollArea::horizontal().id_salt("best_traps").show(ui, |ui| {
-                        self.add_best_traps(ui)
+                        match self.trap_view {
+                            TrapView::PerShard => self.add_best_traps(ui),
+                            TrapView::PerLocation => self.add_trap_location_profits(ui),
+                        }
+                    });
+                }
+                FusionPartners => {
+                    ScrollArea::horizontal().id_salt("fusion_partners").show(ui, |ui| {
+                        self.add_fusion_partners(ui)
+                    });
+                }
+                Inventory => {
+                    ui.horizontal(|ui| {
+                        ui.selectable_value(&mut self.inventory_view, InventoryView::Stacks, "Sell or Fuse");
+                        ui.selectable_value(&mut self.inventory_view, InventoryView::OwnedFusions, "Fusions From Inventory");
+                        ui.selectable_value(&mut self.inventory_view, InventoryView::Purchases, "Fusions To Buy For");
+                    });
+                    ScrollArea::horizontal().id_salt("shard_inventory").show(ui, |ui| {
+                        self.add_inventory_suggestions(ui)
+                    });
+                }
+                AttributeLevelCalculator => {
+                    ScrollArea::horizontal().id_salt("attribute_levels").show(ui, |ui| {
+                        self.add_attribute_levels(ui)
+                    });
+                }
+                SourceExplorer => {
+                    ScrollArea::horizontal().id_salt("source_explorer").show(ui, |ui| {
+                        self.add_source_explorer(ui)
+                    });
+                }
+                FarmingComparison => {
+                    ScrollArea::horizontal().id_salt("farming_profits").show(ui, |ui| {
+                        self.add_farming_profits(ui)
                     });
                 }
             }
         });
     }
 
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, INVENTORY_STORAGE_KEY, &self.inventory);
+    }
 }
 
 impl ShardsPage {
-    pub fn new(images: Rc<HashMap<String, TextureHandle>>) -> Self {
+    pub fn new(images: Rc<HashMap<String, TextureHandle>>, bazaar: SharedBazaarClient, storage: Option<&dyn eframe::Storage>) -> Self {
         let shards = shard_data::read_all_shards();
-        let (bz_tx, bz_rx) = unbounded();
 
         Self {
             shards,
@@ -277,13 +427,9 @@ impl ShardsPage {
             combinations_shard_name: None,
             calculator_type: FusionProfits,
             profit_type: InstaSell,
-            looking_up_bazaar_data: false,
-            bazaar_data_sender: bz_tx,
-            bazaar_data_receiver: bz_rx,
-            bazaar_data: None,
+            bazaar,
+            cached_bazaar_data_version: 0,
             bazaar_tax_percent: 0.01,
-            bazaar_request_triggered: false,
-            last_bazaar_request_ms: None,
             pure_reptile_attribute_level: 10,
             profit_data_hash: None,
             all_combinations_by_shards: None,
@@ -291,6 +437,38 @@ impl ShardsPage {
             combination_profit_data: None,
             buy_type: InstaBuy,
             sort_type: AllFusionsSortType::Profit,
+            partners_shard_name: None,
+            partners_sort_type: FusionPartnersSortType::Profit,
+            all_combinations_by_input_shards: None,
+            only_held_partners: false,
+            inventory: storage
+                .and_then(|storage| eframe::get_value(storage, INVENTORY_STORAGE_KEY))
+                .unwrap_or_default(),
+            inventory_view: InventoryView::Stacks,
+            inventory_shard_to_add: None,
+            inventory_amount_to_add: 1,
+            inventory_import_text: String::new(),
+            inventory_import_path: String::new(),
+            inventory_import_messages: Vec::new(),
+            inventory_suggestions_hash: None,
+            inventory_suggestions: None,
+            attribute_levels: read_attribute_levels(),
+            attribute_shard_name: None,
+            attribute_current_level: 0,
+            attribute_target_level: 10,
+            source_type_filter: None,
+            source_explorer_hash: None,
+            source_explorer_rows: None,
+            trap_view: TrapView::PerShard,
+            trap_settings: HashMap::new(),
+            trap_profits_hash: None,
+            trap_profits: None,
+            trap_settings_editing: false,
+            farming_method_filter: None,
+            farming_rates: HashMap::new(),
+            farming_profits_hash: None,
+            farming_profits: None,
+            farming_rates_editing: false,
         }
     }
 
@@ -314,12 +492,9 @@ impl ShardsPage {
     }
 
     fn add_most_profitable_shard_combinations(&mut self, ui: &mut Ui) {
-        if !self.looking_up_bazaar_data && (self.bazaar_data.is_none() || self.bazaar_request_triggered) {
-            self.looking_up_bazaar_data = true;
-            bazaar_api::set_shard_prices(self.bazaar_data_sender.clone());
-        }
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
 
-        if self.bazaar_data.is_none() {
+        if !self.bazaar.borrow().has_data() {
             ui.label("Fetching Bazaar Data...");
             return;
         }
@@ -341,8 +516,6 @@ impl ShardsPage {
         }
         self.sort_all_shards_if_necessary(self.sort_type);
 
-        let bazaar_data = self.bazaar_data.as_ref().unwrap();
-
         let profit_sorted_combinations = if let Some(shard_output_filter) = self.combinations_shard_name.as_ref() {
             self.combination_profit_data
                 .as_ref()
@@ -390,6 +563,9 @@ impl ShardsPage {
                 header.col(|ui| {
                     ui.strong("Total Cost");
                 });
+                header.col(|ui| {
+                    ui.strong("Bonus");
+                });
                 header.col(|ui| {
                     ui.strong("Revenue");
                 });
@@ -418,13 +594,13 @@ impl ShardsPage {
                         self.add_numbered_shard_text(ui, first_input_shard, ConsumedInFusion, combination);
                     });
                     row.col(|ui| {
-                        self.add_shard_cost(ui, first_input_shard, bazaar_data);
+                        self.add_shard_cost(ui, first_input_shard);
                     });
                     row.col(|ui| {
                         self.add_numbered_shard_text(ui, second_input_shard, ConsumedInFusion, combination);
                     });
                     row.col(|ui| {
-                        self.add_shard_cost(ui, second_input_shard, bazaar_data);
+                        self.add_shard_cost(ui, second_input_shard);
                     });
 
                     match combination.get_total_cost(self.buy_type, &self.shards) {
@@ -450,7 +626,7 @@ impl ShardsPage {
                                 ui.label(RichText::new(format!("{}{}%", possible_plus_sign, percent_gain.to_formatted_string(&en))).color(get_profit_color(profit)));
                             });
                             row.col(|ui| {
-                                let purchased_in_last_week = bazaar_data.get(&output_shard.get_bazaar_id()).unwrap().quick_status.buy_moving_week;
+                                let purchased_in_last_week = output_shard.cached_bazaar_data.as_ref().unwrap().buy_moving_week;
                                 ui.label(RichText::new(purchased_in_last_week.to_formatted_string(&en)).color(Color32::from_rgb(255, 255, 85)));
                             })
                         }
@@ -460,12 +636,9 @@ impl ShardsPage {
     }
 
     fn add_best_traps(&mut self, ui: &mut Ui) {
-        if !self.looking_up_bazaar_data && (self.bazaar_data.is_none() || self.bazaar_request_triggered) {
-            self.looking_up_bazaar_data = true;
-            bazaar_api::set_shard_prices(self.bazaar_data_sender.clone());
-        }
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
 
-        if self.bazaar_data.is_none() {
+        if !self.bazaar.borrow().has_data() {
             ui.label("Fetching Bazaar Data...");
             return;
         }
@@ -523,7 +696,7 @@ impl ShardsPage {
                     let purchased_in_last_week = shard_data.cached_bazaar_data.as_ref().unwrap().buy_moving_week;
 
                     row.col(|ui| {
-                        add_shard_text(ui, shard_data, &self.images);
+                        add_shard_text(ui, shard_data, &self.images, self.attribute_levels.max_level);
                     });
                     row.col(|ui| {
                         ui.label(trap_locations);
@@ -541,213 +714,1362 @@ impl ShardsPage {
             });
     }
 
-    fn add_all_fusion_combinations(&mut self, ui: &mut Ui) {
-        if self.all_combinations.is_none() {
-            self.all_combinations = Some(generate_all_possible_combinations(&self.shards));
+    fn add_trap_location_profits(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
+
+        if !self.bazaar.borrow().has_data() {
+            ui.label("Fetching Bazaar Data...");
+            return;
         }
-        let all_combinations = self.all_combinations.as_ref().unwrap();
 
+        let hash = self.generate_trap_profits_hash();
+        if self.trap_profits.is_none() || self.trap_profits_hash != Some(hash) {
+            let mut location_profits = calculate_trap_location_profits(&self.shards, &self.trap_settings, self.profit_type, self.bazaar_tax_percent);
+            // re-ranking would move the row that's being edited, and its widgets would continue on whatever row took
+            // its place
+            if let (true, Some(previous_profits)) = (self.trap_settings_editing, &self.trap_profits) {
+                keep_row_order(&mut location_profits, previous_profits, |profit| profit.key.clone());
+            }
+            self.trap_profits = Some(location_profits);
+            self.trap_profits_hash = Some(hash);
+        }
+        let location_profits = self.trap_profits.as_ref().unwrap();
+        let mut changed_settings = None;
+        let mut editing = false;
+
+        ui.label(RichText::new("Each catch is assumed to be equally likely to be any shard at the location. Placeholder catch rates aren't measured, \
+            so those locations are only ranked once you enter your own rate.")
+            .color(Color32::GRAY));
         ui.end_row();
         let available_height = ui.available_height();
         TableBuilder::new(ui)
             .striped(true)
             .resizable(false)
             .cell_layout(Layout::left_to_right(egui::Align::Center))
-            .columns(Column::auto(), 2)
-            .column(Column::initial(210.0))
-            .column(Column::auto())
-            .columns(Column::initial(190.0), 2)
+            .columns(Column::auto(), 6)
             .column(Column::remainder())
             .drag_to_scroll(true)
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height)
             .header(20.0, |mut header| {
                 header.col(|ui| {
-                    ui.strong("Left Input");
+                    ui.strong("Location");
                 });
                 header.col(|ui| {
-                    ui.strong("+");
+                    ui.strong("Traps");
                 });
                 header.col(|ui| {
-                    ui.strong("Right Input");
+                    ui.strong("Catches/Trap/Hour");
                 });
                 header.col(|ui| {
-                    ui.strong("=");
+                    ui.strong("Catches/Hour");
                 });
                 header.col(|ui| {
-                    ui.strong("Output #1");
+                    ui.strong("Avg Shard Value");
                 });
                 header.col(|ui| {
-                    ui.strong("Output #2");
+                    ui.strong("Coins/Hour");
                 });
                 header.col(|ui| {
-                    ui.strong("Output #3");
+                    ui.strong("Shards");
                 });
             })
             .body(|body| {
-                body.rows(16.0, all_combinations.len(), |mut row| {
-                    let index = row.index();
-                    let combination = all_combinations.get(index).unwrap();
-
-                    let first_input_shard = self.shards.get(&combination.first_input_shard_name).unwrap();
-                    let second_input_shard = self.shards.get(&combination.second_input_shard_name).unwrap();
+                body.rows(18.0, location_profits.len(), |mut row| {
+                    let location_profit = location_profits.get(row.index()).unwrap();
+                    let mut settings = self.trap_settings.get(&location_profit.key).copied().unwrap_or_default();
 
                     row.col(|ui| {
-                        self.add_numbered_shard_text(ui, first_input_shard, ConsumedInFusion, combination);
+                        ui.label(location_profit.key.get_label_name());
                     });
                     row.col(|ui| {
-                        ui.label("+");
+                        ui.push_id(&location_profit.key, |ui| {
+                            let response = ui.add(DragValue::new(&mut settings.trap_count).range(0..=100));
+                            editing |= response.dragged() || response.has_focus();
+                            if response.changed() {
+                                changed_settings = Some((location_profit.key.clone(), settings));
+                            }
+                        });
                     });
                     row.col(|ui| {
-                        self.add_numbered_shard_text(ui, second_input_shard, ConsumedInFusion, combination);
+                        ui.push_id(&location_profit.key, |ui| {
+                            let mut drag_value = DragValue::new(&mut settings.catches_per_trap_per_hour).range(0.0..=3600.0).speed(0.1);
+                            if location_profit.uses_placeholder_rate {
+                                drag_value = drag_value.custom_formatter(|rate, _| format!("{rate} (placeholder)"));
+                            }
+                            let response = ui.add(drag_value);
+                            editing |= response.dragged() || response.has_focus();
+                            if response.changed() {
+                                changed_settings = Some((location_profit.key.clone(), settings));
+                            }
+                        });
                     });
                     row.col(|ui| {
-                        ui.label("=");
+                        ui.label(format!("{:.1}", location_profit.catches_per_hour));
+                    });
+                    row.col(|ui| {
+                        match location_profit.average_shard_value {
+                            Some(value) => ui.label(RichText::new((value as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0))),
+                            None => ui.label("-"),
+                        };
+                    });
+                    row.col(|ui| {
+                        let coins_per_hour = location_profit.coins_per_hour as i64;
+                        let color = if location_profit.uses_placeholder_rate { Color32::GRAY } else { get_profit_color(coins_per_hour) };
+                        ui.label(RichText::new(coins_per_hour.to_formatted_string(&en)).color(color));
+                    });
+                    row.col(|ui| {
+                        for shard_name in location_profit.shard_names.iter() {
+                            let shard = self.shards.get(shard_name).unwrap();
+                            add_shard_text(ui, shard, &self.images, self.attribute_levels.max_level);
+                        }
                     });
-
-                    for i in 0..3 {
-                        row.col(|ui| {
-                            if let Some(output) = combination.listed_fusions.get(i) {
-                                self.add_numbered_shard_text(ui, self.shards.get(output).unwrap(), MadeInFusion, combination);
-                            }
-                        });
-                    }
                 });
             });
-    }
 
-    pub fn cache_bazaar_prices(&mut self) {
-        if let Some(bazaar_data) = self.bazaar_data.as_ref() {
-            for shard_data in self.shards.values_mut() {
-                let possible_data = bazaar_data.get(&shard_data.get_bazaar_id());
-                if possible_data.is_none() {
-                    eprintln!("Couldn't fetch data for shard {} ({}) ({})", shard_data.shard_name, shard_data.attribute_name, shard_data.get_bazaar_id());
-                }
-                let quick_status = &possible_data.unwrap().quick_status;
-                shard_data.cached_bazaar_data = Some(quick_status.clone());
-            }
+        if let Some((key, settings)) = changed_settings {
+            self.trap_settings.insert(key, settings);
         }
+        if self.trap_settings_editing && !editing {
+            // done editing, so rank the rows again
+            self.trap_profits = None;
+            ui.ctx().request_repaint();
+        }
+        self.trap_settings_editing = editing;
     }
 
-    fn add_numbered_shard_text(
-        &self,
-        ui: &mut Ui,
-        shard: &ShardData,
-        amount_type: AmountType,
-        combination: &FusionResults,
-    ) {
-        let amount = match amount_type {
-            ConsumedInFusion => shard.get_amount_consumed_in_fusion(),
-            MadeInFusion => if combination.was_chameleon {
-                1
-            } else {
-                shard.get_default_amount_made_in_fusion()
-            },
-        };
+    fn add_farming_options(&mut self, ctx: &Context, ui: &mut Ui, system_time: Instant) {
         ui.horizontal(|ui| {
-            if amount_type == MadeInFusion && combination.is_reptile_fusion && self.pure_reptile_attribute_level > 0 {
-                let higher_amount = amount * 2;
-                ui.label(format!("{amount}-{higher_amount}x"));
-            } else {
-                ui.label(format!("{amount}x"));
-            }
-            add_shard_image(ui, shard, &self.images);
-            ui.label(RichText::new(shard.shard_name.clone()).color(shard.rarity.get_color()));
-            ui.label(format!("{}{}", shard.rarity.get_char(), shard.id))
+            images::add_image(&self.images, ui, "oak_sign.png");
+            ui.label("Search:");
         });
-    }
+        ui.text_edit_singleline(&mut self.search_query)
+            .on_hover_text("Matches shard names and locations");
+        ui.end_row();
 
-    fn add_shard_cost(&self, ui: &mut Ui, shard: &ShardData, bazaar_data: &BazaarData) {
-        let amount = shard.get_amount_consumed_in_fusion();
-        if let Some(price_data) = bazaar_data.get(&shard.get_bazaar_id()) {
-            let amount_string = ((price_data.quick_status.get_buy_price(self.buy_type) * amount as f64) as i64).to_formatted_string(&en);
-            ui.label(RichText::new(amount_string).color(Color32::from_rgb(255, 170, 0)));
-        }
-    }
+        ui.label("Method:");
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.farming_method_filter, None, "All");
+            for method in FarmingMethod::values() {
+                ui.selectable_value(&mut self.farming_method_filter, Some(method), method.get_label_name());
+            }
+        });
+        ui.end_row();
 
-    fn sort_all_shards_if_necessary(&mut self, sort_type: AllFusionsSortType) {
-        let new_hash = self.generate_sorting_recalculation_hash();
-        if self.profit_data_hash.is_some_and(|h| h != new_hash) {
-            return;
-        }
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "hopper.png");
+            ui.label("Selling Method:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.profit_type, InstaSell, "Insta-Sell");
+            ui.selectable_value(&mut self.profit_type, SellOffer, "Sell Offer");
+        });
+        ui.end_row();
 
-        if let Some(profit_sorted_combinations) = self.combination_profit_data.as_mut() {
-            profit_sorted_combinations.sort_by(|(a_shard_name, a_data), (b_shard_name, b_data)| {
-                match sort_type {
-                    AllFusionsSortType::ShardName => a_shard_name.cmp(b_shard_name)
-                        .then(a_data.first_input_shard_name.cmp(&b_data.first_input_shard_name))
-                        .then(a_data.second_input_shard_name.cmp(&b_data.second_input_shard_name)),
-                    AllFusionsSortType::ShardIdAndRarity => {
-                        let a_shard = &self.shards.get(a_shard_name).unwrap();
-                        let a_shard_first_input = &self.shards.get(&a_data.first_input_shard_name).unwrap();
-                        let a_shard_second_input = &self.shards.get(&a_data.second_input_shard_name).unwrap();
-                        let b_shard = &self.shards.get(b_shard_name).unwrap();
-                        let b_shard_first_input = &self.shards.get(&b_data.first_input_shard_name).unwrap();
-                        let b_shard_second_input = &self.shards.get(&b_data.second_input_shard_name).unwrap();
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "book.png");
+            ui.label("Bazaar Tax Rate:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.bazaar_tax_percent, 0.01, "1%");
+            ui.selectable_value(&mut self.bazaar_tax_percent, 0.01125, "1.125%");
+            ui.selectable_value(&mut self.bazaar_tax_percent, 0.0125, "1.25%");
+        });
+        ui.end_row();
 
-                        a_shard.rarity.cmp(&b_shard.rarity)
-                            .then(a_shard.id.cmp(&b_shard.id))
-                            .then(a_shard_first_input.rarity.cmp(&b_shard_first_input.rarity).then(a_shard_first_input.id.cmp(&b_shard_first_input.id)))
-                            .then(a_shard_second_input.rarity.cmp(&b_shard_second_input.rarity).then(a_shard_second_input.id.cmp(&b_shard_second_input.id)))
-                    }
-                    AllFusionsSortType::Profit => {
-                        let a_profit = a_data.get_result_profit(a_shard_name, self.buy_type, self.profit_type, self.pure_reptile_attribute_level, self.bazaar_tax_percent, &self.shards);
-                        let b_profit = b_data.get_result_profit(b_shard_name, self.buy_type, self.profit_type, self.pure_reptile_attribute_level, self.bazaar_tax_percent, &self.shards);
-                        b_profit.total_cmp(&a_profit)
-                    }
-                }
-            });
+        ui.label("Reset Rates:");
+        if ui.button("Use Defaults").clicked() {
+            self.farming_rates.clear();
         }
-    }
+        ui.end_row();
 
-    fn generate_sorting_recalculation_hash(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        self.sort_type.hash(&mut hasher);
-        self.buy_type.hash(&mut hasher);
-        self.profit_type.hash(&mut hasher);
-        self.bazaar_tax_percent.to_string().hash(&mut hasher);
-        self.pure_reptile_attribute_level.hash(&mut hasher);
-        hasher.finish()
+        ui.label(""); // to force button into row two
+        bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
     }
-}
-
-fn add_shard_option(ui: &mut Ui, id: &str, selected_shard_name: &mut Option<String>, shards: &Shards, search_query: &str, images: &Rc<HashMap<String, TextureHandle>>) {
-    let selected_text = selected_shard_name
-        .as_ref()
-        .map(|shard_name| {
-            let shard = shards.get(shard_name).unwrap();
-            create_shard_text_layout_job(shard)
-        });
 
-    let mut shards: Vec<(&String, &ShardData)> = shards.iter().collect();
-    shards.sort_by(|(_, a), (_, b)| {
-        a.rarity.cmp(&b.rarity).then(a.id.cmp(&b.id))
-    });
+    fn add_farming_profits(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
 
-    ui.horizontal(|ui| {
-        let mut combo_box = egui::ComboBox::from_id_salt(id).height(400.0);
-        if let Some(selected_text) = selected_text {
-            combo_box = combo_box.selected_text(selected_text);
-        } else {
-            combo_box = combo_box.selected_text("None");
+        if !self.bazaar.borrow().has_data() {
+            ui.label("Fetching Bazaar Data...");
+            return;
         }
 
-        combo_box.show_ui(ui, |ui| {
-            if ui.selectable_label(selected_shard_name.is_none(), "None").clicked() {
-                *selected_shard_name = None;
+        let hash = self.generate_farming_profits_hash();
+        if self.farming_profits.is_none() || self.farming_profits_hash != Some(hash) {
+            let mut farming_profits = calculate_farming_profits(&self.shards, &self.farming_rates, self.profit_type, self.bazaar_tax_percent);
+            // same as the trap locations, re-ranking mid-edit would move the edited rows out from under the widgets
+            if let (true, Some(previous_profits)) = (self.farming_rates_editing, &self.farming_profits) {
+                keep_row_order(&mut farming_profits, previous_profits, |profit| (profit.key.clone(), profit.shard_name.clone()));
             }
+            self.farming_profits = Some(farming_profits);
+            self.farming_profits_hash = Some(hash);
+        }
 
-            for (_, shard) in shards.iter() {
-                let shard_label = shard.format();
-                if !shard_label.to_lowercase().contains(search_query.to_lowercase().as_str()) {
-                    continue;
-                }
+        let search_query = self.search_query.to_lowercase();
+        let farming_profits = self.farming_profits
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|profit| self.farming_method_filter.map_or(true, |method| method == profit.key.method))
+            .filter(|profit| {
+                search_query.is_empty()
+                    || profit.shard_name.to_lowercase().contains(&search_query)
+                    || profit.key.location.to_lowercase().contains(&search_query)
+            })
+            .collect::<Vec<&FarmingProfit>>();
+        let mut changed_rate = None;
+        let mut editing = false;
+
+        ui.label(RichText::new("Rates are shared by every shard farmed with the same method and location. Shards are only ranked once you enter a rate for them.")
+            .color(Color32::GRAY));
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 7)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Shard");
+                });
+                header.col(|ui| {
+                    ui.strong("Method");
+                });
+                header.col(|ui| {
+                    ui.strong("Location");
+                });
+                header.col(|ui| {
+                    ui.strong("Kills/Catches Per Hour");
+                });
+                header.col(|ui| {
+                    ui.strong("Shards Each");
+                });
+                header.col(|ui| {
+                    ui.strong("Shards/Hour");
+                });
+                header.col(|ui| {
+                    ui.strong("Shard Value");
+                });
+                header.col(|ui| {
+                    ui.strong("Coins/Hour");
+                });
+            })
+            .body(|body| {
+                body.rows(18.0, farming_profits.len(), |mut row| {
+                    let farming_profit = farming_profits.get(row.index()).unwrap();
+                    let shard = self.shards.get(&farming_profit.shard_name).unwrap();
+                    let mut rate = self.farming_rates.get(&farming_profit.key).copied().unwrap_or_default();
+
+                    row.col(|ui| {
+                        add_shard_text(ui, shard, &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        ui.label(farming_profit.key.method.get_label_name());
+                    });
+                    row.col(|ui| {
+                        ui.label(&farming_profit.key.location);
+                    });
+                    // rates are shared between shards, so the key alone isn't unique
+                    let row_id = (&farming_profit.key, &farming_profit.shard_name);
+                    row.col(|ui| {
+                        ui.push_id(row_id, |ui| {
+                            let suffix = format!(" {}", farming_profit.key.method.get_action_name().to_lowercase());
+                            let response = ui.add(DragValue::new(&mut rate.actions_per_hour).range(0.0..=100_000.0).suffix(suffix));
+                            editing |= response.dragged() || response.has_focus();
+                            if response.changed() {
+                                changed_rate = Some((farming_profit.key.clone(), rate));
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.push_id(row_id, |ui| {
+                            let response = ui.add(DragValue::new(&mut rate.shards_per_action).range(0.0..=100.0).speed(0.01));
+                            editing |= response.dragged() || response.has_focus();
+                            if response.changed() {
+                                changed_rate = Some((farming_profit.key.clone(), rate));
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        match farming_profit.shards_per_hour {
+                            Some(shards_per_hour) => ui.label(format!("{shards_per_hour:.1}")),
+                            None => ui.label("-"),
+                        };
+                    });
+                    row.col(|ui| {
+                        match farming_profit.shard_value {
+                            Some(value) => ui.label(RichText::new((value as i64).to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0))),
+                            None => ui.label("-"),
+                        };
+                    });
+                    row.col(|ui| {
+                        match farming_profit.coins_per_hour {
+                            Some(coins_per_hour) => {
+                                let coins_per_hour = coins_per_hour as i64;
+                                ui.label(RichText::new(coins_per_hour.to_formatted_string(&en)).color(get_profit_color(coins_per_hour)))
+                            }
+                            None => ui.label(RichText::new("Enter a rate").color(Color32::GRAY)),
+                        };
+                    });
+                });
+            });
+
+        if let Some((key, rate)) = changed_rate {
+            self.farming_rates.insert(key, rate);
+        }
+        if self.farming_rates_editing && !editing {
+            // done editing, so rank the rows again
+            self.farming_profits = None;
+            ui.ctx().request_repaint();
+        }
+        self.farming_rates_editing = editing;
+    }
+
+    fn add_fusion_partners(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
+
+        if self.partners_shard_name.is_none() {
+            ui.label("Select a shard to see what it can be fused with.");
+            return;
+        }
+        let shard_name = self.partners_shard_name.as_ref().unwrap();
+
+        if self.all_combinations_by_input_shards.is_none() {
+            self.all_combinations_by_input_shards = Some(generate_all_possible_combinations_per_input_shard(&self.shards));
+        }
+
+        let mut partners = self.all_combinations_by_input_shards
+            .as_ref()
+            .unwrap()
+            .get(shard_name)
+            .unwrap()
+            .iter()
+            .filter(|results| !self.only_held_partners || self.inventory.contains(&results.second_input_shard_name))
+            .map(|results| (results, self.get_best_output_profit(results)))
+            .collect::<Vec<(&FusionResults, Option<(&String, f64)>)>>();
+
+        sort_fusion_partners(&mut partners, self.partners_sort_type, &self.shards);
+
+        if partners.is_empty() {
+            ui.label("No fusions found :(");
+            return;
+        }
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 6)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Partner");
+                });
+                header.col(|ui| {
+                    ui.strong("Total Cost");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #1");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #2");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #3");
+                });
+                header.col(|ui| {
+                    ui.strong("Best Output");
+                });
+                header.col(|ui| {
+                    ui.strong("Best Profit");
+                });
+            })
+            .body(|body| {
+                body.rows(16.0, partners.len(), |mut row| {
+                    let index = row.index();
+                    let (combination, best_profit) = partners.get(index).unwrap();
+                    let partner_shard = self.shards.get(&combination.second_input_shard_name).unwrap();
+
+                    row.col(|ui| {
+                        self.add_numbered_shard_text(ui, partner_shard, ConsumedInFusion, combination);
+                        let held_count = self.inventory.get_count(&partner_shard.shard_name);
+                        if held_count > 0 {
+                            ui.label(RichText::new(format!("({held_count} held)")).color(Color32::from_rgb(85, 255, 85)));
+                        }
+                    });
+                    row.col(|ui| {
+                        match combination.get_total_cost(self.buy_type, &self.shards) {
+                            Ok(cost) => ui.label(RichText::new(cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0))),
+                            Err(_) => ui.label(RichText::new("-").color(Color32::GRAY)),
+                        };
+                    });
+                    for i in 0..3 {
+                        row.col(|ui| {
+                            if let Some(output) = combination.listed_fusions.get(i) {
+                                self.add_numbered_shard_text(ui, self.shards.get(output).unwrap(), MadeInFusion, combination);
+                            }
+                        });
+                    }
+                    match best_profit {
+                        Some((output, profit)) => {
+                            let profit = *profit as i64;
+                            row.col(|ui| {
+                                add_shard_text(ui, self.shards.get(*output).unwrap(), &self.images, self.attribute_levels.max_level);
+                            });
+                            row.col(|ui| {
+                                ui.label(RichText::new(profit.to_formatted_string(&en)).color(get_profit_color(profit)));
+                            });
+                        }
+                        None => {
+                            row.col(|ui| {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            });
+                            row.col(|ui| {
+                                ui.label(RichText::new("-").color(Color32::GRAY));
+                            });
+                        }
+                    }
+                });
+            });
+    }
+
+    /// The most profitable output of a fusion, or None if the prices of the inputs or outputs aren't known yet.
+    fn get_best_output_profit<'a>(&self, combination: &'a FusionResults) -> Option<(&'a String, f64)> {
+        if !combination.has_both_input_costs_above_zero(self.buy_type, &self.shards) {
+            return None;
+        }
+
+        combination.listed_fusions
+            .iter()
+            .filter(|output| self.shards.get(*output).unwrap().cached_bazaar_data.is_some())
+            .map(|output| {
+                let profit = combination.get_result_profit(output, self.buy_type, self.profit_type, self.pure_reptile_attribute_level, self.bazaar_tax_percent, &self.shards);
+                (output, profit)
+            })
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    fn add_inventory_options(&mut self, ctx: &Context, ui: &mut Ui, system_time: Instant) {
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "oak_sign.png");
+            ui.label("Search Shards:");
+        });
+        ui.text_edit_singleline(&mut self.search_query);
+        ui.end_row();
+
+        ui.label("Add Shard:");
+        ui.horizontal(|ui| {
+            add_shard_option(ui, "inventory_shard_to_add", &mut self.inventory_shard_to_add, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
+            ui.add(DragValue::new(&mut self.inventory_amount_to_add).range(1..=100_000).suffix("x"));
+            if let Some(shard_name) = &self.inventory_shard_to_add {
+                if ui.button("Add").clicked() {
+                    let count = self.inventory.get_count(shard_name).saturating_add(self.inventory_amount_to_add);
+                    self.inventory.set_count(shard_name.clone(), count);
+                    self.inventory_shard_to_add = None;
+                }
+            }
+        });
+        ui.end_row();
+
+        ui.label("Import:")
+            .on_hover_text("One shard per line, like \"Sea Serpent,12\", \"Sea Serpent: 12\" or \"12x Sea Serpent\"");
+        ui.add(TextEdit::multiline(&mut self.inventory_import_text).desired_rows(3).hint_text("Paste text or CSV here"));
+        ui.end_row();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            ui.label("Import File:");
+            ui.text_edit_singleline(&mut self.inventory_import_path);
+            ui.end_row();
+        }
+
+        ui.label("");
+        ui.horizontal(|ui| {
+            if ui.button("Replace Inventory").clicked() {
+                self.import_inventory(true);
+            }
+            if ui.button("Add To Inventory").clicked() {
+                self.import_inventory(false);
+            }
+            if !self.inventory.is_empty() && ui.button("Clear Inventory").clicked() {
+                self.inventory.clear();
+            }
+        });
+        ui.end_row();
+
+        for message in self.inventory_import_messages.iter() {
+            ui.label("");
+            ui.label(RichText::new(message).color(Color32::GRAY));
+            ui.end_row();
+        }
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "golden_horse_armor.png");
+            ui.label("Buying Method:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.buy_type, InstaBuy, "Insta-Buy");
+            ui.selectable_value(&mut self.buy_type, BuyOrder, "Buy Order");
+        });
+        ui.end_row();
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "hopper.png");
+            ui.label("Selling Method:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.profit_type, InstaSell, "Insta-Sell");
+            ui.selectable_value(&mut self.profit_type, SellOffer, "Sell Offer");
+        });
+        ui.end_row();
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "attribute_pure_reptile.png");
+            ui.label("Pure Reptile Level:");
+        });
+        ui.add(Slider::new(&mut self.pure_reptile_attribute_level, 0..=10));
+        ui.end_row();
+
+        ui.label(""); // to force button into row two
+        bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
+    }
+
+    /// Imports the pasted text, or the file at the import path if one was entered (native only).
+    fn import_inventory(&mut self, replace: bool) {
+        let mut text = self.inventory_import_text.clone();
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.inventory_import_path.trim().is_empty() {
+            match std::fs::read_to_string(self.inventory_import_path.trim()) {
+                Ok(contents) => text = contents,
+                Err(e) => {
+                    self.inventory_import_messages = vec![format!("Failed to read file: {e}")];
+                    return;
+                }
+            }
+        }
+
+        let import = parse_inventory_text(&text, &self.shards);
+        if replace {
+            self.inventory.clear();
+        }
+        for (shard_name, count) in import.counts.iter() {
+            let count = self.inventory.get_count(shard_name).saturating_add(*count);
+            self.inventory.set_count(shard_name.clone(), count);
+        }
+
+        self.inventory_import_messages = vec![format!("Imported {} shard stack(s)", import.counts.len())];
+        self.inventory_import_messages.extend(import.errors);
+    }
+
+    fn add_inventory_suggestions(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
+
+        if self.inventory.is_empty() {
+            ui.label("Add some shards to your inventory to get suggestions.");
+            return;
+        }
+
+        let hash = self.generate_inventory_suggestions_hash();
+        if self.inventory_suggestions.is_none() || self.inventory_suggestions_hash != Some(hash) {
+            let prices = PriceSettings {
+                buy_type: self.buy_type,
+                profit_type: self.profit_type,
+                bazaar_tax_rate: self.bazaar_tax_percent,
+                pure_reptile_attribute_level: self.pure_reptile_attribute_level,
+            };
+            self.inventory_suggestions = Some(InventorySuggestions::generate(&self.inventory, &self.shards, &prices));
+            self.inventory_suggestions_hash = Some(hash);
+        }
+
+        match self.inventory_view {
+            InventoryView::Stacks => self.add_inventory_stacks(ui),
+            InventoryView::OwnedFusions => self.add_owned_fusions(ui),
+            InventoryView::Purchases => self.add_fusion_purchases(ui),
+        }
+    }
+
+    fn add_inventory_stacks(&mut self, ui: &mut Ui) {
+        let suggestions = self.inventory_suggestions.as_ref().unwrap();
+        let mut changed_count = None;
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 4)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Shard");
+                });
+                header.col(|ui| {
+                    ui.strong("Amount");
+                });
+                header.col(|ui| {
+                    ui.strong("Sell Value");
+                });
+                header.col(|ui| {
+                    ui.strong("Suggestion");
+                });
+                header.col(|ui| {
+                    ui.strong("Best Fusion");
+                });
+            })
+            .body(|body| {
+                body.rows(18.0, suggestions.stack_decisions.len(), |mut row| {
+                    let decision = suggestions.stack_decisions.get(row.index()).unwrap();
+                    let shard = self.shards.get(&decision.shard_name).unwrap();
+
+                    row.col(|ui| {
+                        add_shard_text(ui, shard, &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        let mut count = decision.count;
+                        if ui.add(DragValue::new(&mut count).range(0..=100_000)).changed() {
+                            changed_count = Some((decision.shard_name.clone(), count));
+                        }
+                    });
+                    row.col(|ui| {
+                        let value = decision.sell_value as i64;
+                        ui.label(RichText::new(value.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                    });
+                    row.col(|ui| {
+                        if decision.should_fuse() {
+                            ui.label(RichText::new("Fuse").color(Color32::from_rgb(85, 255, 85)));
+                        } else {
+                            ui.label(RichText::new("Sell").color(Color32::from_rgb(255, 255, 85)));
+                        }
+                    });
+                    row.col(|ui| {
+                        match &decision.best_fusion {
+                            Some(fusion) => {
+                                let partner = if fusion.first_input_shard_name == decision.shard_name {
+                                    &fusion.second_input_shard_name
+                                } else {
+                                    &fusion.first_input_shard_name
+                                };
+                                let gain = fusion.gain_per_fusion as i64;
+                                ui.label(format!("With {partner} into {}:", fusion.output_shard_name));
+                                ui.label(RichText::new(format!("{}{} per fusion", if gain >= 0 { "+" } else { "" }, gain.to_formatted_string(&en)))
+                                    .color(get_profit_color(gain)));
+                            }
+                            None => {
+                                ui.label(RichText::new("No fusions with owned shards").color(Color32::GRAY));
+                            }
+                        }
+                    });
+                });
+            });
+
+        if let Some((shard_name, count)) = changed_count {
+            self.inventory.set_count(shard_name, count);
+        }
+    }
+
+    fn add_owned_fusions(&self, ui: &mut Ui) {
+        let suggestions = self.inventory_suggestions.as_ref().unwrap();
+        if suggestions.owned_fusions.is_empty() {
+            ui.label("No fusions can be made from your inventory alone.");
+            return;
+        }
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 5)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Left Input");
+                });
+                header.col(|ui| {
+                    ui.strong("Right Input");
+                });
+                header.col(|ui| {
+                    ui.strong("Best Output");
+                });
+                header.col(|ui| {
+                    ui.strong("Times");
+                });
+                header.col(|ui| {
+                    ui.strong("Gain Per Fusion");
+                });
+                header.col(|ui| {
+                    ui.strong("Total Gain");
+                });
+            })
+            .body(|body| {
+                body.rows(16.0, suggestions.owned_fusions.len(), |mut row| {
+                    let fusion = suggestions.owned_fusions.get(row.index()).unwrap();
+                    let gain = fusion.gain_per_fusion as i64;
+
+                    row.col(|ui| {
+                        add_shard_text(ui, self.shards.get(&fusion.first_input_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        add_shard_text(ui, self.shards.get(&fusion.second_input_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        add_shard_text(ui, self.shards.get(&fusion.output_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{}x", fusion.times_possible));
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(gain.to_formatted_string(&en)).color(get_profit_color(gain)));
+                    });
+                    row.col(|ui| {
+                        let total_gain = gain * fusion.times_possible as i64;
+                        ui.label(RichText::new(total_gain.to_formatted_string(&en)).color(get_profit_color(total_gain)));
+                    });
+                });
+            });
+    }
+
+    fn add_fusion_purchases(&self, ui: &mut Ui) {
+        let suggestions = self.inventory_suggestions.as_ref().unwrap();
+        if suggestions.purchases.is_empty() {
+            ui.label("No fusions found that only need a few shards bought.");
+            return;
+        }
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 5)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Owned Shard");
+                });
+                header.col(|ui| {
+                    ui.strong("Buy");
+                });
+                header.col(|ui| {
+                    ui.strong("Cost");
+                });
+                header.col(|ui| {
+                    ui.strong("Best Output");
+                });
+                header.col(|ui| {
+                    ui.strong("Input Order");
+                });
+                header.col(|ui| {
+                    ui.strong("Profit");
+                });
+            })
+            .body(|body| {
+                body.rows(16.0, suggestions.purchases.len(), |mut row| {
+                    let purchase = suggestions.purchases.get(row.index()).unwrap();
+                    let profit = purchase.profit as i64;
+
+                    row.col(|ui| {
+                        add_shard_text(ui, self.shards.get(&purchase.owned_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{}x", purchase.missing_amount));
+                        add_shard_text(ui, self.shards.get(&purchase.missing_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        let cost = purchase.purchase_cost as i64;
+                        ui.label(RichText::new(cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                    });
+                    row.col(|ui| {
+                        add_shard_text(ui, self.shards.get(&purchase.output_shard_name).unwrap(), &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        ui.label(if purchase.owned_is_first_input { "Owned First" } else { "Bought First" });
+                    });
+                    row.col(|ui| {
+                        ui.label(RichText::new(profit.to_formatted_string(&en)).color(get_profit_color(profit)));
+                    });
+                });
+            });
+    }
+
+    fn generate_inventory_suggestions_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inventory.hash(&mut hasher);
+        self.buy_type.hash(&mut hasher);
+        self.profit_type.hash(&mut hasher);
+        self.bazaar_tax_percent.to_string().hash(&mut hasher);
+        self.pure_reptile_attribute_level.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn add_attribute_level_options(&mut self, ctx: &Context, ui: &mut Ui, system_time: Instant) {
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "oak_sign.png");
+            ui.label("Search Shards:");
+        });
+        ui.text_edit_singleline(&mut self.search_query);
+        ui.end_row();
+
+        ui.label("Shard:");
+        add_shard_option(ui, "attribute_shard", &mut self.attribute_shard_name, &self.shards, &self.search_query, &self.images, self.attribute_levels.max_level);
+        ui.end_row();
+
+        let max_level = self.attribute_levels.max_level;
+        ui.label("Current Level:");
+        ui.add(Slider::new(&mut self.attribute_current_level, 0..=max_level));
+        ui.end_row();
+
+        ui.label("Target Level:");
+        ui.add(Slider::new(&mut self.attribute_target_level, 0..=max_level));
+        ui.end_row();
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "golden_horse_armor.png");
+            ui.label("Buying Method:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.buy_type, InstaBuy, "Insta-Buy");
+            ui.selectable_value(&mut self.buy_type, BuyOrder, "Buy Order");
+        });
+        ui.end_row();
+
+        ui.label(""); // to force button into row two
+        bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
+    }
+
+    fn add_attribute_levels(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
+
+        ui.label(RichText::new(format!("Source: {}", self.attribute_levels.source)).color(Color32::GRAY));
+        if self.attribute_shard_name.is_none() {
+            ui.label("Select a shard to see its attribute at each level.");
+            return;
+        }
+        let shard = self.shards.get(self.attribute_shard_name.as_ref().unwrap()).unwrap();
+        let current_level = self.attribute_current_level;
+        let target_level = self.attribute_target_level;
+
+        ui.horizontal(|ui| {
+            add_shard_text(ui, shard, &self.images, self.attribute_levels.max_level);
+            ui.label(RichText::new(format!("({})", shard.attribute_name)).color(Color32::GRAY));
+        });
+        ui.horizontal(|ui| {
+            ui.label(format!("Level {target_level}:"));
+            ui.label(create_description_layout_job(shard, target_level, Color32::GRAY));
+        });
+
+        let price = shard.cached_bazaar_data.as_ref().map(|quick_status| quick_status.get_buy_price(self.buy_type));
+        let shards_needed = self.attribute_levels.get_shards_between_levels(&shard.rarity, current_level, target_level);
+        let shards_held = self.inventory.get_count(&shard.shard_name).min(shards_needed);
+        let shards_to_buy = shards_needed - shards_held;
+        ui.horizontal(|ui| {
+            ui.label(format!("Shards needed from level {current_level} to {target_level}:"));
+            ui.label(RichText::new(shards_needed.to_formatted_string(&en)).color(Color32::from_rgb(85, 255, 255)));
+            if shards_held > 0 {
+                ui.label(RichText::new(format!("({shards_held} held, {shards_to_buy} to buy)")).color(Color32::from_rgb(85, 255, 85)));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Cost:");
+            match price {
+                Some(price) => {
+                    let cost = (price * shards_to_buy as f64) as i64;
+                    ui.label(RichText::new(cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                }
+                None => {
+                    ui.label(RichText::new("No bazaar price").color(Color32::GRAY));
+                }
+            }
+        });
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        let max_level = self.attribute_levels.max_level;
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 4)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Level");
+                });
+                header.col(|ui| {
+                    ui.strong("Shards For Level");
+                });
+                header.col(|ui| {
+                    ui.strong("Total Shards");
+                });
+                header.col(|ui| {
+                    ui.strong("Total Cost");
+                });
+            })
+            .body(|body| {
+                body.rows(18.0, max_level as usize, |mut row| {
+                    let level = row.index() as u8 + 1;
+                    let in_range = level > current_level && level <= target_level;
+                    let level_color = if level <= current_level {
+                        Color32::GRAY
+                    } else if in_range {
+                        Color32::from_rgb(85, 255, 85)
+                    } else {
+                        Color32::WHITE
+                    };
+
+                    row.col(|ui| {
+                        ui.label(RichText::new(level.to_string()).color(level_color));
+                    });
+                    row.col(|ui| {
+                        ui.label(self.attribute_levels.get_shards_for_level(&shard.rarity, level).to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(self.attribute_levels.get_total_shards_for_level(&shard.rarity, level).to_string());
+                    });
+                    row.col(|ui| {
+                        let total_shards = self.attribute_levels.get_total_shards_for_level(&shard.rarity, level);
+                        match price {
+                            Some(price) => {
+                                let cost = (price * total_shards as f64) as i64;
+                                ui.label(RichText::new(cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                            }
+                            None => {
+                                ui.label("-");
+                            }
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(create_description_layout_job(shard, level, Color32::GRAY));
+                    });
+                });
+            });
+    }
+
+    fn add_source_explorer_options(&mut self, ctx: &Context, ui: &mut Ui, system_time: Instant) {
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "oak_sign.png");
+            ui.label("Search:");
+        });
+        ui.text_edit_singleline(&mut self.search_query)
+            .on_hover_text("Matches shard names and locations");
+        ui.end_row();
+
+        ui.label("Source:");
+        ui.horizontal_wrapped(|ui| {
+            ui.selectable_value(&mut self.source_type_filter, None, "All");
+            for source_type in SourceType::values() {
+                ui.selectable_value(&mut self.source_type_filter, Some(source_type), source_type.get_label_name());
+            }
+        });
+        ui.end_row();
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "golden_horse_armor.png");
+            ui.label("Buying Method:");
+        });
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.buy_type, InstaBuy, "Insta-Buy");
+            ui.selectable_value(&mut self.buy_type, BuyOrder, "Buy Order");
+        });
+        ui.end_row();
+
+        ui.label(""); // to force button into row two
+        bazaar_client::add_refresh_button(&self.bazaar, ctx, ui, system_time);
+    }
+
+    fn add_source_explorer(&mut self, ui: &mut Ui) {
+        self.bazaar.borrow_mut().fetch_if_missing(Instant::now());
+
+        if self.all_combinations_by_shards.is_none() {
+            self.all_combinations_by_shards = Some(generate_all_possible_combinations_per_shard(&self.shards));
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.buy_type.hash(&mut hasher);
+        let hash = hasher.finish();
+        if self.source_explorer_rows.is_none() || self.source_explorer_hash != Some(hash) {
+            let combinations = self.all_combinations_by_shards.as_ref().unwrap();
+            self.source_explorer_rows = Some(generate_source_explorer_rows(&self.shards, combinations, self.buy_type));
+            self.source_explorer_hash = Some(hash);
+        }
+
+        let search_query = self.search_query.to_lowercase();
+        let rows = self.source_explorer_rows
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|row| self.source_type_filter.map_or(true, |source_type| source_type == row.source_type))
+            .filter(|row| {
+                search_query.is_empty()
+                    || row.shard_name.to_lowercase().contains(&search_query)
+                    || row.location.to_lowercase().contains(&search_query)
+            })
+            .collect::<Vec<&SourceExplorerRow>>();
+
+        if rows.is_empty() {
+            ui.label("No shards match the search.");
+            return;
+        }
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 6)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Source");
+                });
+                header.col(|ui| {
+                    ui.strong("Location");
+                });
+                header.col(|ui| {
+                    ui.strong("Shard");
+                });
+                header.col(|ui| {
+                    ui.strong("Buy Price");
+                });
+                header.col(|ui| {
+                    ui.strong("Cheapest Cost");
+                });
+                header.col(|ui| {
+                    ui.strong("Cheapest Way");
+                });
+                header.col(|ui| {
+                    ui.strong("Trap");
+                });
+            })
+            .body(|body| {
+                body.rows(18.0, rows.len(), |mut row| {
+                    let index = row.index();
+                    let source_row = rows[index];
+                    let shard = self.shards.get(&source_row.shard_name).unwrap();
+                    // only label the first row of each group
+                    let starts_group = index == 0
+                        || rows[index - 1].source_type != source_row.source_type
+                        || rows[index - 1].location != source_row.location;
+
+                    row.col(|ui| {
+                        if starts_group {
+                            ui.label(RichText::new(source_row.source_type.get_label_name()).color(Color32::from_rgb(85, 255, 255)));
+                        }
+                    });
+                    row.col(|ui| {
+                        if starts_group {
+                            ui.label(&source_row.location);
+                        }
+                    });
+                    row.col(|ui| {
+                        add_shard_text(ui, shard, &self.images, self.attribute_levels.max_level);
+                    });
+                    row.col(|ui| {
+                        let buy_price = source_row.acquisition_paths.iter().find_map(|path| match path {
+                            AcquisitionPath::Buy { price } => Some(*price as i64),
+                            _ => None,
+                        });
+                        match buy_price {
+                            Some(price) => ui.label(RichText::new(price.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0))),
+                            None => ui.label("-"),
+                        };
+                    });
+                    row.col(|ui| {
+                        if let Some(path) = source_row.get_cheapest_path() {
+                            let cost = path.get_cost() as i64;
+                            ui.label(RichText::new(cost.to_formatted_string(&en)).color(Color32::from_rgb(255, 170, 0)));
+                        }
+                    });
+                    row.col(|ui| {
+                        match source_row.get_cheapest_path() {
+                            Some(path) => ui.label(path.get_label_name()),
+                            None => ui.label(RichText::new("No priced way to get this").color(Color32::GRAY)),
+                        };
+                    });
+                    row.col(|ui| {
+                        if source_row.trap_locations.is_empty() {
+                            ui.label("-");
+                        } else {
+                            ui.label(source_row.trap_locations.join(", "))
+                                .on_hover_text("Traps only cost time, so they aren't compared by price");
+                        }
+                    });
+                });
+            });
+    }
+
+    fn add_all_fusion_combinations(&mut self, ui: &mut Ui) {
+        if self.all_combinations.is_none() {
+            self.all_combinations = Some(generate_all_possible_combinations(&self.shards));
+        }
+        let all_combinations = self.all_combinations.as_ref().unwrap();
+
+        ui.end_row();
+        let available_height = ui.available_height();
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(false)
+            .cell_layout(Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 2)
+            .column(Column::initial(210.0))
+            .column(Column::auto())
+            .columns(Column::initial(190.0), 2)
+            .column(Column::remainder())
+            .drag_to_scroll(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(available_height)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Left Input");
+                });
+                header.col(|ui| {
+                    ui.strong("+");
+                });
+                header.col(|ui| {
+                    ui.strong("Right Input");
+                });
+                header.col(|ui| {
+                    ui.strong("=");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #1");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #2");
+                });
+                header.col(|ui| {
+                    ui.strong("Output #3");
+                });
+            })
+            .body(|body| {
+                body.rows(16.0, all_combinations.len(), |mut row| {
+                    let index = row.index();
+                    let combination = all_combinations.get(index).unwrap();
+
+                    let first_input_shard = self.shards.get(&combination.first_input_shard_name).unwrap();
+                    let second_input_shard = self.shards.get(&combination.second_input_shard_name).unwrap();
+
+                    row.col(|ui| {
+                        self.add_numbered_shard_text(ui, first_input_shard, ConsumedInFusion, combination);
+                    });
+                    row.col(|ui| {
+                        ui.label("+");
+                    });
+                    row.col(|ui| {
+                        self.add_numbered_shard_text(ui, second_input_shard, ConsumedInFusion, combination);
+                    });
+                    row.col(|ui| {
+                        ui.label("=");
+                    });
+
+                    for i in 0..3 {
+                        row.col(|ui| {
+                            if let Some(output) = combination.listed_fusions.get(i) {
+                                self.add_numbered_shard_text(ui, self.shards.get(output).unwrap(), MadeInFusion, combination);
+                            }
+                        });
+                    }
+                });
+            });
+    }
+
+    pub fn cache_bazaar_prices(&mut self) {
+        let bazaar = self.bazaar.borrow();
+        self.cached_bazaar_data_version = bazaar.get_data_version();
+        if let Some(bazaar_data) = bazaar.get_data() {
+            for shard_data in self.shards.values_mut() {
+                let possible_data = bazaar_data.get(&shard_data.get_bazaar_id());
+                if possible_data.is_none() {
+                    eprintln!("Couldn't fetch data for shard {} ({}) ({})", shard_data.shard_name, shard_data.attribute_name, shard_data.get_bazaar_id());
+                }
+                let quick_status = &possible_data.unwrap().quick_status;
+                shard_data.cached_bazaar_data = Some(quick_status.clone());
+            }
+            // prices changed, so these need to be regenerated
+            self.inventory_suggestions = None;
+            self.source_explorer_rows = None;
+            self.trap_profits = None;
+            self.farming_profits = None;
+        }
+    }
+
+    fn add_numbered_shard_text(
+        &self,
+        ui: &mut Ui,
+        shard: &ShardData,
+        amount_type: AmountType,
+        combination: &FusionResults,
+    ) {
+        let amount = match amount_type {
+            ConsumedInFusion => shard.get_amount_consumed_in_fusion(),
+            MadeInFusion => if combination.was_chameleon {
+                1
+            } else {
+                shard.get_default_amount_made_in_fusion()
+            },
+        };
+        ui.horizontal(|ui| {
+            if amount_type == MadeInFusion && combination.is_reptile_fusion && self.pure_reptile_attribute_level > 0 {
+                let higher_amount = amount * 2;
+                ui.label(format!("{amount}-{higher_amount}x"));
+            } else {
+                ui.label(format!("{amount}x"));
+            }
+            add_shard_image(ui, shard, &self.images);
+            add_shard_label(ui, shard, self.attribute_levels.max_level);
+            ui.label(format!("{}{}", shard.rarity.get_char(), shard.id))
+        });
+    }
+
+    fn add_shard_cost(&self, ui: &mut Ui, shard: &ShardData) {
+        let amount = shard.get_amount_consumed_in_fusion();
+        if let Some(quick_status) = shard.cached_bazaar_data.as_ref() {
+            let amount_string = ((quick_status.get_buy_price(self.buy_type) * amount as f64) as i64).to_formatted_string(&en);
+            ui.label(RichText::new(amount_string).color(Color32::from_rgb(255, 170, 0)));
+        }
+    }
+
+    fn sort_all_shards_if_necessary(&mut self, sort_type: AllFusionsSortType) {
+        let new_hash = self.generate_sorting_recalculation_hash();
+        if self.profit_data_hash.is_some_and(|h| h != new_hash) {
+            return;
+        }
+
+        if let Some(profit_sorted_combinations) = self.combination_profit_data.as_mut() {
+            profit_sorted_combinations.sort_by(|(a_shard_name, a_data), (b_shard_name, b_data)| {
+                match sort_type {
+                    AllFusionsSortType::ShardName => a_shard_name.cmp(b_shard_name)
+                        .then(a_data.first_input_shard_name.cmp(&b_data.first_input_shard_name))
+                        .then(a_data.second_input_shard_name.cmp(&b_data.second_input_shard_name)),
+                    AllFusionsSortType::ShardIdAndRarity => {
+                        let a_shard = &self.shards.get(a_shard_name).unwrap();
+                        let a_shard_first_input = &self.shards.get(&a_data.first_input_shard_name).unwrap();
+                        let a_shard_second_input = &self.shards.get(&a_data.second_input_shard_name).unwrap();
+                        let b_shard = &self.shards.get(b_shard_name).unwrap();
+                        let b_shard_first_input = &self.shards.get(&b_data.first_input_shard_name).unwrap();
+                        let b_shard_second_input = &self.shards.get(&b_data.second_input_shard_name).unwrap();
+
+                        a_shard.rarity.cmp(&b_shard.rarity)
+                            .then(a_shard.id.cmp(&b_shard.id))
+                            .then(a_shard_first_input.rarity.cmp(&b_shard_first_input.rarity).then(a_shard_first_input.id.cmp(&b_shard_first_input.id)))
+                            .then(a_shard_second_input.rarity.cmp(&b_shard_second_input.rarity).then(a_shard_second_input.id.cmp(&b_shard_second_input.id)))
+                    }
+                    AllFusionsSortType::Profit => {
+                        let a_profit = a_data.get_result_profit(a_shard_name, self.buy_type, self.profit_type, self.pure_reptile_attribute_level, self.bazaar_tax_percent, &self.shards);
+                        let b_profit = b_data.get_result_profit(b_shard_name, self.buy_type, self.profit_type, self.pure_reptile_attribute_level, self.bazaar_tax_percent, &self.shards);
+                        b_profit.total_cmp(&a_profit)
+                    }
+                }
+            });
+        }
+    }
+
+    fn generate_trap_profits_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.profit_type.hash(&mut hasher);
+        self.bazaar_tax_percent.to_string().hash(&mut hasher);
+        let mut settings = self.trap_settings.iter().collect::<Vec<_>>();
+        settings.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (key, settings) in settings {
+            key.hash(&mut hasher);
+            settings.trap_count.hash(&mut hasher);
+            settings.catches_per_trap_per_hour.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn generate_farming_profits_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.profit_type.hash(&mut hasher);
+        self.bazaar_tax_percent.to_string().hash(&mut hasher);
+        let mut rates = self.farming_rates.iter().collect::<Vec<_>>();
+        rates.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (key, rate) in rates {
+            key.hash(&mut hasher);
+            rate.actions_per_hour.to_bits().hash(&mut hasher);
+            rate.shards_per_action.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn generate_sorting_recalculation_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.sort_type.hash(&mut hasher);
+        self.buy_type.hash(&mut hasher);
+        self.profit_type.hash(&mut hasher);
+        self.bazaar_tax_percent.to_string().hash(&mut hasher);
+        self.pure_reptile_attribute_level.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+fn add_shard_option(ui: &mut Ui, id: &str, selected_shard_name: &mut Option<String>, shards: &Shards, search_query: &str, images: &Rc<HashMap<String, TextureHandle>>, max_level: u8) {
+    let selected_text = selected_shard_name
+        .as_ref()
+        .map(|shard_name| {
+            let shard = shards.get(shard_name).unwrap();
+            create_shard_text_layout_job(shard)
+        });
+
+    let mut shards: Vec<(&String, &ShardData)> = shards.iter().collect();
+    shards.sort_by(|(_, a), (_, b)| {
+        a.rarity.cmp(&b.rarity).then(a.id.cmp(&b.id))
+    });
+
+    ui.horizontal(|ui| {
+        let mut combo_box = egui::ComboBox::from_id_salt(id).height(400.0);
+        if let Some(selected_text) = selected_text {
+            combo_box = combo_box.selected_text(selected_text);
+        } else {
+            combo_box = combo_box.selected_text("None");
+        }
+
+        combo_box.show_ui(ui, |ui| {
+            if ui.selectable_label(selected_shard_name.is_none(), "None").clicked() {
+                *selected_shard_name = None;
+            }
+
+            for (_, shard) in shards.iter() {
+                let shard_label = shard.format();
+                if !shard_label.to_lowercase().contains(search_query.to_lowercase().as_str()) {
+                    continue;
+                }
 
                 ui.horizontal(|ui| {
                     add_shard_image(ui, shard, images);
                     let text = create_shard_text_layout_job(shard);
                     let clicked = selected_shard_name.as_ref().is_some_and(|selected_shard_name| selected_shard_name == &shard.shard_name);
-                    if ui.selectable_label(clicked, text).clicked() {
+                    if ui.selectable_label(clicked, text).on_hover_ui(|ui| add_shard_tooltip(ui, shard, max_level)).clicked() {
                         *selected_shard_name = Some(shard.shard_name.clone());
                     }
                 });
@@ -782,17 +2104,58 @@ fn create_shard_text_layout_job(shard: &ShardData) -> LayoutJob {
     job
 }
 
-fn add_shard_text(ui: &mut Ui, shard: &ShardData, images: &Rc<HashMap<String, TextureHandle>>) {
+fn add_shard_text(ui: &mut Ui, shard: &ShardData, images: &Rc<HashMap<String, TextureHandle>>, max_level: u8) {
     ui.horizontal(|ui| {
         add_shard_image(ui, shard, images);
-        ui.label(RichText::new(shard.shard_name.clone()).color(shard.rarity.get_color()));
+        add_shard_label(ui, shard, max_level);
+    });
+}
+
+fn add_shard_label(ui: &mut Ui, shard: &ShardData, max_level: u8) {
+    ui.label(RichText::new(shard.shard_name.clone()).color(shard.rarity.get_color()))
+        .on_hover_ui(|ui| add_shard_tooltip(ui, shard, max_level));
+}
+
+/// The description is shown at level 1 and the attribute's max level.
+fn add_shard_tooltip(ui: &mut Ui, shard: &ShardData, max_level: u8) {
+    ui.label(RichText::new(&shard.attribute_name).color(shard.rarity.get_color()).strong());
+    ui.horizontal(|ui| {
+        ui.label("Level 1:");
+        ui.label(create_description_layout_job(shard, 1, Color32::GRAY));
     });
+    ui.horizontal(|ui| {
+        ui.label(format!("Level {max_level}:"));
+        ui.label(create_description_layout_job(shard, max_level, Color32::GRAY));
+    });
+    ui.separator();
+
+    if let Some(families) = &shard.families {
+        ui.label(format!("Family: {}", families.join(", ")));
+    }
+    ui.label(format!("Skill: {:?}", shard.skill));
+    ui.label(format!("Category: {:?}", shard.category));
+
+    let sources = shard.sources.describe();
+    if !sources.is_empty() {
+        ui.label("Sources:");
+        for source in sources {
+            ui.label(RichText::new(format!("- {source}")).color(Color32::GRAY));
+        }
+    }
 }
 
 fn add_shard_image(ui: &mut Ui, shard: &ShardData, images: &Rc<HashMap<String, TextureHandle>>) {
-    let id = shard.id_override.clone().unwrap_or_else(|| shard.attribute_name.to_lowercase().replace("'", "").replace(" ", "_"));
-    let image_name = format!("attribute_{id}.png");
-    images::add_first_valid_image(images, ui, vec![image_name]);
+    images::add_first_valid_image(images, ui, vec![shard.get_image_file_name()]);
+}
+
+/// Puts the rows back in the order they had before, by their key. Rows that weren't there before go last.
+fn keep_row_order<T, K: Hash + Eq>(rows: &mut [T], previous_rows: &[T], get_key: impl Fn(&T) -> K) {
+    let previous_positions = previous_rows
+        .iter()
+        .enumerate()
+        .map(|(position, row)| (get_key(row), position))
+        .collect::<HashMap<K, usize>>();
+    rows.sort_by_cached_key(|row| previous_positions.get(&get_key(row)).copied().unwrap_or(usize::MAX));
 }
 
 fn get_profit_color(profit: i64) -> Color32 {
@@ -0,0 +1,160 @@
+use crate::bazaar::bazaar_data::ProfitType;
+use crate::shards::shard_data::Shards;
+use std::collections::{BTreeMap, HashMap};
+
+/// A placeholder of one catch every ten minutes, not a measured rate. Catch rates depend on the trap and the player's
+/// stats, so locations only get a real ranking once the user enters their own rate.
+pub const DEFAULT_CATCHES_PER_TRAP_PER_HOUR: f64 = 6.0;
+
+#[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TrapLocationKey {
+    pub location: String,
+    pub placement_type: Option<String>,
+}
+
+impl TrapLocationKey {
+    pub fn get_label_name(&self) -> String {
+        match &self.placement_type {
+            Some(placement_type) => format!("{} ({placement_type})", self.location),
+            None => self.location.clone(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TrapSettings {
+    pub trap_count: u32,
+    pub catches_per_trap_per_hour: f64,
+}
+
+impl Default for TrapSettings {
+    fn default() -> Self {
+        Self {
+            trap_count: 1,
+            catches_per_trap_per_hour: DEFAULT_CATCHES_PER_TRAP_PER_HOUR,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TrapLocationProfit {
+    pub key: TrapLocationKey,
+    pub shard_names: Vec<String>,
+    /// The average after tax sell price of the shards that can be caught there, or None if none of them have a price.
+    pub average_shard_value: Option<f64>,
+    pub catches_per_hour: f64,
+    pub coins_per_hour: f64,
+    /// True if the user hasn't entered settings for the location, so the catch rate is
+    /// [`DEFAULT_CATCHES_PER_TRAP_PER_HOUR`].
+    pub uses_placeholder_rate: bool,
+}
+
+/// Every shard that can be trapped, grouped by where the trap has to be placed. Shards listed with extra conditions
+/// (like only at night) are still grouped by their location and placement type.
+pub fn group_trap_shards(shards: &Shards) -> BTreeMap<TrapLocationKey, Vec<String>> {
+    let mut groups: BTreeMap<TrapLocationKey, Vec<String>> = BTreeMap::new();
+    for shard in shards.values() {
+        for trap in shard.sources.traps.iter().flatten() {
+            let key = TrapLocationKey {
+                location: trap.location.clone(),
+                placement_type: trap.placement_type.clone(),
+            };
+            groups.entry(key).or_default().push(shard.shard_name.clone());
+        }
+    }
+
+    for shard_names in groups.values_mut() {
+        shard_names.sort_by(|a, b| {
+            let a_shard = shards.get(a).unwrap();
+            let b_shard = shards.get(b).unwrap();
+            a_shard.rarity.cmp(&b_shard.rarity).then(a_shard.id.cmp(&b_shard.id))
+        });
+    }
+
+    groups
+}
+
+/// Coins per hour for every trap location, sorted from the most to the least profitable. Each catch is assumed to be
+/// equally likely to be any of the shards at the location, since the real odds aren't known. Locations without
+/// settings use [`TrapSettings::default`] and are sorted after the ones with settings, as their placeholder rate says
+/// nothing about how they compare.
+pub fn calculate_trap_location_profits(
+    shards: &Shards,
+    settings: &HashMap<TrapLocationKey, TrapSettings>,
+    profit_type: ProfitType,
+    bazaar_tax_rate: f64,
+) -> Vec<TrapLocationProfit> {
+    let mut profits = group_trap_shards(shards)
+        .into_iter()
+        .map(|(key, shard_names)| {
+            let uses_placeholder_rate = !settings.contains_key(&key);
+            let location_settings = settings.get(&key).copied().unwrap_or_default();
+            let sell_prices = shard_names
+                .iter()
+                .filter_map(|name| shards.get(name).unwrap().cached_bazaar_data.as_ref())
+                .map(|quick_status| quick_status.get_sell_price(profit_type) * (1.0 - bazaar_tax_rate))
+                .collect::<Vec<f64>>();
+            let average_shard_value = if sell_prices.is_empty() {
+                None
+            } else {
+                Some(sell_prices.iter().sum::<f64>() / sell_prices.len() as f64)
+            };
+
+            let catches_per_hour = location_settings.trap_count as f64 * location_settings.catches_per_trap_per_hour;
+            TrapLocationProfit {
+                key,
+                shard_names,
+                average_shard_value,
+                catches_per_hour,
+                coins_per_hour: catches_per_hour * average_shard_value.unwrap_or(0.0),
+                uses_placeholder_rate,
+            }
+        })
+        .collect::<Vec<TrapLocationProfit>>();
+
+    profits.sort_by(|a, b| {
+        a.uses_placeholder_rate.cmp(&b.uses_placeholder_rate)
+            .then(b.coins_per_hour.total_cmp(&a.coins_per_hour))
+            .then(a.key.cmp(&b.key))
+    });
+    profits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_trap_location_profits, group_trap_shards, TrapLocationKey, TrapSettings, DEFAULT_CATCHES_PER_TRAP_PER_HOUR};
+    use crate::bazaar::bazaar_data::ProfitType;
+    use crate::shards::shard_data::{read_all_shards, read_all_shards_priced_at};
+    use std::collections::HashMap;
+
+    #[test]
+    fn every_trapped_shard_is_grouped() {
+        let shards = read_all_shards();
+        let groups = group_trap_shards(&shards);
+        for shard in shards.values() {
+            for trap in shard.sources.traps.iter().flatten() {
+                let key = TrapLocationKey { location: trap.location.clone(), placement_type: trap.placement_type.clone() };
+                assert!(groups[&key].contains(&shard.shard_name), "{} isn't grouped at {}", shard.shard_name, key.get_label_name());
+            }
+        }
+    }
+
+    #[test]
+    fn placeholder_rates_are_flagged_and_sorted_last() {
+        let shards = read_all_shards_priced_at(1000.0);
+        let profits = calculate_trap_location_profits(&shards, &HashMap::new(), ProfitType::InstaSell, 0.0);
+        assert!(profits.iter().all(|profit| profit.uses_placeholder_rate));
+        assert!(profits.iter().all(|profit| profit.catches_per_hour == DEFAULT_CATCHES_PER_TRAP_PER_HOUR));
+
+        // the location the user entered a rate for comes first, even with a lower rate than the placeholder
+        let key = profits.last().unwrap().key.clone();
+        let settings = HashMap::from([(key.clone(), TrapSettings { trap_count: 2, catches_per_trap_per_hour: 1.5 })]);
+        let profits = calculate_trap_location_profits(&shards, &settings, ProfitType::InstaSell, 0.01);
+        let first = &profits[0];
+        assert_eq!(first.key, key);
+        assert!(!first.uses_placeholder_rate);
+        assert_eq!(first.catches_per_hour, 3.0);
+        assert!((first.coins_per_hour - 3.0 * 990.0).abs() < 1e-9);
+        assert!(profits[1..].iter().all(|profit| profit.uses_placeholder_rate));
+    }
+}
@@ -1,3 +1,6 @@
+pub mod magic_find;
 pub mod slayer_loot;
+pub mod slayer_levels;
 pub mod slayer_loot_calculator;
+pub mod slayer_loot_validator;
 pub mod slayer_page;
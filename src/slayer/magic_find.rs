@@ -0,0 +1,259 @@
+use crate::slayer::slayer_loot::LootTable;
+use crate::slayer::slayer_loot_calculator::{calculate_chances, RngMeterData};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const MAGIC_FIND_STORAGE_KEY: &str = "slayer_magic_find";
+/// Each source is capped at this as well, so the total can't go past it even if every source is at its cap.
+pub const MAX_MAGIC_FIND: f32 = 900.0;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
+pub enum MagicFindSource {
+    Pet,
+    Armor,
+    Accessories,
+    Bestiary,
+    Potions,
+    SlayerBoosts,
+    Other,
+}
+
+impl MagicFindSource {
+    pub const ALL: [MagicFindSource; 7] = [
+        MagicFindSource::Pet,
+        MagicFindSource::Armor,
+        MagicFindSource::Accessories,
+        MagicFindSource::Bestiary,
+        MagicFindSource::Potions,
+        MagicFindSource::SlayerBoosts,
+        MagicFindSource::Other,
+    ];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            MagicFindSource::Pet => "Pet",
+            MagicFindSource::Armor => "Armor",
+            MagicFindSource::Accessories => "Accessories",
+            MagicFindSource::Bestiary => "Bestiary",
+            MagicFindSource::Potions => "Potions",
+            MagicFindSource::SlayerBoosts => "Slayer Boosts",
+            MagicFindSource::Other => "Other",
+        }
+    }
+
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            MagicFindSource::Pet => "The pet's magic find, like from a Black Cat",
+            MagicFindSource::Armor => "Armor pieces, their reforges and enchantments",
+            MagicFindSource::Accessories => "Accessories, their reforges and the accessory bag power",
+            MagicFindSource::Bestiary => "The bestiary bonus of the boss's family",
+            MagicFindSource::Potions => "Magic Find potions and other consumables",
+            MagicFindSource::SlayerBoosts => "Boosts that only work on slayer bosses, like slayer level rewards",
+            MagicFindSource::Other => "Anything else, like the weapon or mayor perks",
+        }
+    }
+}
+
+// key: source, value: magic find from it
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct MagicFindBreakdown {
+    sources: BTreeMap<MagicFindSource, f32>,
+}
+
+impl MagicFindBreakdown {
+    pub fn get(&self, source: MagicFindSource) -> f32 {
+        self.sources.get(&source).copied().unwrap_or(0.0)
+    }
+
+    pub fn set(&mut self, source: MagicFindSource, magic_find: f32) {
+        if magic_find == 0.0 {
+            self.sources.remove(&source);
+        } else {
+            self.sources.insert(source, magic_find);
+        }
+    }
+
+    pub fn get_total(&self) -> f32 {
+        self.sources.values().sum::<f32>().clamp(0.0, MAX_MAGIC_FIND)
+    }
+
+    /// Only the sources that give any magic find.
+    pub fn iter(&self) -> impl Iterator<Item = (MagicFindSource, f32)> + '_ {
+        self.sources.iter().map(|(source, magic_find)| (*source, *magic_find))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct MagicFindPreset {
+    pub name: String,
+    pub breakdown: MagicFindBreakdown,
+}
+
+/// Everything about magic find that's kept between sessions.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct MagicFindSettings {
+    pub current: MagicFindBreakdown,
+    pub presets: Vec<MagicFindPreset>,
+}
+
+impl MagicFindSettings {
+    /// Saves the current breakdown, replacing any preset with the same name.
+    pub fn save_preset(&mut self, name: String) {
+        let preset = MagicFindPreset { name, breakdown: self.current.clone() };
+        match self.presets.iter_mut().find(|p| p.name == preset.name) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn load_preset(&mut self, name: &str) {
+        if let Some(preset) = self.presets.iter().find(|p| p.name == name) {
+            self.current = preset.breakdown.clone();
+        }
+    }
+
+    pub fn delete_preset(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+}
+
+pub struct MagicFindDropEffect {
+    /// Whether magic find applies to the drop at all, which is only the case for drops under 5% of their loot table's
+    /// weight.
+    pub affected: bool,
+    pub base_chance: f64,
+    /// The chance with only that source's magic find, for every source that gives any.
+    pub source_chances: Vec<(MagicFindSource, f64)>,
+    pub chance: f64,
+}
+
+/// How magic find, and each of its sources on their own, changes the chance of every drop of the table.
+// key: entry identifier
+pub fn calculate_magic_find_effects(
+    table: &LootTable,
+    breakdown: &MagicFindBreakdown,
+    slayer_level: u8,
+    rng_meter_data: &RngMeterData,
+) -> BTreeMap<String, MagicFindDropEffect> {
+    let get_chances = |magic_find: f32| {
+        calculate_chances(table, magic_find, slayer_level, rng_meter_data)
+            .into_iter()
+            .map(|entry| (entry.entry.to_string(), (entry.chance, entry.magic_find_multiplier > 1.0)))
+            .collect::<BTreeMap<String, (f64, bool)>>()
+    };
+
+    // any amount works to see which drops magic find applies to, the 5% cutoff uses the weights from before it applies
+    let affected = get_chances(100.0);
+    let base_chances = get_chances(0.0);
+    let total_chances = get_chances(breakdown.get_total());
+    let per_source_chances = breakdown
+        .iter()
+        .map(|(source, magic_find)| (source, get_chances(magic_find)))
+        .collect::<Vec<_>>();
+
+    base_chances
+        .into_iter()
+        .map(|(identifier, (base_chance, _))| {
+            let effect = MagicFindDropEffect {
+                affected: affected.get(&identifier).is_some_and(|(_, affected)| *affected),
+                base_chance,
+                source_chances: per_source_chances
+                    .iter()
+                    .map(|(source, chances)| (*source, chances.get(&identifier).map_or(base_chance, |(chance, _)| *chance)))
+                    .collect(),
+                chance: total_chances.get(&identifier).map_or(base_chance, |(chance, _)| *chance),
+            };
+            (identifier, effect)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_magic_find_effects, MagicFindBreakdown, MagicFindSettings, MagicFindSource, MAX_MAGIC_FIND};
+    use crate::slayer::slayer_loot::{read_all_loot, DropType};
+    use crate::slayer::slayer_loot_calculator::{calculate_chances, RngMeterData};
+
+    fn create_breakdown(magic_find_per_source: f32) -> MagicFindBreakdown {
+        let mut breakdown = MagicFindBreakdown::default();
+        for source in MagicFindSource::ALL {
+            breakdown.set(source, magic_find_per_source);
+        }
+        breakdown
+    }
+
+    #[test]
+    fn total_is_capped() {
+        assert_eq!(create_breakdown(100.0).get_total(), 700.0);
+        assert_eq!(create_breakdown(MAX_MAGIC_FIND).get_total(), MAX_MAGIC_FIND);
+    }
+
+    #[test]
+    fn saving_a_preset_replaces_the_one_with_the_same_name() {
+        let mut settings = MagicFindSettings::default();
+        settings.current.set(MagicFindSource::Pet, 20.0);
+        settings.save_preset("Slayer".to_string());
+        settings.save_preset("Other".to_string());
+
+        settings.current.set(MagicFindSource::Pet, 50.0);
+        settings.save_preset("Slayer".to_string());
+        assert_eq!(settings.presets.len(), 2);
+        assert_eq!(settings.presets[0].name, "Slayer");
+        assert_eq!(settings.presets[0].breakdown.get(MagicFindSource::Pet), 50.0);
+        assert_eq!(settings.presets[1].breakdown.get(MagicFindSource::Pet), 20.0);
+
+        settings.load_preset("Other");
+        assert_eq!(settings.current.get_total(), 20.0);
+    }
+
+    #[test]
+    fn only_drops_under_five_percent_are_affected() {
+        let mut breakdown = MagicFindBreakdown::default();
+        breakdown.set(MagicFindSource::Pet, 20.0);
+        breakdown.set(MagicFindSource::Armor, 55.5);
+        let rng_meter_data = RngMeterData::default();
+        let mut any_affected = false;
+
+        for table in read_all_loot(&crate::app::ASSETS_DIR).into_values().flatten() {
+            // tokens share the main drops' total weight, extra drops roll against the whole table
+            let get_total_weight = |drop_type: &DropType| {
+                table
+                    .loot
+                    .iter()
+                    .filter(|entry| drop_type == &DropType::Extra || entry.get_drop_type() != &DropType::Extra)
+                    .map(|entry| entry.get_weight())
+                    .sum::<f64>()
+            };
+            let get_chance = |magic_find: f32, identifier: &String| {
+                calculate_chances(&table, magic_find, 9, &rng_meter_data)
+                    .into_iter()
+                    .find(|e| &e.entry.to_string() == identifier)
+                    .unwrap()
+                    .chance
+            };
+            let effects = calculate_magic_find_effects(&table, &breakdown, 9, &rng_meter_data);
+
+            for entry in table.loot.iter() {
+                let identifier = entry.to_string();
+                // the effects are keyed by identifier, so entries sharing one can't be told apart
+                if table.loot.iter().filter(|other| other.to_string() == identifier).count() > 1 {
+                    continue;
+                }
+                let effect = &effects[&identifier];
+
+                let share = entry.get_weight() / get_total_weight(entry.get_drop_type());
+                let expected = table.rules.magic_find && entry.get_drop_type() != &DropType::Token && share < 0.05;
+                assert_eq!(effect.affected, expected, "{identifier} has {:.2}% of the weight", share * 100.0);
+                any_affected |= effect.affected;
+
+                let sources = effect.source_chances.iter().map(|(source, _)| *source).collect::<Vec<MagicFindSource>>();
+                assert_eq!(sources, vec![MagicFindSource::Pet, MagicFindSource::Armor]);
+                for (source, chance) in effect.source_chances.iter() {
+                    assert_eq!(*chance, get_chance(breakdown.get(*source), &identifier), "{identifier} with only {source:?}");
+                }
+                assert_eq!(effect.chance, get_chance(breakdown.get_total(), &identifier));
+            }
+        }
+        assert!(any_affected);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::slayer::slayer_loot::{LootEntry, LootTable};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+const SLAYER_LEVELS_JSON: &str = include_str!("../../assets/slayer_levels.json");
+
+pub const SLAYER_XP_STORAGE_KEY: &str = "slayer_xp";
+
+#[derive(Deserialize, Debug)]
+pub struct SlayerLevels {
+    /// Shown next to the planner, as some of the values are estimates.
+    pub note: String,
+    // key: boss type, like the loot folders
+    boss_types: BTreeMap<String, BossLevels>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BossLevels {
+    /// Total XP needed for each level, starting at level 1.
+    pub level_xp: Vec<u32>,
+    /// What the bosses are paid for with, if not coins.
+    #[serde(default = "default_currency")]
+    pub currency: String,
+    /// Starting at tier 1.
+    pub tiers: Vec<BossTier>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BossTier {
+    pub xp: u32,
+    pub cost: u64,
+}
+
+fn default_currency() -> String {
+    "Coins".to_string()
+}
+
+impl SlayerLevels {
+    pub fn get(&self, boss_type: &str) -> Option<&BossLevels> {
+        self.boss_types.get(boss_type)
+    }
+
+    pub fn get_boss_types(&self) -> impl Iterator<Item = &String> {
+        self.boss_types.keys()
+    }
+}
+
+impl BossLevels {
+    pub fn get_max_level(&self) -> u8 {
+        self.level_xp.len() as u8
+    }
+
+    pub fn get_level(&self, xp: u32) -> u8 {
+        self.level_xp.iter().take_while(|level_xp| xp >= **level_xp).count() as u8
+    }
+
+    pub fn get_xp_for_level(&self, level: u8) -> u32 {
+        if level == 0 {
+            return 0;
+        }
+        self.level_xp.get(level as usize - 1).copied().unwrap_or(u32::MAX)
+    }
+
+    /// How many bosses of each tier it takes to get from the XP to the level, if only that tier is killed. XP bonuses
+    /// (like from Aatrox) are a percentage on top of the base XP.
+    pub fn plan_leveling(&self, xp: u32, target_level: u8, xp_bonus_percent: f64) -> Vec<TierPlan> {
+        let needed_xp = self.get_xp_for_level(target_level).saturating_sub(xp);
+        self.tiers
+            .iter()
+            .enumerate()
+            .map(|(index, tier)| {
+                let xp_per_boss = tier.xp as f64 * (1.0 + xp_bonus_percent / 100.0);
+                let bosses = (needed_xp as f64 / xp_per_boss).ceil() as u64;
+                TierPlan {
+                    tier: index as u8 + 1,
+                    xp_per_boss,
+                    bosses,
+                    cost: bosses * tier.cost,
+                }
+            })
+            .collect()
+    }
+}
+
+pub struct TierPlan {
+    pub tier: u8,
+    pub xp_per_boss: f64,
+    pub bosses: u64,
+    pub cost: u64,
+}
+
+pub fn read_slayer_levels() -> SlayerLevels {
+    serde_json::from_str(SLAYER_LEVELS_JSON).expect("Failed to parse slayer level data")
+}
+
+/// The drops that need each slayer level from above the current one up to the target, with the lowest tier they drop
+/// from. Entries with the same name in several tiers are only listed once.
+// key: slayer level
+pub fn get_level_unlocks(loot_tables: &[Rc<LootTable>], current_level: u8, target_level: u8) -> BTreeMap<u8, Vec<(Rc<LootEntry>, u8)>> {
+    let mut unlocks: BTreeMap<u8, Vec<(Rc<LootEntry>, u8)>> = BTreeMap::new();
+    // auto-sorted from lowest to highest tier
+    for table in loot_tables {
+        for entry in table.loot.iter() {
+            let level = entry.get_slayer_level_requirement();
+            if level <= current_level || level > target_level {
+                continue;
+            }
+
+            let entries = unlocks.entry(level).or_default();
+            if !entries.iter().any(|(e, _)| e.to_string() == entry.to_string()) {
+                entries.push((Rc::clone(entry), table.boss_tier));
+            }
+        }
+    }
+    unlocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::read_slayer_levels;
+    use crate::slayer::slayer_loot::read_all_loot;
+
+    #[test]
+    fn every_boss_type_has_level_data() {
+        let levels = read_slayer_levels();
+        for (boss_type, tables) in read_all_loot(&crate::app::ASSETS_DIR) {
+            let boss_levels = levels.get(&boss_type).unwrap_or_else(|| panic!("no level data for {boss_type}"));
+            assert!(boss_levels.level_xp.is_sorted(), "{boss_type} level XP isn't increasing");
+            assert_eq!(boss_levels.tiers.len(), tables.len(), "{boss_type} tiers don't match its loot tables");
+        }
+    }
+
+    #[test]
+    fn leveling_plan_rounds_bosses_up() {
+        let levels = read_slayer_levels();
+        let zombie = levels.get("Zombie").unwrap();
+        assert_eq!(zombie.get_level(0), 0);
+        assert_eq!(zombie.get_level(15), 2);
+        assert_eq!(zombie.get_level(999_999), 8);
+
+        let plans = zombie.plan_leveling(200, 4, 0.0);
+        // 800 XP to go
+        assert_eq!(plans[0].bosses, 160);
+        assert_eq!(plans[3].bosses, 2);
+        assert_eq!(plans[4].bosses, 1);
+        assert_eq!(plans[4].cost, 100000);
+    }
+}
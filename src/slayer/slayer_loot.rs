@@ -1,3 +1,4 @@
+use crate::bazaar::bazaar_client::get_enchantment_id;
 use crate::slayer::slayer_loot_calculator::SelectedRngMeterItem;
 use convert_case::{Case, Casing};
 use include_dir::Dir;
@@ -11,6 +12,9 @@ pub struct LootTable {
     pub boss_type: String,
     pub boss_tier: u8,
     pub loot: Vec<Rc<LootEntry>>,
+    /// Only set for bosses whose drops don't follow the usual rules.
+    #[serde(default)]
+    pub rules: LootRules,
 
     #[serde(skip_serializing, skip_deserializing, default)]
     pub loot_strings: Vec<String>,
@@ -25,6 +29,51 @@ impl LootTable {
         self.loot_strings.contains(&entry.identifier)
     }
 }
+
+/// How a boss's drops differ from the usual slayer rules. Every tier of a boss should have the same rules.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Hash)]
+pub struct LootRules {
+    /// Whether magic find raises the chance of rare drops. It doesn't work in the Rift.
+    #[serde(default = "default_true")]
+    pub magic_find: bool,
+    /// Whether filling the RNG meter raises the selected drop's weight (up to 3x) before it's guaranteed, instead of
+    /// only guaranteeing it once the meter is full.
+    #[serde(default = "default_true")]
+    pub rng_meter_weight_scaling: bool,
+}
+
+impl Default for LootRules {
+    fn default() -> Self {
+        LootRules {
+            magic_find: true,
+            rng_meter_weight_scaling: true,
+        }
+    }
+}
+
+impl LootRules {
+    /// Shown with the options, to explain what's different. None if the usual rules apply.
+    pub fn describe(&self) -> Option<String> {
+        let mut sentences = Vec::new();
+        if !self.magic_find {
+            sentences.push("Magic find doesn't work for this boss.");
+        }
+        if !self.rng_meter_weight_scaling {
+            sentences.push("The RNG meter only guarantees the selected drop once it's full.");
+        }
+
+        if sentences.is_empty() {
+            None
+        } else {
+            Some(sentences.join(" "))
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
 #[derive(Debug, PartialEq, Clone, Default, Deserialize)]
 pub struct FilteredEntryData {
     pub entries: Vec<Rc<LootEntry>>,
@@ -96,6 +145,21 @@ impl LootEntry {
         )
     }
 
+    pub fn get_bazaar_id(&self) -> String {
+        match self {
+            LootEntry::Item { item, .. } => item.clone(),
+            LootEntry::Enchantment { enchantment, enchantment_level, .. } => get_enchantment_id(enchantment, *enchantment_level),
+        }
+    }
+
+    /// Books only go on the bazaar, everything else might be on the auction house.
+    pub fn get_auction_id(&self) -> Option<String> {
+        match self {
+            LootEntry::Item { item, .. } => Some(item.clone()),
+            LootEntry::Enchantment { .. } => None,
+        }
+    }
+
     pub fn get_possible_file_names(&self) -> Vec<String> {
         match self {
             LootEntry::Item { item, .. } => {
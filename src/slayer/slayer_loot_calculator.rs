@@ -1,4 +1,4 @@
-use crate::slayer::slayer_loot::{DropType, LootEntry, LootTable};
+use crate::slayer::slayer_loot::{DropType, LootEntry, LootRules, LootTable};
 use std::rc::Rc;
 
 #[derive(Clone)]
@@ -44,6 +44,7 @@ pub fn calculate_chances(
     rng_meter_data: &RngMeterData,
 ) -> Vec<LootChanceEntry> {
     let mut rng_meter_guaranteed_item_type: Option<DropType> = None;
+    let magic_find = if chest.rules.magic_find { magic_find } else { 0.0 };
 
     let mut entries = chest
         .loot
@@ -59,10 +60,11 @@ pub fn calculate_chances(
 
             if let Some(selected_item_data) = &rng_meter_data.selected_item {
                 if selected_item_data.identifier == entry.entry.to_string() {
-                    let multiplier = 1.0
-                        + (2.0 * rng_meter_data.selected_xp as f32
-                            / selected_item_data.required_xp as f32)
-                            .min(2.0) as f64;
+                    let multiplier = get_rng_meter_multiplier(
+                        &chest.rules,
+                        rng_meter_data.selected_xp,
+                        selected_item_data.required_xp,
+                    );
                     entry.used_weight *= multiplier;
 
                     if multiplier >= 3.0 {
@@ -87,6 +89,14 @@ pub fn calculate_chances(
     entries
 }
 
+/// How much the selected drop's weight is multiplied by, a multiplier of 3 or more means it's guaranteed.
+fn get_rng_meter_multiplier(rules: &LootRules, selected_xp: i32, required_xp: i32) -> f64 {
+    if !rules.rng_meter_weight_scaling {
+        return if selected_xp >= required_xp { 3.0 } else { 1.0 };
+    }
+    1.0 + (2.0 * selected_xp as f32 / required_xp as f32).min(2.0) as f64
+}
+
 fn process_main_entries(entries: &mut Vec<LootChanceEntry>, magic_find: f32) {
     let mut total_weight = get_total_token_and_main_weights(entries);
 
@@ -105,7 +115,9 @@ fn process_extra_entries(entries: &mut Vec<LootChanceEntry>, magic_find: f32) {
         .sum::<f64>();
 
     if magic_find > 0.0 {
-        apply_magic_find(entries, DropType::Extra, total_weight, magic_find);
+        // the main drops already have magic find applied, but the cutoff goes by the weights from before it
+        let cutoff_total_weight = entries.iter().map(|e| e.used_weight).sum::<f64>();
+        apply_magic_find(entries, DropType::Extra, cutoff_total_weight, magic_find);
         total_weight = entries
             .iter()
             .map(|e| e.used_weight * e.magic_find_multiplier)
@@ -143,3 +155,41 @@ fn apply_chances(entries: &mut Vec<LootChanceEntry>, total_weight: f64, drop_typ
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_chances, RngMeterData, SelectedRngMeterItem};
+    use crate::slayer::slayer_loot::read_all_loot;
+    use std::rc::Rc;
+
+    #[test]
+    fn vampire_rules_ignore_magic_find_and_meter_progress() {
+        let table = read_all_loot(&crate::app::ASSETS_DIR).remove("Vampire").unwrap().pop().unwrap();
+        let entry = table.loot.iter().find(|e| e.get_weight() > 0.0 && e.get_weight() < 10.0).unwrap();
+        let get_chance = |magic_find: f32, rng_meter_data: &RngMeterData| {
+            calculate_chances(&table, magic_find, 9, rng_meter_data)
+                .into_iter()
+                .find(|e| e.entry == *entry)
+                .unwrap()
+                .chance
+        };
+
+        let base_chance = get_chance(0.0, &RngMeterData::default());
+        assert_eq!(get_chance(300.0, &RngMeterData::default()), base_chance);
+
+        let mut rng_meter_data = RngMeterData {
+            selected_item: Some(SelectedRngMeterItem {
+                identifier: entry.to_string(),
+                required_xp: 1000,
+                highest_tier_chest_entry: Rc::clone(entry),
+                highest_boss_level: table.boss_tier,
+                lowest_tier_chest_entry: Rc::clone(entry),
+                lowest_boss_level: table.boss_tier,
+            }),
+            selected_xp: 999,
+        };
+        assert_eq!(get_chance(0.0, &rng_meter_data), base_chance);
+        rng_meter_data.selected_xp = 1000;
+        assert_eq!(get_chance(0.0, &rng_meter_data), 1.0);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::app;
+use crate::catacombs::loot_validator::IssueSeverity;
+use crate::slayer::slayer_levels::read_slayer_levels;
+use crate::slayer::slayer_loot::{DropType, LootEntry, LootTable};
+use include_dir::Dir;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+pub enum SlayerLootValidationIssue {
+    ParseError {
+        path: String,
+        error: String,
+    },
+    MismatchedPath {
+        path: String,
+        boss_type: String,
+        boss_tier: u8,
+    },
+    MissingTier {
+        boss_type: String,
+        boss_tier: u8,
+    },
+    TokenCount {
+        path: String,
+        count: usize,
+    },
+    ZeroWeight {
+        path: String,
+        entry: String,
+        drop_type: DropType,
+    },
+    DuplicateEntry {
+        path: String,
+        entry: String,
+    },
+    MalformedIdentifier {
+        path: String,
+        entry: String,
+        identifier: String,
+    },
+    InconsistentEntries {
+        boss_type: String,
+        identifier: String,
+        // (tier, display name, drop type, level requirement)
+        entries: Vec<(u8, String, DropType, u8)>,
+    },
+    InconsistentRules {
+        boss_type: String,
+        tiers: Vec<u8>,
+    },
+    MissingImage {
+        path: String,
+        entry: String,
+        possible_file_names: Vec<String>,
+    },
+}
+
+impl SlayerLootValidationIssue {
+    pub fn get_severity(&self) -> IssueSeverity {
+        match self {
+            SlayerLootValidationIssue::MissingImage { .. } => IssueSeverity::Warning,
+            _ => IssueSeverity::Error,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.get_severity() == IssueSeverity::Error
+    }
+}
+
+impl Display for SlayerLootValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlayerLootValidationIssue::ParseError { path, error } => write!(f, "{path}: failed to parse: {error}"),
+            SlayerLootValidationIssue::MismatchedPath { path, boss_type, boss_tier } => write!(
+                f,
+                "{path}: file is for the tier {boss_tier} {boss_type} boss, which doesn't match where it is"
+            ),
+            SlayerLootValidationIssue::MissingTier { boss_type, boss_tier } => {
+                write!(f, "{boss_type}: there's no loot table for tier {boss_tier}")
+            }
+            SlayerLootValidationIssue::TokenCount { path, count } => {
+                write!(f, "{path}: loot table should have exactly one Token entry, but has {count}")
+            }
+            SlayerLootValidationIssue::ZeroWeight { path, entry, drop_type } => {
+                write!(f, "{path}: {entry} is a {drop_type:?} drop with no weight, so it can never drop")
+            }
+            SlayerLootValidationIssue::DuplicateEntry { path, entry } => write!(f, "{path}: {entry} is listed more than once"),
+            SlayerLootValidationIssue::MalformedIdentifier { path, entry, identifier } => write!(
+                f,
+                "{path}: {entry} has a malformed identifier ({identifier}), so it won't match any prices"
+            ),
+            SlayerLootValidationIssue::InconsistentEntries { boss_type, identifier, entries } => write!(
+                f,
+                "{boss_type}: {identifier} is listed differently across tiers ({})",
+                entries
+                    .iter()
+                    .map(|(tier, name, drop_type, level)| format!("tier {tier}: {name}, {drop_type:?}, level {level}"))
+                    .collect::<Vec<String>>()
+                    .join("; ")
+            ),
+            SlayerLootValidationIssue::InconsistentRules { boss_type, tiers } => write!(
+                f,
+                "{boss_type}: the rules of tier(s) {} don't match tier 1",
+                tiers.iter().map(|tier| tier.to_string()).collect::<Vec<String>>().join(", ")
+            ),
+            SlayerLootValidationIssue::MissingImage { path, entry, possible_file_names } => write!(
+                f,
+                "{path}: no image for {entry} (looked for {})",
+                possible_file_names.join(", ")
+            ),
+        }
+    }
+}
+
+/// Checks the slayer loot data bundled with the app.
+pub fn validate_bundled_slayer_loot_data() -> Vec<SlayerLootValidationIssue> {
+    validate_slayer_loot_data(&app::ASSETS_DIR)
+}
+
+/// Loads every `slayer_loot/<boss type>/<tier>.json` file from the given assets directory and reports anything that
+/// would either be skipped when loading or make the calculators give wrong results. Every boss type with level data
+/// needs a loot table for each of its tiers, and any other boss type needs them from tier 1 up to its highest one.
+pub fn validate_slayer_loot_data(dir: &Dir<'_>) -> Vec<SlayerLootValidationIssue> {
+    let mut issues = Vec::new();
+
+    let image_file_names = dir
+        .find("**/*.png")
+        .unwrap()
+        .chain(dir.find("**/*.gif").unwrap())
+        .filter_map(|file| file.path().file_name().and_then(|n| n.to_str()).map(String::from))
+        .collect::<HashSet<String>>();
+
+    // key: boss type
+    let mut boss_types: BTreeMap<String, Vec<LootTable>> = BTreeMap::new();
+    for file in dir.find("slayer_loot/**/*.json").unwrap() {
+        let path = file.path().display().to_string();
+        let table = match serde_json::from_slice::<LootTable>(file.as_file().unwrap().contents()) {
+            Ok(table) => table,
+            Err(e) => {
+                issues.push(SlayerLootValidationIssue::ParseError { path, error: e.to_string() });
+                continue;
+            }
+        };
+
+        let (boss_type, boss_tier) = get_boss_from_path(file.path());
+        if table.boss_type != boss_type || Some(table.boss_tier) != boss_tier {
+            issues.push(SlayerLootValidationIssue::MismatchedPath {
+                path: path.clone(),
+                boss_type: table.boss_type.clone(),
+                boss_tier: table.boss_tier,
+            });
+        }
+
+        validate_table(&table, &path, &image_file_names, &mut issues);
+        boss_types.entry(boss_type).or_default().push(table);
+    }
+
+    let levels = read_slayer_levels();
+    for boss_type in levels.get_boss_types() {
+        boss_types.entry(boss_type.clone()).or_default();
+    }
+
+    for (boss_type, tables) in boss_types.iter_mut() {
+        tables.sort_by_key(|table| table.boss_tier);
+        let highest_tier = tables.last().map_or(0, |table| table.boss_tier);
+        let expected_tiers = levels.get(boss_type).map_or(0, |boss_levels| boss_levels.tiers.len() as u8);
+        for boss_tier in 1..=highest_tier.max(expected_tiers) {
+            if !tables.iter().any(|table| table.boss_tier == boss_tier) {
+                issues.push(SlayerLootValidationIssue::MissingTier { boss_type: boss_type.clone(), boss_tier });
+            }
+        }
+
+        validate_boss_type(boss_type, tables, &mut issues);
+    }
+
+    issues
+}
+
+/// The boss type is the folder name like when loading, and the tier the file name.
+fn get_boss_from_path(path: &Path) -> (String, Option<u8>) {
+    let boss_type = path
+        .parent()
+        .and_then(|parent| parent.file_name())
+        .and_then(|name| name.to_str())
+        .unwrap_or_default()
+        .to_string();
+    let boss_tier = path.file_stem().and_then(|stem| stem.to_str()).and_then(|stem| stem.parse().ok());
+    (boss_type, boss_tier)
+}
+
+fn validate_table(table: &LootTable, path: &str, image_file_names: &HashSet<String>, issues: &mut Vec<SlayerLootValidationIssue>) {
+    // the token drop is what every kill gives, the calculators rely on there being exactly one
+    let token_count = table.loot.iter().filter(|entry| *entry.get_drop_type() == DropType::Token).count();
+    if token_count != 1 {
+        issues.push(SlayerLootValidationIssue::TokenCount { path: path.to_string(), count: token_count });
+    }
+
+    let mut seen_entries = HashSet::new();
+    for entry in table.loot.iter() {
+        let name = entry.to_string();
+        if !seen_entries.insert(get_identifier(entry)) {
+            issues.push(SlayerLootValidationIssue::DuplicateEntry { path: path.to_string(), entry: name.clone() });
+        }
+
+        let drop_type = entry.get_drop_type();
+        if *drop_type != DropType::Token && entry.get_weight() == 0.0 {
+            issues.push(SlayerLootValidationIssue::ZeroWeight {
+                path: path.to_string(),
+                entry: name.clone(),
+                drop_type: drop_type.clone(),
+            });
+        }
+
+        let identifier = match entry.as_ref() {
+            LootEntry::Item { item, .. } => item,
+            LootEntry::Enchantment { enchantment, .. } => enchantment,
+        };
+        if !is_well_formed_identifier(identifier) {
+            issues.push(SlayerLootValidationIssue::MalformedIdentifier {
+                path: path.to_string(),
+                entry: name.clone(),
+                identifier: identifier.clone(),
+            });
+        }
+
+        let possible_file_names = entry.get_possible_file_names();
+        if !possible_file_names.iter().any(|file_name| image_file_names.contains(file_name)) {
+            issues.push(SlayerLootValidationIssue::MissingImage {
+                path: path.to_string(),
+                entry: name,
+                possible_file_names,
+            });
+        }
+    }
+}
+
+/// Expects the tables to be sorted from the lowest to the highest tier. The quantity (and so the display name) of a
+/// drop can change between tiers, but its name, drop type and level requirement can't, and neither can the rules.
+fn validate_boss_type(boss_type: &str, tables: &[LootTable], issues: &mut Vec<SlayerLootValidationIssue>) {
+    if let Some(first) = tables.first() {
+        let tiers = tables
+            .iter()
+            .filter(|table| table.rules != first.rules)
+            .map(|table| table.boss_tier)
+            .collect::<Vec<u8>>();
+        if !tiers.is_empty() {
+            issues.push(SlayerLootValidationIssue::InconsistentRules { boss_type: boss_type.to_string(), tiers });
+        }
+    }
+
+    // key: identifier
+    let mut entries: BTreeMap<String, Vec<(u8, String, DropType, u8)>> = BTreeMap::new();
+    for table in tables {
+        for entry in table.loot.iter() {
+            let name = match entry.as_ref() {
+                LootEntry::Item { item_name, .. } => item_name.clone().unwrap_or_default(),
+                LootEntry::Enchantment { .. } => String::new(),
+            };
+            entries.entry(get_identifier(entry)).or_default().push((
+                table.boss_tier,
+                name,
+                entry.get_drop_type().clone(),
+                entry.get_slayer_level_requirement(),
+            ));
+        }
+    }
+
+    for (identifier, entries) in entries {
+        let first = &entries[0];
+        if entries.iter().any(|(_, name, drop_type, level)| (name, drop_type, level) != (&first.1, &first.2, &first.3)) {
+            issues.push(SlayerLootValidationIssue::InconsistentEntries {
+                boss_type: boss_type.to_string(),
+                identifier,
+                entries,
+            });
+        }
+    }
+}
+
+fn get_identifier(entry: &LootEntry) -> String {
+    match entry {
+        LootEntry::Item { item, .. } => item.clone(),
+        LootEntry::Enchantment { enchantment, enchantment_level, .. } => format!("{enchantment} {enchantment_level}"),
+    }
+}
+
+/// Item IDs and enchantments look like `REVENANT_FLESH` or `SMITE`, some item IDs also have a `:` and a number (like
+/// `INK_SACK:3`).
+fn is_well_formed_identifier(identifier: &str) -> bool {
+    let name = identifier.split(':').next().unwrap_or_default();
+    !name.is_empty()
+        && !name.starts_with('_')
+        && !name.ends_with('_')
+        && !name.contains("__")
+        && identifier.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_' || c == ':')
+}
@@ -1,5 +1,9 @@
+use crate::bazaar::auction_client::{self, SharedAuctionClient};
+use crate::bazaar::bazaar_client::SharedBazaarClient;
+use crate::slayer::magic_find::{calculate_magic_find_effects, MagicFindDropEffect, MagicFindSettings, MagicFindSource, MAGIC_FIND_STORAGE_KEY, MAX_MAGIC_FIND};
+use crate::slayer::slayer_levels::{get_level_unlocks, read_slayer_levels, SlayerLevels, SLAYER_XP_STORAGE_KEY};
 use crate::slayer::slayer_loot;
-use crate::slayer::slayer_loot::{DropType, LootEntry, LootTable};
+use crate::slayer::slayer_loot::{DropType, LootEntry, LootRules, LootTable};
 use crate::slayer::slayer_loot_calculator::{
     calculate_chances, LootChanceEntry, RngMeterData, SelectedRngMeterItem,
 };
@@ -12,19 +16,38 @@ use std::collections::{BTreeMap, HashMap};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::rc::Rc;
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SlayerView {
+    LootTable,
+    LevelingPlanner,
+}
+
 pub struct SlayerLootPage {
+    view: SlayerView,
     boss_type: Option<String>,
     loot_table: Option<Rc<LootTable>>,
 
     slayer_level: u8,
-    magic_find: f32,
+    magic_find: MagicFindSettings,
+    magic_find_breakdown_open: bool,
+    magic_find_preset_name: String,
 
     rng_meter_data: RngMeterData,
 
+    slayer_levels: SlayerLevels,
+    // key: boss type
+    slayer_xp: BTreeMap<String, u32>,
+    target_slayer_level: u8,
+    slayer_xp_bonus_percent: f64,
+
     hashed_chances: HashMap<u64, Vec<LootChanceEntry>>,
+    // key: entry identifier
+    hashed_magic_find_effects: HashMap<u64, BTreeMap<String, MagicFindDropEffect>>,
     loot: BTreeMap<String, Vec<Rc<LootTable>>>,
 
     images: Rc<HashMap<String, TextureHandle>>,
+    bazaar: SharedBazaarClient,
+    auctions: SharedAuctionClient,
 }
 
 impl eframe::App for SlayerLootPage {
@@ -45,7 +68,13 @@ impl eframe::App for SlayerLootPage {
                         ui.separator();
                     });
 
-                    if self.boss_type.is_some() && self.loot_table.is_some() {
+                    if self.view == SlayerView::LevelingPlanner && self.boss_type.is_some() {
+                        strip.cell(|ui| {
+                            ScrollArea::both().show(ui, |ui| {
+                                self.add_leveling_planner_section(ui);
+                            });
+                        });
+                    } else if self.view == SlayerView::LootTable && self.boss_type.is_some() && self.loot_table.is_some() {
                         strip.cell(|ui| {
                             ScrollArea::both().show(ui, |ui| {
                                 let hash = self.generate_hash();
@@ -55,13 +84,24 @@ impl eframe::App for SlayerLootPage {
                                     let chest = self.loot_table.as_ref().unwrap();
                                     let new_chances = calculate_chances(
                                         chest,
-                                        self.magic_find,
+                                        self.magic_find.current.get_total(),
                                         self.slayer_level,
                                         &self.rng_meter_data,
                                     );
                                     self.hashed_chances.insert(hash, new_chances);
                                 }
 
+                                let magic_find_hash = self.generate_magic_find_effects_hash();
+                                if !self.hashed_magic_find_effects.contains_key(&magic_find_hash) {
+                                    let effects = calculate_magic_find_effects(
+                                        self.loot_table.as_ref().unwrap(),
+                                        &self.magic_find.current,
+                                        self.slayer_level,
+                                        &self.rng_meter_data,
+                                    );
+                                    self.hashed_magic_find_effects.insert(magic_find_hash, effects);
+                                }
+
                                 self.add_loot_section(ui);
                             });
                         });
@@ -73,30 +113,57 @@ impl eframe::App for SlayerLootPage {
     }
 
     /// Called by the frame work to save state before shutdown.
-    fn save(&mut self, _storage: &mut dyn eframe::Storage) {}
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, MAGIC_FIND_STORAGE_KEY, &self.magic_find);
+        eframe::set_value(storage, SLAYER_XP_STORAGE_KEY, &self.slayer_xp);
+    }
 }
 
 impl SlayerLootPage {
-    pub fn new(images: Rc<HashMap<String, TextureHandle>>) -> Self {
+    pub fn new(
+        images: Rc<HashMap<String, TextureHandle>>,
+        bazaar: SharedBazaarClient,
+        auctions: SharedAuctionClient,
+        storage: Option<&dyn eframe::Storage>,
+    ) -> Self {
         Self {
+            view: SlayerView::LootTable,
             boss_type: None,
             loot_table: None,
 
             slayer_level: 9,
-            magic_find: 0.0,
+            magic_find: storage
+                .and_then(|storage| eframe::get_value(storage, MAGIC_FIND_STORAGE_KEY))
+                .unwrap_or_default(),
+            magic_find_breakdown_open: false,
+            magic_find_preset_name: String::new(),
             rng_meter_data: Default::default(),
 
+            slayer_levels: read_slayer_levels(),
+            slayer_xp: storage
+                .and_then(|storage| eframe::get_value(storage, SLAYER_XP_STORAGE_KEY))
+                .unwrap_or_default(),
+            target_slayer_level: 9,
+            slayer_xp_bonus_percent: 0.0,
+
             hashed_chances: HashMap::new(),
+            hashed_magic_find_effects: HashMap::new(),
 
             loot: slayer_loot::read_all_loot(&app::ASSETS_DIR)
                 .into_iter()
                 .map(|(k, v)| (k, v.into_iter().map(Rc::new).collect()))
                 .collect(),
             images,
+            bazaar,
+            auctions,
         }
     }
 
     fn add_regular_settings_section(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.view, SlayerView::LootTable, "Loot Table");
+            ui.selectable_value(&mut self.view, SlayerView::LevelingPlanner, "Leveling Planner");
+        });
         ui.heading("Options");
 
         Grid::new("config_grid")
@@ -106,12 +173,26 @@ impl SlayerLootPage {
             .show(ui, |ui| {
                 self.add_boss_type_options(ui);
                 ui.end_row();
+
+                if self.view == SlayerView::LevelingPlanner {
+                    self.add_leveling_options(ui);
+                    return;
+                }
+
                 self.add_boss_tier_options(ui);
                 ui.end_row();
                 self.add_slayer_level_option(ui);
                 ui.end_row();
-                self.add_magic_find_options(ui);
-                ui.end_row();
+                let rules = self.get_loot_rules().cloned().unwrap_or_default();
+                if rules.magic_find {
+                    self.add_magic_find_options(ui);
+                    ui.end_row();
+                }
+                if let Some(note) = rules.describe() {
+                    ui.label("");
+                    ui.add(Label::new(note).wrap_mode(TextWrapMode::Wrap));
+                    ui.end_row();
+                }
 
                 self.add_rng_meter_section(ui);
             });
@@ -123,11 +204,199 @@ impl SlayerLootPage {
             ui.label("Magic Find: ");
         });
 
-        ui.add(
-            egui::DragValue::new(&mut self.magic_find)
-                .speed(0.5)
-                .range(0.0..=900.0),
-        );
+        ui.horizontal(|ui| {
+            let total = self.magic_find.current.get_total();
+            ui.label(RichText::new(format!("{total}")).color(Color32::from_rgb(85, 255, 255)));
+            ui.toggle_value(&mut self.magic_find_breakdown_open, "Edit Sources");
+        });
+
+        if !self.magic_find_breakdown_open {
+            return;
+        }
+
+        for source in MagicFindSource::ALL {
+            ui.end_row();
+            ui.label(format!("  {}:", source.get_name())).on_hover_text(source.get_description());
+            let mut magic_find = self.magic_find.current.get(source);
+            if ui.add(egui::DragValue::new(&mut magic_find).speed(0.5).range(0.0..=MAX_MAGIC_FIND)).changed() {
+                self.magic_find.current.set(source, magic_find);
+            }
+        }
+        ui.end_row();
+
+        ui.label("  Presets:");
+        ui.horizontal(|ui| {
+            let mut preset_to_load = None;
+            let mut preset_to_delete = None;
+            egui::ComboBox::from_id_salt("magic_find_presets")
+                .selected_text("Load")
+                .show_ui(ui, |ui| {
+                    for preset in self.magic_find.presets.iter() {
+                        ui.horizontal(|ui| {
+                            let text = format!("{} ({} MF)", preset.name, preset.breakdown.get_total());
+                            if ui.selectable_label(false, text).clicked() {
+                                preset_to_load = Some(preset.name.clone());
+                            }
+                            if ui.small_button("x").on_hover_text("Delete").clicked() {
+                                preset_to_delete = Some(preset.name.clone());
+                            }
+                        });
+                    }
+                });
+            if let Some(name) = preset_to_load {
+                self.magic_find.load_preset(&name);
+                self.magic_find_preset_name = name;
+            }
+            if let Some(name) = preset_to_delete {
+                self.magic_find.delete_preset(&name);
+            }
+
+            ui.add(egui::TextEdit::singleline(&mut self.magic_find_preset_name).desired_width(100.0).hint_text("Preset name"));
+            let name = self.magic_find_preset_name.trim().to_string();
+            if ui.add_enabled(!name.is_empty(), egui::Button::new("Save")).clicked() {
+                self.magic_find.save_preset(name);
+            }
+        });
+    }
+
+    fn add_leveling_options(&mut self, ui: &mut Ui) {
+        let Some(boss_type) = self.boss_type.clone() else {
+            return;
+        };
+        let Some(boss_levels) = self.slayer_levels.get(&boss_type) else {
+            ui.label("");
+            ui.label(RichText::new("There's no leveling data for this boss yet.").color(Color32::GRAY));
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "slayer_level.png");
+            ui.label("Slayer XP: ");
+        });
+        ui.horizontal(|ui| {
+            let xp = self.slayer_xp.entry(boss_type.clone()).or_insert(0);
+            ui.add(egui::DragValue::new(xp).speed(10.0).range(0..=u32::MAX));
+
+            let level = boss_levels.get_level(*xp);
+            ui.label(RichText::new(format!("Level {level}")).color(Color32::from_rgb(85, 255, 255)));
+            if ui.button("Use For Loot").on_hover_text("Sets the slayer level used by the loot table").clicked() {
+                self.slayer_level = level;
+            }
+        });
+        ui.end_row();
+
+        ui.horizontal(|ui| {
+            images::add_image(&self.images, ui, "slayer_tiers.png");
+            ui.label("Target Level: ");
+        });
+        let max_level = boss_levels.get_max_level();
+        self.target_slayer_level = self.target_slayer_level.clamp(1, max_level);
+        ui.add(egui::Slider::new(&mut self.target_slayer_level, 1..=max_level).step_by(1.0));
+        ui.end_row();
+
+        ui.label("XP Bonus: ").on_hover_text("Extra slayer XP per boss, like from Aatrox's perk");
+        ui.add(egui::DragValue::new(&mut self.slayer_xp_bonus_percent).speed(1.0).range(0.0..=100.0).suffix("%"));
+    }
+
+    fn add_leveling_planner_section(&mut self, ui: &mut Ui) {
+        let boss_type = self.boss_type.as_ref().unwrap();
+        let Some(boss_levels) = self.slayer_levels.get(boss_type) else {
+            return;
+        };
+        let xp = self.slayer_xp.get(boss_type).copied().unwrap_or(0);
+        let current_level = boss_levels.get_level(xp);
+        let target_level = self.target_slayer_level;
+
+        ui.label(RichText::new(&self.slayer_levels.note).color(Color32::GRAY));
+        ui.separator();
+
+        if current_level >= target_level {
+            ui.label(format!("Already at {boss_type} Slayer {}.", roman::to(current_level as i32).unwrap_or_default()));
+            return;
+        }
+
+        let needed_xp = boss_levels.get_xp_for_level(target_level) - xp;
+        ui.label(format!(
+            "{} XP to go from level {current_level} to level {target_level}.",
+            needed_xp.to_formatted_string(&Locale::en)
+        ));
+
+        let text_height = TextStyle::Body
+            .resolve(ui.style())
+            .size
+            .max(ui.spacing().interact_size.y);
+
+        let plans = boss_levels.plan_leveling(xp, target_level, self.slayer_xp_bonus_percent);
+        let cheapest_cost = plans.iter().map(|plan| plan.cost).min().unwrap_or(0);
+        let currency = &boss_levels.currency;
+        TableBuilder::new(ui)
+            .id_salt("leveling_plan")
+            .striped(true)
+            .resizable(false)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .columns(Column::auto(), 4)
+            .vscroll(false)
+            .header(20.0, |mut header| {
+                header.col(|ui| {
+                    ui.strong("Tier");
+                });
+                header.col(|ui| {
+                    ui.strong("XP Per Boss");
+                });
+                header.col(|ui| {
+                    ui.strong("Bosses Needed");
+                });
+                header.col(|ui| {
+                    ui.strong(format!("{currency} Cost"));
+                });
+            })
+            .body(|mut body| {
+                for plan in plans.iter() {
+                    body.row(text_height, |mut row| {
+                        row.col(|ui| {
+                            ui.label(format!("Tier {}", roman::to(plan.tier as i32).unwrap_or_default()));
+                        });
+                        row.col(|ui| {
+                            let text = format!("{:.2}", plan.xp_per_boss);
+                            ui.label(RichText::new(text.trim_end_matches('0').trim_end_matches('.')).color(Color32::from_rgb(85, 255, 255)));
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(plan.bosses.to_formatted_string(&Locale::en)).color(Color32::from_rgb(85, 255, 85)));
+                        });
+                        row.col(|ui| {
+                            ui.label(RichText::new(plan.cost.to_formatted_string(&Locale::en)).color(Color32::from_rgb(255, 170, 0)));
+                            if plan.cost == cheapest_cost {
+                                ui.label(RichText::new("(cheapest)").color(Color32::GRAY));
+                            }
+                        });
+                    });
+                }
+            });
+
+        ui.separator();
+        ui.heading("Unlocks");
+
+        let unlocks = get_level_unlocks(self.loot.get(boss_type).unwrap(), current_level, target_level);
+        if unlocks.is_empty() {
+            ui.label("No new drops unlock along the way.");
+        }
+        for (level, entries) in unlocks.iter() {
+            ui.label(RichText::new(format!(
+                "Level {level} ({} XP)",
+                boss_levels.get_xp_for_level(*level).to_formatted_string(&Locale::en)
+            )).strong());
+            for (entry, lowest_tier) in entries.iter() {
+                ui.horizontal(|ui| {
+                    ui.add_space(15.0);
+                    images::add_first_valid_image(&self.images, ui, entry.get_possible_file_names());
+                    ui.hyperlink_to(entry.to_string(), entry.get_wiki_page_name());
+                    ui.label(
+                        RichText::new(format!("({:?}, Tier {}+)", entry.get_drop_type(), roman::to(*lowest_tier as i32).unwrap_or_default()))
+                            .color(Color32::GRAY),
+                    );
+                });
+            }
+        }
     }
 
     fn add_boss_type_options(&mut self, ui: &mut Ui) {
@@ -443,11 +712,17 @@ impl SlayerLootPage {
     }
 
     fn add_loot_section(&mut self, ui: &mut Ui) {
+        let system_time = web_time::Instant::now();
+        self.bazaar.borrow_mut().fetch_if_missing(system_time);
+        let ctx = ui.ctx().clone();
+        auction_client::add_auction_refresh_button(&self.auctions, &ctx, ui, system_time);
         let chances = self.get_chances();
         if chances.is_none() {
             return;
         }
         let chances = chances.unwrap();
+        let magic_find_effects = self.hashed_magic_find_effects.get(&self.generate_magic_find_effects_hash());
+        let show_magic_find = self.get_loot_rules().map_or(true, |rules| rules.magic_find);
 
         let text_height = TextStyle::Body
             .resolve(ui.style())
@@ -455,7 +730,7 @@ impl SlayerLootPage {
             .max(ui.spacing().interact_size.y);
 
         let available_height = ui.available_height();
-        let table = TableBuilder::new(ui)
+        let mut table = TableBuilder::new(ui)
             .striped(true)
             .resizable(false)
             .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
@@ -463,7 +738,11 @@ impl SlayerLootPage {
             .column(Column::auto())
             .column(Column::auto())
             .column(Column::auto().clip(false))
-            .column(Column::auto())
+            .column(Column::auto());
+        if show_magic_find {
+            table = table.column(Column::auto());
+        }
+        table = table
             .drag_to_scroll(true)
             .min_scrolled_height(0.0)
             .max_scroll_height(available_height);
@@ -485,6 +764,11 @@ impl SlayerLootPage {
                 header.col(|ui| {
                     ui.strong("Average Chance");
                 });
+                if show_magic_find {
+                    header.col(|ui| {
+                        ui.strong("Magic Find");
+                    });
+                }
             })
             .body(|mut body| {
                 for entry in chances.iter() {
@@ -506,7 +790,14 @@ impl SlayerLootPage {
 
                             let text = entry.to_string();
                             let page_url = entry.get_wiki_page_name();
-                            ui.hyperlink_to(text, page_url);
+                            let response = ui.hyperlink_to(text, page_url);
+                            auction_client::add_item_price_hover(
+                                response,
+                                &self.bazaar,
+                                &self.auctions,
+                                Some(&entry.get_bazaar_id()),
+                                entry.get_auction_id().as_deref(),
+                            );
                         });
 
                         row.col(|ui| {
@@ -536,6 +827,13 @@ impl SlayerLootPage {
                         row.col(|ui| {
                             fill_in_chance_column(ui, chance);
                         });
+
+                        if show_magic_find {
+                            row.col(|ui| {
+                                let effect = magic_find_effects.and_then(|effects| effects.get(&entry.to_string()));
+                                fill_in_magic_find_column(ui, effect);
+                            });
+                        }
                     });
                 }
 
@@ -550,12 +848,31 @@ impl SlayerLootPage {
         self.boss_type.hash(&mut hasher);
         self.loot_table.hash(&mut hasher);
         self.slayer_level.hash(&mut hasher);
-        self.magic_find.to_string().hash(&mut hasher);
+        self.magic_find.current.get_total().to_string().hash(&mut hasher);
         self.rng_meter_data.selected_xp.hash(&mut hasher);
         self.rng_meter_data.selected_item.hash(&mut hasher);
         hasher.finish()
     }
 
+    fn generate_magic_find_effects_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.generate_hash().hash(&mut hasher);
+        for (source, magic_find) in self.magic_find.current.iter() {
+            source.hash(&mut hasher);
+            magic_find.to_string().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// The rules of the selected loot table, or of the boss type's highest tier if no tier is selected yet.
+    fn get_loot_rules(&self) -> Option<&LootRules> {
+        if let Some(table) = &self.loot_table {
+            return Some(&table.rules);
+        }
+        let boss_type = self.boss_type.as_ref()?;
+        self.loot.get(boss_type).and_then(|tables| tables.last()).map(|table| &table.rules)
+    }
+
     fn get_chances(&self) -> Option<&Vec<LootChanceEntry>> {
         let hash = self.generate_hash();
         self.hashed_chances.get(&hash)
@@ -576,6 +893,29 @@ fn fill_in_chance_column(ui: &mut Ui, chance: f64) {
     ui.label(" runs)");
 }
 
+/// The total change in chance from magic find, with what each source adds on its own on hover.
+fn fill_in_magic_find_column(ui: &mut Ui, effect: Option<&MagicFindDropEffect>) {
+    let Some(effect) = effect else {
+        return;
+    };
+    if !effect.affected {
+        ui.label(RichText::new("Not affected").color(Color32::GRAY))
+            .on_hover_text("Magic find only applies to drops under 5% of their loot table's weight");
+        return;
+    }
+
+    let format_increase = |chance: f64| format!("+{:.4}%", (chance - effect.base_chance) * 100.0);
+    let response = ui.label(RichText::new(format_increase(effect.chance)).color(Color32::from_rgb(85, 255, 255)));
+    if !effect.source_chances.is_empty() {
+        response.on_hover_ui(|ui| {
+            ui.label(format!("Without Magic Find: {:.4}%", effect.base_chance * 100.0));
+            for (source, chance) in effect.source_chances.iter() {
+                ui.label(format!("{}: {}", source.get_name(), format_increase(*chance)));
+            }
+        });
+    }
+}
+
 fn match_loot_type_or_none(
     chest: &Rc<LootTable>,
     others: &Vec<Rc<LootTable>>,
@@ -0,0 +1,94 @@
+use cata_calc::{get_pet_auction_id, load_auctions_blocking, parse_auction_responses, read_item_bytes, JsonAuctionSource};
+
+const AUCTIONS_SAMPLE: &str = include_str!("data/auctions_sample.json");
+const AUCTIONS_ENDED_SAMPLE: &str = include_str!("data/auctions_ended_sample.json");
+
+fn sample_responses() -> Vec<String> {
+    vec![AUCTIONS_SAMPLE.to_string(), AUCTIONS_ENDED_SAMPLE.to_string()]
+}
+
+#[test]
+fn lowest_bin_ignores_regular_auctions() {
+    let prices = parse_auction_responses(&sample_responses()).unwrap();
+
+    let handle = &prices["NECRON_HANDLE"];
+    assert_eq!(handle.lowest_bin, Some(560_000_000.0));
+    assert_eq!(handle.bin_count, 2);
+    // stacks are priced per item
+    assert_eq!(prices["RECOMBOBULATOR_3000"].lowest_bin, Some(800_000.0));
+}
+
+#[test]
+fn median_sold_counts_every_ended_auction() {
+    let prices = parse_auction_responses(&sample_responses()).unwrap();
+
+    let handle = &prices["NECRON_HANDLE"];
+    assert_eq!(handle.median_sold, Some(550_000_000.0));
+    assert_eq!(handle.sold_count, 3);
+
+    // nothing listed right now, so the sales are all there is to go by
+    let giants_sword = &prices["GIANTS_SWORD"];
+    assert_eq!(giants_sword.lowest_bin, None);
+    assert_eq!(giants_sword.median_sold, Some(35_000_000.0));
+    assert_eq!(giants_sword.get_price(), Some(35_000_000.0));
+}
+
+#[test]
+fn pets_are_keyed_by_type_and_tier() {
+    let prices = parse_auction_responses(&sample_responses()).unwrap();
+
+    let id = get_pet_auction_id("spirit", "legendary");
+    assert_eq!(id, "PET_SPIRIT_LEGENDARY");
+    assert_eq!(prices[&id].lowest_bin, Some(2_500_000.0));
+    assert!(!prices.contains_key("PET"));
+}
+
+#[test]
+fn unreadable_item_bytes_are_skipped() {
+    assert_eq!(read_item_bytes("not base64!"), None);
+    // valid base64, but not gzip
+    assert_eq!(read_item_bytes("aGVsbG8="), None);
+}
+
+#[test]
+fn broken_json_source_resolves_to_none() {
+    let source = JsonAuctionSource::new("broken".to_string(), vec![AUCTIONS_SAMPLE.to_string(), "{ not json".to_string()]);
+    assert!(load_auctions_blocking(&source).is_none());
+}
+
+#[test]
+fn huge_array_lengths_are_skipped() {
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    // { i: [ { data: long array of i32::MAX longs } ] }, without the longs
+    let mut nbt = vec![10, 0, 0, 9, 0, 1, b'i', 10, 0, 0, 0, 1];
+    nbt.extend([12, 0, 4, b'd', b'a', b't', b'a', 0x7f, 0xff, 0xff, 0xff]);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&nbt).unwrap();
+    let item_bytes = BASE64_STANDARD.encode(encoder.finish().unwrap());
+
+    assert_eq!(cata_calc::read_inventory_bytes(&item_bytes), None);
+}
+
+#[test]
+fn deeply_nested_item_bytes_are_skipped() {
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    // { i: [ { x: [ [ [ ... ] ] ] } ] }, nested far deeper than any item and deep enough to overflow the stack
+    let mut nbt = vec![10, 0, 0, 9, 0, 1, b'i', 10, 0, 0, 0, 1, 9, 0, 1, b'x'];
+    for _ in 0..100_000 {
+        nbt.extend([9, 0, 0, 0, 1]);
+    }
+    nbt.extend([1, 0, 0, 0, 0]);
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&nbt).unwrap();
+    let item_bytes = BASE64_STANDARD.encode(encoder.finish().unwrap());
+
+    assert_eq!(cata_calc::read_inventory_bytes(&item_bytes), None);
+}
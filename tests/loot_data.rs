@@ -0,0 +1,46 @@
+#[test]
+fn bundled_loot_data_has_no_errors() {
+    let errors = cata_calc::validate_bundled_loot_data()
+        .into_iter()
+        .filter(|issue| issue.is_error())
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    assert!(errors.is_empty(), "loot data errors:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn broken_loot_data_is_reported() {
+    use include_dir::{Dir, DirEntry, File};
+
+    const BROKEN_CHEST: &str = r#"{
+        "floor": 1,
+        "master_mode": false,
+        "chest_type": "Wood",
+        "base_cost": 0,
+        "base_quality": 110,
+        "loot": [
+            { "item": "BONZO_STAFF", "item_name": null, "extra_chest_cost": 0, "quality": 100, "weight": 1 },
+            { "item": "BONZO_STAFF", "item_name": null, "extra_chest_cost": 0, "quality": 100, "weight": 1 },
+            { "essence_type": "UNDEAD", "essence_amount": 1, "extra_chest_cost": 0, "quality": 5, "weight": 0 }
+        ]
+    }"#;
+
+    static DIR: Dir<'static> = Dir::new("", &[
+        DirEntry::Dir(Dir::new("dungeon_loot", &[
+            DirEntry::Dir(Dir::new("dungeon_loot/f1", &[
+                DirEntry::File(File::new("dungeon_loot/f1/wood.json", BROKEN_CHEST.as_bytes())),
+                DirEntry::File(File::new("dungeon_loot/f1/gold.json", b"{ not json")),
+            ])),
+        ])),
+    ]);
+
+    let issues = cata_calc::validate_loot_data(&DIR);
+    let has_issue = |predicate: fn(&cata_calc::LootValidationIssue) -> bool| issues.iter().any(predicate);
+
+    assert!(has_issue(|i| matches!(i, cata_calc::LootValidationIssue::ParseError { .. })));
+    assert!(has_issue(|i| matches!(i, cata_calc::LootValidationIssue::DuplicateEntry { .. })));
+    assert!(has_issue(|i| matches!(i, cata_calc::LootValidationIssue::MissingImage { .. })));
+    assert!(has_issue(|i| matches!(i, cata_calc::LootValidationIssue::InvalidEssenceEntry { .. })));
+    assert!(has_issue(|i| matches!(i, cata_calc::LootValidationIssue::MissingEssenceEntry { .. })));
+}
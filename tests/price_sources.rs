@@ -0,0 +1,83 @@
+use cata_calc::{load_prices_blocking, JsonPriceSource, ManualPriceSource, PriceOverride, PriceSource, UrlPriceSource};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+const BAZAAR_SAMPLE: &str = include_str!("data/bazaar_sample.json");
+
+fn sample_source() -> Box<dyn PriceSource> {
+    Box::new(JsonPriceSource::new("sample".to_string(), BAZAAR_SAMPLE.to_string()))
+}
+
+#[test]
+fn json_source_loads_saved_response() {
+    let prices = load_prices_blocking(sample_source().as_ref()).expect("sample should parse");
+
+    assert_eq!(prices.len(), 2);
+    let griffin = &prices["SHARD_GRIFFIN"].quick_status;
+    assert_eq!(griffin.buy_price, 105000.0);
+    assert_eq!(griffin.sell_price, 98000.0);
+    assert_eq!(griffin.buy_moving_week, 410);
+}
+
+#[test]
+fn broken_json_source_resolves_to_none() {
+    let source = JsonPriceSource::new("broken".to_string(), "{ not json".to_string());
+    assert!(load_prices_blocking(&source).is_none());
+}
+
+#[test]
+fn manual_overrides_replace_and_add_prices() {
+    let overrides = ManualPriceSource::parse_overrides(
+        r#"{
+            "SHARD_GRIFFIN": { "buy_price": 1.0, "sell_price": 2.0 },
+            "NOT_ON_BAZAAR": { "buy_price": 3.0, "sell_price": 4.0 }
+        }"#,
+    )
+    .unwrap();
+    let source = ManualPriceSource { base: sample_source(), overrides };
+    let prices = load_prices_blocking(&source).unwrap();
+
+    assert_eq!(prices.len(), 3);
+    let griffin = &prices["SHARD_GRIFFIN"].quick_status;
+    assert_eq!((griffin.buy_price, griffin.sell_price), (1.0, 2.0));
+    // everything else about an overridden product is kept
+    assert_eq!(griffin.buy_moving_week, 410);
+    let added = &prices["NOT_ON_BAZAAR"].quick_status;
+    assert_eq!((added.buy_price, added.sell_price), (3.0, 4.0));
+    assert_eq!(prices["ESSENCE_UNDEAD"].quick_status.sell_price, 1200.5);
+}
+
+#[test]
+fn manual_overrides_fail_with_their_base() {
+    let mut overrides = BTreeMap::new();
+    overrides.insert("SHARD_GRIFFIN".to_string(), PriceOverride { buy_price: 1.0, sell_price: 1.0 });
+    let source = ManualPriceSource {
+        base: Box::new(JsonPriceSource::new("broken".to_string(), String::new())),
+        overrides,
+    };
+    assert!(load_prices_blocking(&source).is_none());
+}
+
+#[test]
+fn url_source_loads_from_local_stand_in() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/skyblock/bazaar", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = [0; 4096];
+        let _ = stream.read(&mut request);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{BAZAAR_SAMPLE}",
+            BAZAAR_SAMPLE.len()
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+    });
+
+    let source = UrlPriceSource { url: url.clone() };
+    assert_eq!(source.get_name(), url);
+    let prices = load_prices_blocking(&source).expect("stand-in should be reachable");
+    server.join().unwrap();
+
+    assert_eq!(prices["ESSENCE_UNDEAD"].quick_status.buy_price, 1350.0);
+}
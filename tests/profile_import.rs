@@ -0,0 +1,42 @@
+use cata_calc::{parse_dungeon_profile, DungeonProfile};
+
+const PROFILE_SAMPLE: &str = include_str!("data/profile_sample.json");
+const MEMBER_UUID: &str = "01234567-89ab-cdef-0123-456789abcdef";
+
+#[test]
+fn selected_profile_member_is_imported() {
+    let profile = parse_dungeon_profile(PROFILE_SAMPLE, Some(MEMBER_UUID)).unwrap();
+
+    // the ring beats the talisman, and the regular accessory is ignored
+    assert_eq!(profile.treasure_accessory_multiplier, Some(1.02));
+    // perk level 3 is Boss Luck III
+    assert_eq!(profile.boss_luck_increase, Some(5));
+    assert_eq!(profile.catacombs_box_attribute_increase, Some(7));
+    assert_eq!(profile.rng_meter_xp.get("f7"), Some(&120000));
+    assert_eq!(profile.rng_meter_xp.get("f5"), Some(&3500));
+    assert_eq!(profile.rng_meter_xp.get("m7"), Some(&640000));
+}
+
+#[test]
+fn shared_profiles_need_a_member_uuid() {
+    assert!(parse_dungeon_profile(PROFILE_SAMPLE, None).is_err());
+    assert!(parse_dungeon_profile(PROFILE_SAMPLE, Some("not a member")).is_err());
+}
+
+#[test]
+fn missing_fields_are_left_unchanged() {
+    let profile = parse_dungeon_profile(r#"{ "player_data": { "perks": {} } }"#, None).unwrap();
+    assert_eq!(
+        profile,
+        DungeonProfile {
+            boss_luck_increase: Some(0),
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn api_errors_are_reported() {
+    let error = parse_dungeon_profile(r#"{ "success": false, "cause": "Invalid API key" }"#, None).unwrap_err();
+    assert!(error.contains("Invalid API key"));
+}
@@ -0,0 +1,79 @@
+use cata_calc::SlayerLootValidationIssue;
+
+#[test]
+fn bundled_slayer_loot_data_has_no_errors() {
+    let errors = cata_calc::validate_bundled_slayer_loot_data()
+        .into_iter()
+        .filter(|issue| issue.is_error())
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    assert!(errors.is_empty(), "slayer loot data errors:\n{}", errors.join("\n"));
+}
+
+#[test]
+fn every_bundled_slayer_drop_has_an_image() {
+    let missing_images = cata_calc::validate_bundled_slayer_loot_data()
+        .into_iter()
+        .filter(|issue| matches!(issue, SlayerLootValidationIssue::MissingImage { .. }))
+        .map(|issue| issue.to_string())
+        .collect::<Vec<String>>();
+
+    assert!(missing_images.is_empty(), "slayer drops without an image:\n{}", missing_images.join("\n"));
+}
+
+#[test]
+fn broken_slayer_loot_data_is_reported() {
+    use include_dir::{Dir, DirEntry, File};
+
+    const BROKEN_TABLE: &str = r#"{
+        "boss_type": "Zombie",
+        "boss_tier": 2,
+        "loot": [
+            { "item": "REVENANT_FLESH", "item_name": "Revenant Flesh", "level_requirement": 0, "loot_table": "Token", "weight": 100, "quantity_range": "1" },
+            { "item": "FOUL_FLESH", "item_name": "Foul Flesh", "level_requirement": 0, "loot_table": "Main", "weight": 0, "quantity_range": "1" },
+            { "item": "FOUL_FLESH", "item_name": "Foul Flesh", "level_requirement": 0, "loot_table": "Main", "weight": 10, "quantity_range": "2" },
+            { "item": "Undead Catalyst", "item_name": "Undead Catalyst", "level_requirement": 3, "loot_table": "Main", "weight": 10, "quantity_range": "1" }
+        ]
+    }"#;
+    const TOKENLESS_TABLE: &str = r#"{
+        "boss_type": "Zombie",
+        "boss_tier": 3,
+        "rules": { "magic_find": false },
+        "loot": [
+            { "item": "FOUL_FLESH", "item_name": "Foul Flesh", "level_requirement": 1, "loot_table": "Extra", "weight": 10, "quantity_range": "1" }
+        ]
+    }"#;
+
+    // tier 1 is missing, and tier 4 can't be parsed
+    static DIR: Dir<'static> = Dir::new("", &[
+        DirEntry::Dir(Dir::new("slayer_loot", &[
+            DirEntry::Dir(Dir::new("slayer_loot/Zombie", &[
+                DirEntry::File(File::new("slayer_loot/Zombie/2.json", BROKEN_TABLE.as_bytes())),
+                DirEntry::File(File::new("slayer_loot/Zombie/3.json", TOKENLESS_TABLE.as_bytes())),
+                DirEntry::File(File::new("slayer_loot/Zombie/4.json", b"{ not json")),
+            ])),
+            DirEntry::Dir(Dir::new("slayer_loot/Spider", &[
+                DirEntry::File(File::new("slayer_loot/Spider/1.json", TOKENLESS_TABLE.as_bytes())),
+            ])),
+        ])),
+    ]);
+
+    let issues = cata_calc::validate_slayer_loot_data(&DIR);
+    let has_issue = |predicate: &dyn Fn(&SlayerLootValidationIssue) -> bool| issues.iter().any(predicate);
+
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::ParseError { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::MismatchedPath { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::TokenCount { count: 0, .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::ZeroWeight { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::DuplicateEntry { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::MalformedIdentifier { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::InconsistentEntries { .. })));
+    assert!(has_issue(&|i| matches!(i, SlayerLootValidationIssue::InconsistentRules { .. })));
+    for boss_tier in [1, 4, 5] {
+        assert!(has_issue(&|i| *i == SlayerLootValidationIssue::MissingTier { boss_type: "Zombie".to_string(), boss_tier }));
+    }
+    // boss types without any files are missing every tier
+    assert!(has_issue(&|i| *i == SlayerLootValidationIssue::MissingTier { boss_type: "Vampire".to_string(), boss_tier: 1 }));
+    assert!(!has_issue(&|i| *i == SlayerLootValidationIssue::MissingTier { boss_type: "Zombie".to_string(), boss_tier: 2 }));
+}